@@ -21,7 +21,7 @@ use chainx_runtime::constants::currency::DOLLARS;
 use dev_runtime::constants::{currency::DOLLARS as DEV_DOLLARS, time::DAYS as DEV_DAYS};
 use xp_assets_registrar::Chain;
 use xp_protocol::{NetworkType, PCX, PCX_DECIMALS, X_BTC};
-use xpallet_gateway_bitcoin::{BtcParams, BtcTxVerifier};
+use xpallet_gateway_bitcoin::BtcTxVerifier;
 use xpallet_gateway_common::types::TrusteeInfoConfig;
 
 use crate::genesis::assets::{genesis_assets, init_assets, pcx, AssetParams};
@@ -246,6 +246,78 @@ pub fn local_testnet_config() -> Result<DevChainSpec, String> {
     ))
 }
 
+/// Development chain which relays a local `bitcoind -regtest` node.
+pub fn regtest_config() -> Result<DevChainSpec, String> {
+    let wasm_binary =
+        dev::WASM_BINARY.ok_or_else(|| "Development wasm binary not available".to_string())?;
+
+    let endowed_balance = 50 * DEV_DOLLARS;
+    let constructor = move || {
+        build_genesis(
+            wasm_binary,
+            vec![authority_keys_from_seed("Alice")],
+            get_account_id_from_seed::<sr25519::Public>("Alice"),
+            get_account_id_from_seed::<sr25519::Public>("vesting"),
+            genesis_assets(),
+            endowed_gen![
+                ("Alice", endowed_balance),
+                ("Bob", endowed_balance),
+                ("Alice//stash", endowed_balance),
+                ("Bob//stash", endowed_balance),
+            ],
+            btc_genesis_params(include_str!("res/btc_genesis_params_regtest.json")),
+            crate::genesis::bitcoin::local_testnet_trustees(),
+        )
+    };
+    Ok(DevChainSpec::from_genesis(
+        "Regtest",
+        "regtest",
+        ChainType::Development,
+        constructor,
+        vec![],
+        None,
+        Some("chainx-regtest"),
+        Some(as_properties(NetworkType::Testnet)),
+        Default::default(),
+    ))
+}
+
+/// Development chain which relays the default Bitcoin signet.
+pub fn signet_config() -> Result<DevChainSpec, String> {
+    let wasm_binary =
+        dev::WASM_BINARY.ok_or_else(|| "Development wasm binary not available".to_string())?;
+
+    let endowed_balance = 50 * DEV_DOLLARS;
+    let constructor = move || {
+        build_genesis(
+            wasm_binary,
+            vec![authority_keys_from_seed("Alice")],
+            get_account_id_from_seed::<sr25519::Public>("Alice"),
+            get_account_id_from_seed::<sr25519::Public>("vesting"),
+            genesis_assets(),
+            endowed_gen![
+                ("Alice", endowed_balance),
+                ("Bob", endowed_balance),
+                ("Alice//stash", endowed_balance),
+                ("Bob//stash", endowed_balance),
+            ],
+            btc_genesis_params(include_str!("res/btc_genesis_params_signet.json")),
+            crate::genesis::bitcoin::local_testnet_trustees(),
+        )
+    };
+    Ok(DevChainSpec::from_genesis(
+        "Signet",
+        "signet",
+        ChainType::Development,
+        constructor,
+        vec![],
+        None,
+        Some("chainx-signet"),
+        Some(as_properties(NetworkType::Testnet)),
+        Default::default(),
+    ))
+}
+
 pub fn mainnet_config() -> Result<ChainXChainSpec, String> {
     ChainXChainSpec::from_json_bytes(&include_bytes!("./res/chainx.json")[..])
 }
@@ -401,13 +473,8 @@ fn build_genesis(
             confirmation_number: bitcoin.confirmation_number,
             genesis_hash: bitcoin.hash(),
             genesis_info: (bitcoin.header(), bitcoin.height),
-            params_info: BtcParams::new(
-                486604799,            // max_bits
-                2 * 60 * 60,          // block_max_future
-                2 * 7 * 24 * 60 * 60, // target_timespan_seconds
-                10 * 60,              // target_spacing_seconds
-                4,                    // retargeting_factor
-            ), // retargeting_factor
+            params_info: bitcoin.params(),
+            pow_rules: bitcoin.pow_rules,
             btc_withdrawal_fee: 500000,
             max_withdrawal_count: 100,
             verifier: BtcTxVerifier::Recover,
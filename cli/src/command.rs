@@ -53,6 +53,8 @@ fn load_spec(id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
         "dev" => Box::new(chain_spec::development_config()?),
         "malan" | "testnet" => Box::new(chain_spec::malan_config()?),
         "local" => Box::new(chain_spec::local_testnet_config()?),
+        "regtest" => Box::new(chain_spec::regtest_config()?),
+        "signet" => Box::new(chain_spec::signet_config()?),
         "benchmarks" => {
             #[cfg(feature = "runtime-benchmarks")]
             {
@@ -70,7 +72,7 @@ fn load_spec(id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
             let p = std::path::PathBuf::from(path);
             if !p.exists() {
                 // TODO more better hint
-                return Err("invalid path or just use --chain={dev, local, testnet, mainnet, malan, benchmarks}".into());
+                return Err("invalid path or just use --chain={dev, local, regtest, signet, testnet, mainnet, malan, benchmarks}".into());
             }
             Box::new(chain_spec::ChainXChainSpec::from_json_file(p)?)
         }
@@ -8,7 +8,8 @@ use sp_core::sr25519;
 
 use chainx_primitives::AccountId;
 use chainx_runtime::{
-    h256_rev, trustees, BtcCompact, BtcHash, BtcHeader, BtcNetwork, Chain, TrusteeInfoConfig,
+    h256_rev, trustees, BtcCompact, BtcHash, BtcHeader, BtcNetwork, BtcParams, BtcPowRules, Chain,
+    TrusteeInfoConfig,
 };
 
 use crate::chain_spec::get_account_id_from_seed;
@@ -16,6 +17,8 @@ use crate::chain_spec::get_account_id_from_seed;
 #[derive(Debug, Deserialize)]
 pub struct BtcGenesisParams {
     pub network: BtcNetwork,
+    #[serde(default)]
+    pub pow_rules: Option<BtcPowRules>,
     pub confirmation_number: u32,
     pub height: u32,
     hash: String,
@@ -49,6 +52,22 @@ impl BtcGenesisParams {
             nonce: self.nonce,
         }
     }
+
+    /// Return the proof-of-work params, the max bits depend on the pow rules of the network.
+    pub fn params(&self) -> BtcParams {
+        let max_bits = match self.pow_rules {
+            Some(BtcPowRules::Regtest) => 545259519, // 0x207fffff
+            Some(BtcPowRules::Signet) => 503543726,  // 0x1e0377ae
+            _ => 486604799,                          // 0x1d00ffff
+        };
+        BtcParams::new(
+            max_bits,             // max_bits
+            2 * 60 * 60,          // block_max_future
+            2 * 7 * 24 * 60 * 60, // target_timespan_seconds
+            10 * 60,              // target_spacing_seconds
+            4,                    // retargeting_factor
+        )
+    }
 }
 
 pub fn btc_genesis_params(res: &str) -> BtcGenesisParams {
@@ -31,11 +31,17 @@ use chainx_primitives::{AssetId, Decimals};
 //      thus we agree on the prefix:
 //      L_: use 0x90000000
 //      S_: use 0xa0000000
+//
+// 4. derived token on ChainX for the native token.
+//      e.g. B_PCX means the liquid staking derivative of bonded PCX, we agree on the prefix:
+//      B_: use 0xb0000000
 
 /// Native asset of ChainX.
 pub const PCX: AssetId = 0;
 /// Decimals of PCX, the native token of ChainX.
 pub const PCX_DECIMALS: Decimals = 8;
+/// Liquid staking derivative of the PCX bonded in Staking.
+pub const B_PCX: AssetId = 0xb0000000 | PCX;
 
 /// BTC asset in ChainX backed by the Mainnet Bitcoin.
 pub const X_BTC: AssetId = 1;
@@ -67,12 +67,20 @@ impl core::fmt::Display for Memo {
 impl Memo {
     /// Returns true if the inner byte length is in the range of [0, 128] and passes the xss check.
     pub fn check_validity(&self) -> DispatchResult {
+        self.check_len()?;
+        xss_check(&self.0)
+    }
+
+    /// Returns true if the inner byte length is in the range of [0, 128].
+    ///
+    /// The memo which is not a readable note (e.g. the calldata of Ethereum) only checks the length.
+    pub fn check_len(&self) -> DispatchResult {
         if self.0.len() > MAXIMUM_MEMO_LEN as usize {
             Err(DispatchError::Other(
                 "transaction memo too long, valid byte length range: [0, 128]",
             ))
         } else {
-            xss_check(&self.0)
+            Ok(())
         }
     }
 }
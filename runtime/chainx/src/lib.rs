@@ -47,7 +47,9 @@ use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 
 use xpallet_dex_spot::{Depth, FullPairInfo, RpcOrder, TradingPairId};
 use xpallet_mining_asset::{MinerLedger, MiningAssetInfo, MiningDividendInfo};
-use xpallet_mining_staking::{NominatorInfo, NominatorLedger, ValidatorInfo};
+use xpallet_mining_staking::{
+    EraIndex, EraRewards, MiningYield, NominatorInfo, NominatorLedger, StakeSlash, ValidatorInfo,
+};
 use xpallet_support::traits::MultisigAddressFor;
 
 // A few exports that help ease life for downstream crates.
@@ -80,14 +82,17 @@ pub use xpallet_assets::{
 #[cfg(feature = "std")]
 pub use xpallet_gateway_bitcoin::h256_rev;
 pub use xpallet_gateway_bitcoin::{
-    hash_rev, BtcHeader, BtcNetwork, BtcParams, BtcTxVerifier, Compact as BtcCompact,
+    hash_rev, BtcHeader, BtcNetwork, BtcParams, BtcPowRules, BtcTxVerifier, Compact as BtcCompact,
     H256 as BtcHash,
 };
 pub use xpallet_gateway_common::{
     trustees,
-    types::{GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig},
+    types::{
+        AddrFormat, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
+        TrusteeLiveness, TrusteeSigningStats,
+    },
 };
-pub use xpallet_gateway_records::Withdrawal;
+pub use xpallet_gateway_records::{CompletedWithdrawal, Withdrawal};
 pub use xpallet_mining_asset::MiningWeight;
 pub use xpallet_mining_staking::VoteWeight;
 
@@ -104,7 +109,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("chainx"),
     impl_name: create_runtime_str!("chainx-net"),
     authoring_version: 1,
-    spec_version: 10,
+    spec_version: 11,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -302,8 +307,10 @@ impl pallet_babe::Trait for Runtime {
         pallet_babe::AuthorityId,
     )>>::IdentificationTuple;
 
-    type HandleEquivocation =
-        pallet_babe::EquivocationHandler<Self::KeyOwnerIdentification, Offences>;
+    type HandleEquivocation = pallet_babe::EquivocationHandler<
+        Self::KeyOwnerIdentification,
+        xpallet_mining_staking::EquivocationReporter<Self, Offences>,
+    >;
 
     type WeightInfo = ();
 }
@@ -318,7 +325,10 @@ impl pallet_grandpa::Trait for Runtime {
         GrandpaId,
     )>>::IdentificationTuple;
     type KeyOwnerProofSystem = Historical;
-    type HandleEquivocation = ();
+    type HandleEquivocation = pallet_grandpa::EquivocationHandler<
+        Self::KeyOwnerIdentification,
+        xpallet_mining_staking::EquivocationReporter<Self, Offences>,
+    >;
 
     type WeightInfo = ();
 }
@@ -911,6 +921,7 @@ impl xpallet_assets::Trait for Runtime {
 
 impl xpallet_gateway_records::Trait for Runtime {
     type Event = Event;
+    type GuardianOrigin = EnsureRootOrHalfCouncil;
     type WeightInfo = xpallet_gateway_records::weights::SubstrateWeight<Runtime>;
 }
 
@@ -925,8 +936,8 @@ impl xpallet_gateway_common::Trait for Runtime {
     type Event = Event;
     type Validator = XStaking;
     type DetermineMultisigAddress = MultisigProvider;
-    type Bitcoin = XGatewayBitcoin;
-    type BitcoinTrustee = XGatewayBitcoin;
+    type Gateways = (XGatewayBitcoin,);
+    type Slasher = XStaking;
     type WeightInfo = xpallet_gateway_common::weights::SubstrateWeight<Runtime>;
 }
 
@@ -938,6 +949,8 @@ impl xpallet_gateway_bitcoin::Trait for Runtime {
     type TrusteeOrigin = EnsureSignedBy<trustees::bitcoin::BtcTrusteeMultisig<Runtime>, AccountId>;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type TrusteeMisbehaviour = XGatewayCommon;
+    type TrusteeSigningRecorder = XGatewayCommon;
     type WeightInfo = xpallet_gateway_bitcoin::weights::SubstrateWeight<Runtime>;
 }
 
@@ -970,6 +983,7 @@ impl xpallet_mining_staking::Trait for Runtime {
     type MinimumReferralId = MinimumReferralId;
     type MaximumReferralId = MaximumReferralId;
     type SessionInterface = Self;
+    type OnNewEra = XGatewayCommon;
     type TreasuryAccount = SimpleTreasuryAccount;
     type AssetMining = XMiningAsset;
     type DetermineRewardPotAccount =
@@ -995,6 +1009,20 @@ impl xpallet_mining_asset::Trait for Runtime {
     type WeightInfo = xpallet_mining_asset::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const LiquidStakingModuleId: ModuleId = ModuleId(*b"pcx/lqst");
+    pub const DerivativeAssetId: AssetId = xp_protocol::B_PCX;
+    pub const CompoundPeriod: BlockNumber = HOURS;
+}
+
+impl xpallet_mining_liquid_staking::Trait for Runtime {
+    type Event = Event;
+    type ModuleId = LiquidStakingModuleId;
+    type DerivativeAssetId = DerivativeAssetId;
+    type CompoundPeriod = CompoundPeriod;
+    type WeightInfo = xpallet_mining_liquid_staking::weights::SubstrateWeight<Runtime>;
+}
+
 impl xpallet_genesis_builder::Trait for Runtime {}
 
 construct_runtime!(
@@ -1021,7 +1049,7 @@ construct_runtime!(
         Offences: pallet_offences::{Module, Call, Storage, Event} = 9,
         Historical: pallet_session_historical::{Module} = 10,
         Session: pallet_session::{Module, Call, Storage, Event, Config<T>} = 11,
-        Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event} = 12,
+        Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event, ValidateUnsigned} = 12,
         ImOnline: pallet_im_online::{Module, Call, Storage, Event<T>, ValidateUnsigned, Config<T>} = 13,
         AuthorityDiscovery: pallet_authority_discovery::{Module, Call, Config} = 14,
 
@@ -1067,6 +1095,8 @@ construct_runtime!(
         XTransactionFee: xpallet_transaction_fee::{Module, Event<T>} = 35,
 
         Proxy: pallet_proxy::{Module, Call, Storage, Event<T>} = 37,
+
+        XLiquidStaking: xpallet_mining_liquid_staking::{Module, Call, Storage, Event<T>} = 40,
     }
 );
 
@@ -1330,6 +1360,21 @@ impl_runtime_apis! {
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber> {
             XStaking::nominator_info_of(who)
         }
+        fn stake_slashes(era: EraIndex) -> Vec<StakeSlash<AccountId, Balance>> {
+            XStaking::stake_slashes(era)
+        }
+
+        fn era_rewards(era: EraIndex) -> EraRewards<AccountId, Balance> {
+            XStaking::eras_rewards(era)
+        }
+
+        fn validator_yields() -> BTreeMap<AccountId, MiningYield> {
+            XStaking::validator_yields()
+        }
+
+        fn mining_asset_yields() -> BTreeMap<AssetId, MiningYield> {
+            XStaking::mining_asset_yields()
+        }
     }
 
     impl xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance> for Runtime {
@@ -1368,6 +1413,10 @@ impl_runtime_apis! {
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<u32, Withdrawal<AccountId, Balance, BlockNumber>> {
             XGatewayRecords::withdrawals_list_by_chain(chain)
         }
+
+        fn withdrawal_history(who: AccountId, page_index: u32, page_size: u32) -> Vec<CompletedWithdrawal<Balance, BlockNumber>> {
+            XGatewayRecords::withdrawal_history(&who, page_index, page_size)
+        }
     }
 
     impl xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<Block, AccountId, Balance> for Runtime {
@@ -1387,6 +1436,10 @@ impl_runtime_apis! {
             XGatewayCommon::trustee_multisigs()
         }
 
+        fn addr_formats() -> BTreeMap<Chain, AddrFormat> {
+            XGatewayCommon::addr_formats()
+        }
+
         fn trustee_properties(chain: Chain, who: AccountId) -> Option<GenericTrusteeIntentionProps> {
             XGatewayCommon::trustee_intention_props_of(who, chain)
         }
@@ -1404,6 +1457,21 @@ impl_runtime_apis! {
             let _ = XGatewayCommon::generate_multisig_addr(chain, &info)?;
             Ok(info)
         }
+
+        fn withdrawal_batch(chain: Chain) -> Result<Vec<u32>, DispatchError> {
+            match chain {
+                Chain::Bitcoin => Ok(XGatewayBitcoin::withdrawal_batch()),
+                _ => Err(xpallet_gateway_common::Error::<Runtime>::NotSupportedChain.into()),
+            }
+        }
+
+        fn trustee_liveness(chain: Chain) -> Vec<(AccountId, TrusteeLiveness)> {
+            XGatewayCommon::trustee_liveness(chain)
+        }
+
+        fn trustee_signing_stats(chain: Chain, session_number: Option<u32>) -> Vec<(AccountId, TrusteeSigningStats)> {
+            XGatewayCommon::trustee_signing_stats(chain, session_number)
+        }
     }
 
     #[cfg(feature = "runtime-benchmarks")]
@@ -1437,6 +1505,7 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, xpallet_assets_registrar, XAssetsRegistrar);
             add_benchmark!(params, batches, xpallet_mining_asset, XMiningAsset);
             add_benchmark!(params, batches, xpallet_mining_staking, XStaking);
+            add_benchmark!(params, batches, xpallet_mining_liquid_staking, XLiquidStaking);
             add_benchmark!(params, batches, xpallet_gateway_records, XGatewayRecords);
             add_benchmark!(params, batches, xpallet_gateway_common, XGatewayCommon);
             add_benchmark!(params, batches, xpallet_gateway_bitcoin, XGatewayBitcoin);
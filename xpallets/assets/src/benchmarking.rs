@@ -51,6 +51,7 @@ benchmarks! {
         balances.insert(AssetType::Reserved, 1000.into());
         balances.insert(AssetType::ReservedWithdrawal, 1000.into());
         balances.insert(AssetType::ReservedDexSpot, 1000.into());
+        balances.insert(AssetType::ReservedOrphaned, 1000.into());
     }: set_balance(RawOrigin::Root, user_lookup, ASSET_ID, balances.clone())
     verify {
         assert_eq!(XAssets::<T>::asset_balance(&user, &ASSET_ID), balances);
@@ -397,6 +397,19 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Destroys the asset frozen for an orphaned cross-chain deposit.
+    pub fn destroy_reserved_orphaned(
+        id: &AssetId,
+        who: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::ensure_not_native_asset(id)?;
+        xpallet_assets_registrar::Module::<T>::ensure_asset_is_valid(id)?;
+
+        Self::inner_destroy(id, who, AssetType::ReservedOrphaned, value)?;
+        Ok(())
+    }
+
     pub fn destroy_usable(id: &AssetId, who: &T::AccountId, value: BalanceOf<T>) -> DispatchResult {
         Self::ensure_not_native_asset(id)?;
         xpallet_assets_registrar::Module::<T>::ensure_asset_is_valid(id)?;
@@ -17,12 +17,13 @@ use frame_support::traits::LockIdentifier;
 
 use crate::{Error, Trait};
 
-const ASSET_TYPES: [AssetType; 5] = [
+const ASSET_TYPES: [AssetType; 6] = [
     AssetType::Usable,
     AssetType::Locked,
     AssetType::Reserved,
     AssetType::ReservedWithdrawal,
     AssetType::ReservedDexSpot,
+    AssetType::ReservedOrphaned,
 ];
 
 #[derive(PartialEq, PartialOrd, Ord, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
@@ -33,6 +34,8 @@ pub enum AssetType {
     Reserved,
     ReservedWithdrawal,
     ReservedDexSpot,
+    /// Frozen for a cross-chain deposit whose block was orphaned.
+    ReservedOrphaned,
 }
 
 impl AssetType {
@@ -9,7 +9,7 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use chainx_primitives::AssetId;
 use xp_gateway_bitcoin::BtcTxType;
-use xpallet_assets::{BalanceOf, Module as XAssets};
+use xpallet_assets::{BalanceOf, Chain, Module as XAssets};
 use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalState};
 
 use light_bitcoin::{
@@ -20,7 +20,8 @@ use light_bitcoin::{
 };
 
 use crate::{
-    types::*, Call, Module, PendingDeposits, Trait, TxState, Verifier, WithdrawalProposal,
+    types::*, Call, DepositRecords, HeldDeposits, Module, PendingDeposits, ProposalSigningSince,
+    SigningDeadline, Trait, TxState, Verifier, WithdrawalProposal,
 };
 
 const ASSET_ID: AssetId = xp_protocol::X_BTC;
@@ -321,6 +322,122 @@ benchmarks! {
     }: _(RawOrigin::Root,  2000000)
     verify {
     }
+
+    resolve_orphaned_deposit {
+        let who: T::AccountId = whitelisted_caller();
+        let txid = H256::repeat_byte(1);
+        let balance = 100000000_u64;
+        XGatewayRecords::<T>::deposit(&who, ASSET_ID, balance.saturated_into()).unwrap();
+        DepositRecords::<T>::insert(&txid, (who.clone(), balance));
+        crate::tx::freeze_orphaned_deposit::<T>(H256::repeat_byte(2), txid);
+    }: _(RawOrigin::Root, txid, true)
+    verify {
+        assert!(Module::<T>::orphaned_deposits(&txid).is_none());
+        assert_eq!(XAssets::<T>::all_type_asset_balance(&who, &ASSET_ID), 0.into());
+    }
+
+    set_withdrawal_batch_limits {
+        let limits = BtcWithdrawalBatchLimits {
+            max_tx_size: 50_000,
+            max_per_account: 5,
+        };
+    }: _(RawOrigin::Root, limits)
+    verify {
+        assert_eq!(Module::<T>::withdrawal_batch_limits(), limits);
+    }
+
+    set_deposit_limits {
+        let limits = BtcDepositLimits {
+            per_block: Some(100000000),
+            per_day: Some(1000000000),
+            per_account_per_day: Some(100000000),
+        };
+    }: _(RawOrigin::Root, limits)
+    verify {
+        assert_eq!(Module::<T>::deposit_limits(), limits);
+    }
+
+    release_held_deposit {
+        let who: T::AccountId = whitelisted_caller();
+        let txid = H256::repeat_byte(1);
+        let balance = 100000000_u64;
+        HeldDeposits::<T>::insert(&txid, BtcHeldDeposit { who: who.clone(), balance });
+        TxState::insert(&txid, BtcTxState {
+            tx_type: BtcTxType::Deposit,
+            result: BtcTxResult::Success,
+        });
+    }: _(RawOrigin::Root, txid)
+    verify {
+        assert!(Module::<T>::held_deposits(&txid).is_none());
+        assert_eq!(XAssets::<T>::usable_balance(&who, &ASSET_ID), balance.saturated_into());
+    }
+
+    set_signing_deadline {
+        let deadline: T::BlockNumber = 600u32.into();
+    }: _(RawOrigin::Root, deadline)
+    verify {
+        assert_eq!(Module::<T>::signing_deadline(), deadline);
+    }
+
+    check_signing_deadline {
+        let deadline: T::BlockNumber = 600u32.into();
+        SigningDeadline::<T>::put(deadline);
+        let proposal = BtcWithdrawalProposal::<T::AccountId> {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list: vec![0, 1],
+            tx: create_tx(),
+            trustee_list: vec![],
+        };
+        WithdrawalProposal::<T>::put(proposal);
+        let now = frame_system::Module::<T>::block_number();
+        ProposalSigningSince::<T>::put(now);
+    }: {
+        Module::<T>::check_signing_deadline(now);
+    }
+    verify {
+        assert!(WithdrawalProposal::<T>::get().is_some());
+    }
+
+    expire_withdrawal_proposal {
+        let w in 1 .. 100;
+
+        let who: T::AccountId = whitelisted_caller();
+        let balance: BalanceOf<T> = 100000000u64.saturated_into();
+        let total: BalanceOf<T> = (100000000u64 * w as u64).saturated_into();
+        XGatewayRecords::<T>::deposit(&who, ASSET_ID, total).unwrap();
+        let mut withdrawal_id_list = Vec::new();
+        for _ in 0..w {
+            let id = XGatewayRecords::<T>::id();
+            XGatewayRecords::<T>::withdraw(
+                &who,
+                ASSET_ID,
+                balance,
+                b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
+                b"".to_vec().into(),
+            )
+            .unwrap();
+            XGatewayRecords::<T>::process_withdrawal(id, Chain::Bitcoin).unwrap();
+            withdrawal_id_list.push(id);
+        }
+
+        // none of the trustees has voted, all of them are reported
+        let deadline: T::BlockNumber = 600u32.into();
+        SigningDeadline::<T>::put(deadline);
+        let proposal = BtcWithdrawalProposal::<T::AccountId> {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list,
+            tx: create_tx(),
+            trustee_list: vec![],
+        };
+        WithdrawalProposal::<T>::put(proposal);
+        ProposalSigningSince::<T>::put(T::BlockNumber::from(0u32));
+    }: {
+        Module::<T>::check_signing_deadline(deadline);
+    }
+    verify {
+        assert!(WithdrawalProposal::<T>::get().is_none());
+        assert_eq!(XGatewayRecords::<T>::state_of(0), Some(WithdrawalState::Applying));
+    }
 }
 
 #[cfg(test)]
@@ -339,9 +456,17 @@ mod tests {
             assert_ok!(test_benchmark_set_best_index::<Test>());
             assert_ok!(test_benchmark_set_confirmed_index::<Test>());
             assert_ok!(test_benchmark_remove_pending::<Test>());
+            assert_ok!(test_benchmark_remove_proposal::<Test>());
             assert_ok!(test_benchmark_force_replace_proposal_tx::<Test>());
             assert_ok!(test_benchmark_set_btc_withdrawal_fee::<Test>());
             assert_ok!(test_benchmark_set_btc_deposit_limit::<Test>());
+            assert_ok!(test_benchmark_resolve_orphaned_deposit::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_batch_limits::<Test>());
+            assert_ok!(test_benchmark_set_deposit_limits::<Test>());
+            assert_ok!(test_benchmark_release_held_deposit::<Test>());
+            assert_ok!(test_benchmark_set_signing_deadline::<Test>());
+            assert_ok!(test_benchmark_check_signing_deadline::<Test>());
+            assert_ok!(test_benchmark_expire_withdrawal_proposal::<Test>());
         });
     }
 }
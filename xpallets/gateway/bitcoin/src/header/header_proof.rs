@@ -6,13 +6,12 @@ use sp_std::{cmp, convert::TryFrom};
 
 use light_bitcoin::{
     chain::BlockHeader as BtcHeader,
-    keys::Network,
     primitives::{hash_rev, Compact, H256, U256},
 };
 
 use xp_logging::{debug, error, info, warn};
 
-use crate::types::{BtcHeaderInfo, BtcParams};
+use crate::types::{BtcHeaderInfo, BtcParams, BtcPowRules};
 use crate::{Error, Module, Trait};
 
 pub struct HeaderVerifier<'a> {
@@ -37,9 +36,9 @@ impl<'a> HeaderVerifier<'a> {
 
     pub fn check<T: Trait>(&self) -> DispatchResult {
         let params: BtcParams = Module::<T>::params_info();
-        let network_id: Network = Module::<T>::network_id();
-        if let Network::Mainnet = network_id {
-            self.work.check::<T>(&params)?;
+        let rules: BtcPowRules = Module::<T>::pow_rules();
+        if rules.check_work() {
+            self.work.check::<T>(&params, rules)?;
         }
         self.proof_of_work.check::<T>(&params)?;
         // ignore this in benchmarks
@@ -65,9 +64,8 @@ impl<'a> HeaderWork<'a> {
         HeaderWork { info }
     }
 
-    fn check<T: Trait>(&self, params: &BtcParams) -> DispatchResult {
-        let previous_header_hash = self.info.header.previous_header_hash;
-        let work = work_required::<T>(previous_header_hash, self.info.height, params);
+    fn check<T: Trait>(&self, params: &BtcParams, rules: BtcPowRules) -> DispatchResult {
+        let work = work_required::<T>(&self.info.header, self.info.height, params, rules);
         match work {
             RequiredWork::Value(work) => {
                 if work != self.info.header.bits {
@@ -84,17 +82,29 @@ impl<'a> HeaderWork<'a> {
     }
 }
 
-pub fn work_required<T: Trait>(parent_hash: H256, height: u32, params: &BtcParams) -> RequiredWork {
+pub fn work_required<T: Trait>(
+    header: &BtcHeader,
+    height: u32,
+    params: &BtcParams,
+    rules: BtcPowRules,
+) -> RequiredWork {
     let max_bits = params.max_bits();
     if height == 0 {
         return RequiredWork::Value(max_bits);
     }
 
-    let parent_header: BtcHeader = Module::<T>::headers(&parent_hash)
-        .expect("pre header must exist here")
-        .header;
+    let parent_info =
+        Module::<T>::headers(&header.previous_header_hash).expect("pre header must exist here");
+    let parent_header: BtcHeader = parent_info.header.clone();
 
     if is_retarget_height(height, params) {
+        if rules.no_retargeting() {
+            debug!(
+                "[work_required] No retargeting, use old work required, old bits:{:?}",
+                parent_header.bits
+            );
+            return RequiredWork::Value(parent_header.bits);
+        }
         let new_work = work_required_retarget::<T>(parent_header, height, params);
         info!(
             "[work_required] Retarget new work required, height:{}, retargeting_interval:{}, new_work:{:?}",
@@ -102,6 +112,9 @@ pub fn work_required<T: Trait>(parent_hash: H256, height: u32, params: &BtcParam
         );
         return new_work;
     }
+    if rules.allow_min_difficulty_blocks() {
+        return work_required_min_difficulty(header, parent_info, params);
+    }
     debug!(
         "[work_required] Use old work required, old bits:{:?}",
         parent_header.bits
@@ -109,6 +122,39 @@ pub fn work_required<T: Trait>(parent_hash: H256, height: u32, params: &BtcParam
     RequiredWork::Value(parent_header.bits)
 }
 
+/// Special difficulty rule for testnet and regtest:
+/// If the new block's timestamp is more than twice the target spacing later than the previous
+/// block, then allow mining of a min-difficulty block. Otherwise return the bits of the last
+/// block which is not a min-difficulty block, which is recorded in the parent header info.
+fn work_required_min_difficulty(
+    header: &BtcHeader,
+    parent_info: BtcHeaderInfo,
+    params: &BtcParams,
+) -> RequiredWork {
+    if header.time > parent_info.header.time + params.target_spacing_seconds() * 2 {
+        debug!(
+            "[work_required_min_difficulty] Allow min difficulty, header time:{}, parent time:{}",
+            header.time, parent_info.header.time
+        );
+        return RequiredWork::Value(params.max_bits());
+    }
+    RequiredWork::Value(parent_info.normal_bits)
+}
+
+/// Returns the bits of the last block which is not a min-difficulty block up to the header,
+/// the header at the retarget height is always counted as a normal one.
+pub fn normal_bits(
+    header: &BtcHeader,
+    height: u32,
+    parent_normal_bits: Option<Compact>,
+    params: &BtcParams,
+) -> Compact {
+    if is_retarget_height(height, params) || header.bits != params.max_bits() {
+        return header.bits;
+    }
+    parent_normal_bits.unwrap_or(header.bits)
+}
+
 fn is_retarget_height(height: u32, params: &BtcParams) -> bool {
     height % params.retargeting_interval() == 0
 }
@@ -203,6 +249,16 @@ impl<'a> HeaderProofOfWork<'a> {
     }
 }
 
+/// Return the expected number of hashes required to produce a block with the given bits,
+/// i.e. `2**256 / (target + 1)`.
+pub fn block_proof(bits: Compact) -> U256 {
+    match bits.to_u256() {
+        // 2**256 can't be represented as a U256, use `~target / (target + 1) + 1` instead.
+        Ok(target) if !target.is_zero() => (!target / (target + U256::one())) + U256::one(),
+        _ => U256::zero(),
+    }
+}
+
 fn is_valid_proof_of_work(max_work_bits: Compact, bits: Compact, hash: H256) -> bool {
     match (max_work_bits.to_u256(), bits.to_u256()) {
         (Ok(maximum), Ok(target)) => {
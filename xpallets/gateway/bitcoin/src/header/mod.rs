@@ -3,7 +3,7 @@
 mod header_proof;
 
 use frame_support::{StorageMap, StorageValue};
-use sp_runtime::DispatchResult;
+use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::{cmp::Ordering, prelude::*};
 
 use light_bitcoin::primitives::{hash_rev, H256};
@@ -13,7 +13,14 @@ use xp_logging::{error, info};
 use crate::types::{BtcHeaderIndex, BtcHeaderInfo};
 use crate::{ConfirmedIndex, Error, MainChain, Module, Trait};
 
-pub use self::header_proof::HeaderVerifier;
+pub use self::header_proof::{block_proof, normal_bits, HeaderVerifier};
+
+/// The common ancestor, the headers of the new branch and the orphaned headers of a reorg.
+pub type ReorgResult = (
+    Option<BtcHeaderIndex>,
+    Vec<BtcHeaderIndex>,
+    Vec<BtcHeaderIndex>,
+);
 
 /// Look back the headers to pick the confirmed index,
 /// return the header indexes on the look back path.
@@ -76,6 +83,86 @@ pub fn update_confirmed_header<T: Trait>(header_info: &BtcHeaderInfo) -> Option<
     })
 }
 
+/// Switch the main chain to the branch of the new best header.
+///
+/// Walk back from the new best header and the old best header to their common ancestor,
+/// mark the headers of the new branch as main chain, and return the common ancestor, the
+/// headers of the new branch and the headers of the old branch which are orphaned now (from
+/// high to low).
+///
+/// The fork which orphans more than `ConfirmationNumber` headers is rejected, the new branch
+/// is allowed to be twice as long since its headers may have lower difficulty.
+///
+/// e.g:
+///                 b(old_best)
+/// b -- b(fork) -- b -- b
+///           | --- b -- b -- b(new_best)
+///
+pub fn reorganize_main_chain<T: Trait>(
+    new_best: &BtcHeaderInfo,
+    old_best: BtcHeaderIndex,
+) -> Result<ReorgResult, DispatchError> {
+    let max_depth = Module::<T>::confirmation_number() as usize;
+    let mut new_branch = vec![];
+    let mut orphaned = vec![];
+    let mut fork = None;
+
+    let mut new_cursor = Some((new_best.header.hash(), new_best.clone()));
+    let mut old_cursor = Module::<T>::headers(&old_best.hash).map(|info| (old_best.hash, info));
+    while let (Some((new_hash, new_info)), Some((old_hash, old_info))) =
+        (new_cursor.clone(), old_cursor.clone())
+    {
+        if new_hash == old_hash {
+            fork = Some(BtcHeaderIndex {
+                hash: new_hash,
+                height: new_info.height,
+            });
+            break;
+        }
+        if new_info.height >= old_info.height {
+            new_branch.push(BtcHeaderIndex {
+                hash: new_hash,
+                height: new_info.height,
+            });
+            new_cursor = parent_of::<T>(&new_info);
+        }
+        if old_info.height >= new_info.height {
+            orphaned.push(BtcHeaderIndex {
+                hash: old_hash,
+                height: old_info.height,
+            });
+            old_cursor = parent_of::<T>(&old_info);
+        }
+        if orphaned.len() > max_depth || new_branch.len() > 2 * max_depth {
+            error!(
+                "[reorganize_main_chain] Reject the fork deeper than {}, new branch:{:?}, orphaned:{:?}",
+                max_depth, new_branch, orphaned
+            );
+            return Err(Error::<T>::ReorgTooDeep.into());
+        }
+    }
+
+    for index in new_branch.iter() {
+        set_main_chain::<T>(index.height, index.hash);
+    }
+    // the old branch may be higher than the new best
+    for index in orphaned.iter().filter(|i| i.height > new_best.height) {
+        MainChain::remove(&index.hash);
+    }
+    if !orphaned.is_empty() {
+        info!(
+            "[reorganize_main_chain] Switch main chain, new branch:{:?}, orphaned:{:?}",
+            new_branch, orphaned
+        );
+    }
+    Ok((fork, new_branch, orphaned))
+}
+
+fn parent_of<T: Trait>(info: &BtcHeaderInfo) -> Option<(H256, BtcHeaderInfo)> {
+    let parent_hash = info.header.previous_header_hash;
+    Module::<T>::headers(&parent_hash).map(|parent| (parent_hash, parent))
+}
+
 fn set_main_chain<T: Trait>(height: u32, main_hash: H256) {
     let hashes = Module::<T>::block_hash_for(&height);
     if hashes.len() == 1 {
@@ -5,6 +5,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod header;
+mod migration;
 pub mod trustee;
 mod tx;
 mod types;
@@ -22,10 +23,10 @@ use sp_std::prelude::*;
 
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo, PostDispatchInfo},
     ensure,
     traits::{EnsureOrigin, UnixTime},
-    weights::Pays,
+    weights::{Pays, Weight},
 };
 use frame_system::{ensure_root, ensure_signed};
 use orml_utilities::with_transaction_result;
@@ -45,22 +46,31 @@ use light_bitcoin::{
 
 use chainx_primitives::{AssetId, ReferralId};
 use xp_gateway_common::AccountExtractor;
-use xp_logging::{debug, error, info};
+use xp_logging::{debug, error, info, warn};
 use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
 use xpallet_gateway_common::{
-    traits::{AddressBinding, ReferralBinding, TrusteeSession},
+    traits::{
+        AddressBinding, ChainGateway, ReferralBinding, TrusteeForChain, TrusteeMisbehaviour,
+        TrusteeSession, TrusteeSigningRecorder,
+    },
     trustees::bitcoin::BtcTrusteeAddrInfo,
+    types::{
+        AddrFormat, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
+    },
 };
 use xpallet_support::try_addr;
 
-pub use self::types::{BtcAddress, BtcParams, BtcTxVerifier, BtcWithdrawalProposal};
+pub use self::types::{
+    BtcAddress, BtcDepositLimits, BtcParams, BtcPowRules, BtcTxVerifier, BtcWithdrawalBatchLimits,
+    BtcWithdrawalProposal, VoteResult,
+};
 pub use self::weights::WeightInfo;
 use self::{
     trustee::{get_current_trustee_address_pair, get_last_trustee_address_pair},
     tx::remove_pending_deposit,
     types::{
-        BtcDepositCache, BtcHeaderIndex, BtcHeaderInfo, BtcRelayedTx, BtcRelayedTxInfo,
-        BtcTxResult, BtcTxState,
+        BtcDepositCache, BtcHeaderIndex, BtcHeaderInfo, BtcHeldDeposit, BtcOrphanedDeposit,
+        BtcRelayedTx, BtcRelayedTxInfo, BtcTxResult, BtcTxState,
     },
 };
 
@@ -83,6 +93,8 @@ pub trait Trait: xpallet_assets::Trait + xpallet_gateway_records::Trait {
     type TrusteeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
     type ReferralBinding: ReferralBinding<Self::AccountId>;
     type AddressBinding: AddressBinding<Self::AccountId, BtcAddress>;
+    type TrusteeMisbehaviour: TrusteeMisbehaviour<Self::AccountId>;
+    type TrusteeSigningRecorder: TrusteeSigningRecorder<Self::AccountId>;
     type WeightInfo: WeightInfo;
 }
 
@@ -163,6 +175,18 @@ decl_error! {
         NoWithdrawalRecord,
         /// already vote for this withdrawal proposal
         DuplicateVote,
+        /// no orphaned deposit for this tx
+        NoOrphanedDeposit,
+        /// no held deposit for this tx
+        NoHeldDeposit,
+        /// the tx is not in the main chain
+        NotInMainChain,
+        /// withdrawal list skips older eligible withdrawals
+        SkippedWithdrawal,
+        /// the fork orphans more headers than the confirmation number
+        ReorgTooDeep,
+        /// the block has too many processed txs
+        TooManyBlockTxs,
     }
 }
 
@@ -190,10 +214,30 @@ decl_event!(
         WithdrawalProposalVoted(AccountId, bool),
         /// A withdrawal proposal was dropped. [reject_count, total_count, withdrawal_ids]
         WithdrawalProposalDropped(u32, u32, Vec<u32>),
+        /// A withdrawal proposal expired without enough signatures. [withdrawal_ids]
+        WithdrawalProposalExpired(Vec<u32>),
         /// The proposal has been processed successfully and is waiting for broadcasting. [tx_hash]
         WithdrawalProposalCompleted(H256),
         /// A fatal error happened during the withdrwal process. [tx_hash, proposal_hash]
         WithdrawalFatalErr(H256, H256),
+        /// The main chain was switched to a fork with more work. [old_best, new_best, orphaned_headers]
+        ChainReorganized(H256, H256, Vec<H256>),
+        /// Processed txs were orphaned by a reorg deeper than the confirmation. [orphaned_confirmed_header, tx_hashes]
+        ConfirmedTxsOrphaned(H256, Vec<H256>),
+        /// A minted deposit was orphaned and the X-BTC was frozen, waiting for governance. [tx_hash, block_hash, who, frozen]
+        DepositOrphaned(H256, H256, AccountId, Balance),
+        /// An orphaned deposit was resolved by governance. [tx_hash, claw_back]
+        OrphanedDepositResolved(H256, bool),
+        /// The shortfall of the clawed back deposits was repaid by a deposit. [who, amount]
+        DepositShortfallRepaid(AccountId, Balance),
+        /// A deposit exceeding the deposit limits was held for review. [tx_hash, who, amount]
+        DepositHeld(H256, AccountId, Balance),
+        /// A held deposit was released and minted. [tx_hash]
+        HeldDepositReleased(H256),
+        /// A held deposit was dropped since its block was orphaned. [tx_hash, block_hash]
+        HeldDepositOrphaned(H256, H256),
+        /// An unclaimed deposit was dropped since its block was orphaned. [tx_hash, block_hash]
+        PendingDepositOrphaned(H256, H256),
     }
 );
 
@@ -212,11 +256,27 @@ decl_storage! {
 
         /// mark tx has been handled, in case re-handle this tx, and log handle result
         pub TxState get(fn tx_state): map hasher(identity) H256 => Option<BtcTxState>;
+        /// successfully processed txs in a block, block_hash => tx_hash list
+        pub BlockTxs get(fn block_txs): map hasher(identity) H256 => Vec<H256>;
         /// unclaimed deposit info, addr => tx_hash, btc value,
         pub PendingDeposits get(fn pending_deposits): map hasher(blake2_128_concat) BtcAddress => Vec<BtcDepositCache>;
+        /// the address of an unclaimed deposit, tx_hash => addr, used to drop it once orphaned
+        pub PendingDepositAddressOf get(fn pending_deposit_address_of): map hasher(identity) H256 => Option<BtcAddress>;
+        /// minted deposit info, tx_hash => (depositor, btc value)
+        pub DepositRecords get(fn deposit_records): map hasher(identity) H256 => Option<(T::AccountId, u64)>;
+        /// txs of the minted deposits by the confirmed height at minting, used to prune `DepositRecords`
+        pub DepositRecordsAt get(fn deposit_records_at): map hasher(twox_64_concat) u32 => Vec<H256>;
+        /// X-BTC of the clawed back deposits that was spent before the reorg, repaid by the next deposits
+        pub DepositShortfalls get(fn deposit_shortfalls): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+        /// minted deposits whose block was orphaned, tx_hash => orphaned deposit info
+        pub OrphanedDeposits get(fn orphaned_deposits): map hasher(identity) H256 => Option<BtcOrphanedDeposit<T::AccountId, BalanceOf<T>>>;
 
         /// withdrawal tx outs for account, tx_hash => outs ( out index => withdrawal account )
         pub WithdrawalProposal get(fn withdrawal_proposal): Option<BtcWithdrawalProposal<T::AccountId>>;
+        /// the proposal not signed in `SigningDeadline` blocks expires, zero means no deadline
+        pub SigningDeadline get(fn signing_deadline): T::BlockNumber;
+        /// the block in which the current proposal was created
+        pub ProposalSigningSince get(fn proposal_signing_since): T::BlockNumber;
 
         /// get GenesisInfo (header, height)
         pub GenesisInfo get(fn genesis_info) config(): (BtcHeader, u32);
@@ -224,20 +284,35 @@ decl_storage! {
         pub ParamsInfo get(fn params_info) config(): BtcParams;
         ///  NetworkId for testnet or mainnet
         pub NetworkId get(fn network_id) config(): BtcNetwork;
+        /// proof-of-work rules of the header verifier, use the rules of `NetworkId` if not set
+        pub PowRules: Option<BtcPowRules>;
         /// get ConfirmationNumber from genesis_config
         pub ConfirmationNumber get(fn confirmation_number) config(): u32;
         /// get BtcWithdrawalFee from genesis_config
         pub BtcWithdrawalFee get(fn btc_withdrawal_fee) config(): u64;
         /// min deposit value limit, default is 10w sotashi(0.001 BTC)
         pub BtcMinDeposit get(fn btc_min_deposit): u64 = 1 * 100000;
+        /// deposit caps, deposits exceeding the caps would be held for review
+        pub DepositLimits get(fn deposit_limits): BtcDepositLimits;
+        /// deposited value in the current block, (block_number, value)
+        pub BlockDeposited get(fn block_deposited): (T::BlockNumber, u64);
+        /// deposited value in the current day, (day_index, value)
+        pub DailyDeposited get(fn daily_deposited): (u64, u64);
+        /// deposited value of an account in the current day, (day_index, value)
+        pub AccountDailyDeposited get(fn account_daily_deposited): map hasher(blake2_128_concat) T::AccountId => (u64, u64);
+        /// deposits exceeding the caps, tx_hash => held deposit info
+        pub HeldDeposits get(fn held_deposits): map hasher(identity) H256 => Option<BtcHeldDeposit<T::AccountId>>;
         /// max withdraw account count in bitcoin withdrawal transaction
         pub MaxWithdrawalCount get(fn max_withdrawal_count) config(): u32;
+        /// limits for proposing the next withdrawal batch
+        pub WithdrawalBatchLimits get(fn withdrawal_batch_limits): BtcWithdrawalBatchLimits;
 
         Verifier get(fn verifier) config(): BtcTxVerifier;
     }
     add_extra_genesis {
         config(genesis_hash): H256;
         config(genesis_trustees): Vec<T::AccountId>;
+        config(pow_rules): Option<BtcPowRules>;
         build(|config| {
             let genesis_hash = config.genesis_hash;
             let (genesis_header, genesis_height) = config.genesis_info;
@@ -248,12 +323,17 @@ decl_storage! {
             let header_info = BtcHeaderInfo {
                 header: genesis_header,
                 height: genesis_height,
+                chain_work: header::block_proof(genesis_header.bits),
+                normal_bits: genesis_header.bits,
             };
 
             Headers::insert(&genesis_hash, header_info);
             BlockHashFor::insert(&genesis_index.height, vec![genesis_hash]);
             MainChain::insert(&genesis_hash, true);
             BestIndex::put(genesis_index);
+            if let Some(rules) = config.pow_rules {
+                PowRules::put(rules);
+            }
 
             // init trustee (not this action should ha)
             if !config.genesis_trustees.is_empty() {
@@ -268,17 +348,30 @@ decl_module! {
         type Error = Error<T>;
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            migration::migrate_headers_chain_work::<T>()
+        }
+
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::check_signing_deadline(now)
+        }
+
         /// if use `BtcHeader` struct would export in metadata, cause complex in front-end
-        #[weight = <T as Trait>::WeightInfo::push_header()]
+        ///
+        /// The weight covers the deepest reorg allowed, the actual weight is returned.
+        #[weight = <T as Trait>::WeightInfo::push_header().saturating_add(Module::<T>::max_reorg_weight())]
         pub fn push_header(origin, header: Vec<u8>) -> DispatchResultWithPostInfo {
             let from = ensure_signed(origin)?;
             let header: BtcHeader = deserialize(header.as_slice()).map_err(|_| Error::<T>::DeserializeErr)?;
             debug!("[push_header] from:{:?}, header:{:?}", from, header);
 
-            Self::apply_push_header(header)?;
+            let reorg_weight = Self::apply_push_header(header)?;
 
             // Relayer does not pay a fee.
-            Ok(Pays::No.into())
+            Ok(PostDispatchInfo {
+                actual_weight: Some(<T as Trait>::WeightInfo::push_header().saturating_add(reorg_weight)),
+                pays_fee: Pays::No,
+            })
         }
 
         /// if use `RelayTx` struct would export in metadata, cause complex in front-end
@@ -307,7 +400,10 @@ decl_module! {
         /// Trustee create a proposal for a withdrawal list. `tx` is the proposal withdrawal transaction.
         /// The `tx` would have a sign for current creator or do not have sign. if creator do not sign
         /// for this transaction, he could do `sign_withdraw_tx` later.
-        #[weight = <T as Trait>::WeightInfo::create_withdraw_tx()]
+        #[weight = <T as Trait>::WeightInfo::create_withdraw_tx()
+            .saturating_add(T::DbWeight::get().reads(
+                trustee::MAX_WITHDRAWAL_SCAN as Weight + 1,
+            ))]
         pub fn create_withdraw_tx(origin, withdrawal_id_list: Vec<u32>, tx: Vec<u8>) -> DispatchResult {
             let from = ensure_signed(origin)?;
             // committer must be in the trustee list
@@ -366,7 +462,9 @@ decl_module! {
                 remove_pending_deposit::<T>(&addr, &w);
             } else {
                 info!("[remove_pending] Release pending deposit directly, not deposit to someone, addr:{:?}", try_addr(&addr));
-                PendingDeposits::remove(&addr);
+                for record in PendingDeposits::take(&addr) {
+                    PendingDepositAddressOf::remove(&record.txid);
+                }
             }
             Ok(())
         }
@@ -409,6 +507,47 @@ decl_module! {
             BtcMinDeposit::put(value);
             Ok(())
         }
+
+        /// Set the limits for proposing the next withdrawal batch.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_batch_limits()]
+        pub fn set_withdrawal_batch_limits(origin, limits: BtcWithdrawalBatchLimits) -> DispatchResult {
+            ensure_root(origin)?;
+            WithdrawalBatchLimits::put(limits);
+            Ok(())
+        }
+
+        /// Set the signing deadline of the withdrawal proposals in blocks, the proposal expires
+        /// once the deadline passes, zero means no deadline.
+        #[weight = <T as Trait>::WeightInfo::set_signing_deadline()]
+        pub fn set_signing_deadline(origin, #[compact] deadline: T::BlockNumber) -> DispatchResult {
+            ensure_root(origin)?;
+            SigningDeadline::<T>::put(deadline);
+            Ok(())
+        }
+
+        /// Set the deposit caps of X-BTC.
+        #[weight = <T as Trait>::WeightInfo::set_deposit_limits()]
+        pub fn set_deposit_limits(origin, limits: BtcDepositLimits) -> DispatchResult {
+            ensure_root(origin)?;
+            DepositLimits::put(limits);
+            Ok(())
+        }
+
+        /// Allow root or trustees to release a deposit which was held for exceeding the caps.
+        #[weight = <T as Trait>::WeightInfo::release_held_deposit()]
+        pub fn release_held_deposit(origin, txid: H256) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            tx::release_held_deposit::<T>(txid)
+        }
+
+        /// Resolve a deposit whose block was orphaned by a reorg. If `claw_back` is true, the
+        /// frozen X-BTC would be destroyed, otherwise it would be released to the depositor
+        /// (e.g. the deposit tx is packed in the new main chain as well).
+        #[weight = <T as Trait>::WeightInfo::resolve_orphaned_deposit()]
+        pub fn resolve_orphaned_deposit(origin, txid: H256, claw_back: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            tx::resolve_orphaned_deposit::<T>(txid, claw_back)
+        }
     }
 }
 
@@ -460,7 +599,53 @@ impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
     }
 }
 
+impl<T: Trait> ChainGateway<T::AccountId, BalanceOf<T>> for Module<T> {
+    fn chain() -> Chain {
+        Chain::Bitcoin
+    }
+
+    fn addr_format() -> AddrFormat {
+        AddrFormat::Utf8
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        <Self as ChainT<_>>::withdrawal_limit(asset_id)
+    }
+
+    fn check_addr(addr: &[u8], ext: &[u8]) -> DispatchResult {
+        <Self as ChainT<_>>::check_addr(addr, ext)
+    }
+
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<Vec<u8>, DispatchError> {
+        Self::check_generic_trustee_entity(raw_addr)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, GenericTrusteeIntentionProps)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<GenericTrusteeSessionInfo<T::AccountId>, DispatchError> {
+        Self::generate_generic_trustee_session_info(props, config)
+    }
+
+    fn has_pending_proposal() -> bool {
+        WithdrawalProposal::<T>::exists()
+    }
+}
+
 impl<T: Trait> Module<T> {
+    /// Return the proof-of-work rules used by the header verifier.
+    ///
+    /// Regtest and Signet must be set explicitly in genesis, otherwise the rules are
+    /// determined by the `NetworkId`.
+    pub fn pow_rules() -> BtcPowRules {
+        PowRules::get().unwrap_or_else(|| match Self::network_id() {
+            BtcNetwork::Mainnet => BtcPowRules::Mainnet,
+            BtcNetwork::Testnet => BtcPowRules::Testnet,
+        })
+    }
+
     pub fn verify_btc_address(data: &[u8]) -> Result<Address, DispatchError> {
         let r = bs58::decode(data)
             .into_vec()
@@ -475,7 +660,8 @@ impl<T: Trait> Module<T> {
         deserialize(Reader::new(input)).map_err(|_| Error::<T>::DeserializeErr)
     }
 
-    fn apply_push_header(header: BtcHeader) -> DispatchResult {
+    /// Returns the extra weight of the reorg if the header switches the main chain.
+    fn apply_push_header(header: BtcHeader) -> Result<Weight, DispatchError> {
         // current should not exist
         if Self::headers(&header.hash()).is_some() {
             error!(
@@ -495,14 +681,19 @@ impl<T: Trait> Module<T> {
         })?;
 
         // convert btc header to self header info
+        let height = prev_info.height + 1;
+        let params: BtcParams = Self::params_info();
         let header_info = BtcHeaderInfo {
             header,
-            height: prev_info.height + 1,
+            height,
+            chain_work: prev_info.chain_work + header::block_proof(header.bits),
+            normal_bits: header::normal_bits(&header, height, Some(prev_info.normal_bits), &params),
         };
         // verify header
         let header_verifier = header::HeaderVerifier::new::<T>(&header_info);
         header_verifier.check::<T>()?;
 
+        let mut reorg_weight: Weight = 0;
         with_transaction_result(|| {
             // insert into storage
             let hash = header_info.header.hash();
@@ -523,10 +714,30 @@ impl<T: Trait> Module<T> {
             );
 
             let best_index = Self::best_index();
-
-            if header_info.height > best_index.height {
+            let best_work = Self::headers(&best_index.hash)
+                .map(|info| info.chain_work)
+                .unwrap_or_default();
+
+            // fork choice by the cumulative chain work, like Bitcoin itself
+            if header_info.chain_work > best_work {
+                let old_confirmed = Self::confirmed_index();
+                let (fork, new_branch, orphaned) =
+                    header::reorganize_main_chain::<T>(&header_info, best_index)?;
                 // note update_confirmed_header would mutate other storage depend on BlockHashFor
-                let confirmed_index = header::update_confirmed_header::<T>(&header_info);
+                let mut confirmed_index = header::update_confirmed_header::<T>(&header_info);
+                if let (None, Some(old)) = (confirmed_index, old_confirmed) {
+                    // the new chain is not long enough to confirm a header, fallback to the
+                    // common ancestor if the old confirmed header is orphaned.
+                    if !Self::main_chain(&old.hash) {
+                        confirmed_index = fork;
+                        if let Some(fork) = fork {
+                            ConfirmedIndex::put(fork);
+                        }
+                    }
+                }
+                if let Some(confirmed) = confirmed_index {
+                    tx::prune_deposit_records::<T>(confirmed.height);
+                }
                 info!(
                     "[apply_push_header] Update new height:{}, hash:{:?}, confirm:{:?}",
                     header_info.height, hash, confirmed_index
@@ -538,17 +749,43 @@ impl<T: Trait> Module<T> {
                     height: header_info.height,
                 };
                 BestIndex::put(new_best_index);
+
+                if !orphaned.is_empty() {
+                    let walked = (new_branch.len() + orphaned.len()) as u32;
+                    let frozen = Self::on_chain_reorganized(
+                        best_index,
+                        new_best_index,
+                        old_confirmed,
+                        orphaned,
+                    );
+                    reorg_weight = Self::reorg_weight(walked, frozen);
+                }
             } else {
                 // forked chain
                 info!(
-                    "[apply_push_header] Best index {} larger than this height {}",
+                    "[apply_push_header] Best index {} has more or equal work than this header (height {})",
                     best_index.height, header_info.height
                 );
                 header::check_confirmed_header::<T>(&header_info)?;
             };
             Self::deposit_event(Event::<T>::HeaderInserted(hash));
             Ok(())
-        })
+        })?;
+        Ok(reorg_weight)
+    }
+
+    /// The extra weight of a reorg which walks `walked` headers of both branches and looks up
+    /// the deposits of `txs` orphaned txs.
+    fn reorg_weight(walked: u32, txs: u32) -> Weight {
+        let (walked, txs) = (walked as Weight, txs as Weight);
+        T::DbWeight::get().reads_writes(3 * walked + 6 * txs, 2 * walked + 4 * txs)
+    }
+
+    /// The extra weight of the deepest reorg allowed, the orphaned headers and the new branch
+    /// are bounded by `ConfirmationNumber` and twice of it.
+    fn max_reorg_weight() -> Weight {
+        let depth = Self::confirmation_number();
+        Self::reorg_weight(3 * depth, depth.saturating_mul(tx::MAX_BLOCK_TXS))
     }
 
     fn apply_push_transaction(tx: BtcRelayedTx, prev_tx: Option<Transaction>) -> DispatchResult {
@@ -571,6 +808,10 @@ impl<T: Trait> Module<T> {
         ensure!(Self::main_chain(&tx.block_hash), Error::<T>::UnconfirmedTx);
         // if ConfirmedIndex not set, due to confirm height not beyond genesis height
         let confirmed = Self::confirmed_index().ok_or(Error::<T>::UnconfirmedTx)?;
+        ensure!(
+            BlockTxs::decode_len(&block_hash).unwrap_or(0) < tx::MAX_BLOCK_TXS as usize,
+            Error::<T>::TooManyBlockTxs
+        );
         let height = header_info.height;
         if height > confirmed.height {
             error!(
@@ -606,10 +847,48 @@ impl<T: Trait> Module<T> {
             last_trustee_pair,
         );
         TxState::insert(&tx_hash, state);
+        if state.result == BtcTxResult::Success {
+            BlockTxs::append(&block_hash, tx_hash);
+        }
         Self::deposit_event(Event::<T>::TxProcessed(tx_hash, block_hash, state));
         match state.result {
             BtcTxResult::Success => Ok(()),
             BtcTxResult::Failure => Err(Error::<T>::ProcessTxFailed.into()),
         }
     }
+
+    /// Report the orphaned headers, and the processed txs of the orphaned headers which
+    /// were confirmed before the reorg, the deposits of those txs would be frozen.
+    ///
+    /// Returns the number of the orphaned txs.
+    fn on_chain_reorganized(
+        old_best: BtcHeaderIndex,
+        new_best: BtcHeaderIndex,
+        old_confirmed: Option<BtcHeaderIndex>,
+        orphaned: Vec<BtcHeaderIndex>,
+    ) -> u32 {
+        let mut orphaned_txs = 0;
+        warn!(
+            "[on_chain_reorganized] Chain reorganized, old best:{:?}, new best:{:?}, orphaned:{:?}",
+            old_best, new_best, orphaned
+        );
+        if let Some(old_confirmed) = old_confirmed {
+            for index in orphaned.iter().filter(|i| i.height <= old_confirmed.height) {
+                let txs = Self::block_txs(&index.hash);
+                if !txs.is_empty() {
+                    for txid in txs.iter() {
+                        tx::freeze_orphaned_deposit::<T>(index.hash, *txid);
+                    }
+                    orphaned_txs += txs.len() as u32;
+                    Self::deposit_event(Event::<T>::ConfirmedTxsOrphaned(index.hash, txs));
+                }
+            }
+        }
+        Self::deposit_event(Event::<T>::ChainReorganized(
+            old_best.hash,
+            new_best.hash,
+            orphaned.into_iter().map(|index| index.hash).collect(),
+        ));
+        orphaned_txs
+    }
 }
@@ -0,0 +1,71 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+use frame_support::{
+    storage::{IterableStorageMap, StorageMap, StorageValue},
+    traits::Get,
+    weights::Weight,
+};
+
+use light_bitcoin::primitives::{Compact, H256, U256};
+
+use crate::{
+    header::{block_proof, normal_bits},
+    types::BtcHeaderInfo,
+    BtcHeader, GenesisInfo, Headers, ParamsInfo, Trait,
+};
+
+/// The `BtcHeaderInfo` stored before the cumulative chain work and the normal bits were
+/// introduced.
+#[derive(Encode, Decode)]
+pub(crate) struct OldBtcHeaderInfo {
+    pub header: BtcHeader,
+    pub height: u32,
+}
+
+/// Adds the cumulative chain work and the bits of the last normal difficulty block to all the
+/// stored headers.
+///
+/// Both are accumulated from the genesis header (checkpoint) along the stored chains, a header
+/// whose parent is not stored only counts itself.
+///
+/// Does nothing if the genesis header can already be decoded as `BtcHeaderInfo`.
+pub fn migrate_headers_chain_work<T: Trait>() -> Weight {
+    let (genesis_header, _) = GenesisInfo::get();
+    if Headers::get(&genesis_header.hash()).is_some() {
+        return T::DbWeight::get().reads(2);
+    }
+
+    Headers::translate::<OldBtcHeaderInfo, _>(|_, old| {
+        Some(BtcHeaderInfo {
+            header: old.header,
+            height: old.height,
+            chain_work: U256::zero(),
+            normal_bits: old.header.bits,
+        })
+    });
+
+    let mut headers = Headers::iter().collect::<Vec<_>>();
+    headers.sort_by_key(|(_, info)| info.height);
+
+    let params = ParamsInfo::get();
+    let count = headers.len() as Weight;
+    let mut chain_works = BTreeMap::<H256, (U256, Compact)>::new();
+    for (hash, mut info) in headers {
+        let parent = chain_works.get(&info.header.previous_header_hash).copied();
+        let parent_work = parent.map(|(work, _)| work).unwrap_or_default();
+        info.chain_work = parent_work + block_proof(info.header.bits);
+        info.normal_bits = normal_bits(
+            &info.header,
+            info.height,
+            parent.map(|(_, bits)| bits),
+            &params,
+        );
+        chain_works.insert(hash, (info.chain_work, info.normal_bits));
+        Headers::insert(&hash, info);
+    }
+
+    T::DbWeight::get().reads_writes(3 + count * 2, count * 2)
+}
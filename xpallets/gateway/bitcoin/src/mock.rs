@@ -118,6 +118,7 @@ impl xpallet_assets::Trait for Test {
 
 impl xpallet_gateway_records::Trait for Test {
     type Event = ();
+    type GuardianOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -125,8 +126,8 @@ impl xpallet_gateway_common::Trait for Test {
     type Event = ();
     type Validator = ();
     type DetermineMultisigAddress = ();
-    type Bitcoin = XGatewayBitcoin;
-    type BitcoinTrustee = XGatewayBitcoin;
+    type Gateways = (XGatewayBitcoin,);
+    type Slasher = ();
     type WeightInfo = ();
 }
 
@@ -162,6 +163,8 @@ impl Trait for Test {
     >;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type TrusteeMisbehaviour = XGatewayCommon;
+    type TrusteeSigningRecorder = XGatewayCommon;
     type WeightInfo = ();
 }
 
@@ -230,6 +233,7 @@ impl ExtBuilder {
         let network_id = btc_network;
         let _ = GenesisConfig::<Test> {
             genesis_trustees: vec![],
+            pow_rules: None,
             genesis_info: btc_genesis,
             genesis_hash,
             network_id,
@@ -305,6 +309,7 @@ impl ExtBuilder {
 
         let _ = GenesisConfig::<Test> {
             genesis_trustees,
+            pow_rules: None,
             genesis_info,
             genesis_hash,
             network_id,
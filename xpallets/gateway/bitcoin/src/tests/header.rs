@@ -2,13 +2,22 @@
 
 use frame_support::{assert_noop, assert_ok};
 
-use light_bitcoin::{chain::BlockHeader, keys::Network, primitives::h256_rev, serialization};
+use frame_support::{storage::unhashed, StorageMap, StorageValue};
 
+use light_bitcoin::{
+    chain::BlockHeader,
+    keys::Network,
+    primitives::{h256_rev, hash_rev, Compact, U256},
+    serialization,
+};
+
+use crate::migration::{migrate_headers_chain_work, OldBtcHeaderInfo};
 use crate::mock::{
-    generate_blocks_478557_478563, generate_blocks_576576_578692, ExtBuilder, XGatewayBitcoin,
-    XGatewayBitcoinErr,
+    generate_blocks_478557_478563, generate_blocks_576576_578692, ExtBuilder, Test,
+    XGatewayBitcoin, XGatewayBitcoinErr,
 };
-use crate::types::BtcHeaderIndex;
+use crate::types::{BtcHeaderIndex, BtcParams, BtcPowRules};
+use crate::{BlockTxs, Headers, ParamsInfo, PowRules};
 
 #[test]
 fn test_genesis() {
@@ -255,3 +264,241 @@ fn test_call() {
         assert_ok!(XGatewayBitcoin::push_header(origin, v));
     })
 }
+
+fn regtest_genesis() -> BlockHeader {
+    BlockHeader {
+        version: 1,
+        previous_header_hash: Default::default(),
+        merkle_root_hash: h256_rev(
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b",
+        ),
+        time: 1296688602,
+        bits: Compact::new(545259519),
+        nonce: 2,
+    }
+}
+
+fn set_regtest_params(rules: BtcPowRules) {
+    PowRules::put(rules);
+    ParamsInfo::put(BtcParams::new(
+        545259519,            // max_bits
+        2 * 60 * 60,          // block_max_future
+        2 * 7 * 24 * 60 * 60, // target_timespan_seconds
+        10 * 60,              // target_spacing_seconds
+        4,                    // retargeting_factor
+    ));
+}
+
+fn mine_header(prev: &BlockHeader, time: u32, bits: Compact) -> BlockHeader {
+    let mut header = BlockHeader {
+        version: 0x20000000,
+        previous_header_hash: prev.hash(),
+        merkle_root_hash: Default::default(),
+        time,
+        bits,
+        nonce: 0,
+    };
+    let target = bits.to_u256().unwrap();
+    while U256::from(hash_rev(header.hash()).as_bytes()) > target {
+        header.nonce += 1;
+    }
+    header
+}
+
+#[test]
+fn test_regtest_pow_rules() {
+    let genesis = regtest_genesis();
+    assert_eq!(
+        genesis.hash(),
+        h256_rev("0f9188f13cb7b2c71f2a335e3a4fc328bf5beb436012afca590b1a11466e2206")
+    );
+    ExtBuilder::default()
+        .build_mock((genesis.clone(), 0), Network::Testnet)
+        .execute_with(|| {
+            set_regtest_params(BtcPowRules::Regtest);
+            assert_eq!(XGatewayBitcoin::pow_rules(), BtcPowRules::Regtest);
+
+            let b1 = mine_header(&genesis, genesis.time + 1, genesis.bits);
+            assert_ok!(XGatewayBitcoin::apply_push_header(b1.clone()));
+
+            // bits must be the same as the last block which is not a min-difficulty block
+            let b2 = mine_header(&b1, b1.time + 1, Compact::new(545259518));
+            assert_noop!(
+                XGatewayBitcoin::apply_push_header(b2),
+                XGatewayBitcoinErr::HeaderNBitsNotMatch
+            );
+        })
+}
+
+#[test]
+fn test_min_difficulty_blocks() {
+    let genesis = BlockHeader {
+        bits: Compact::new(0x2000ffff),
+        ..regtest_genesis()
+    };
+    ExtBuilder::default()
+        .build_mock((genesis.clone(), 0), Network::Testnet)
+        .execute_with(|| {
+            set_regtest_params(BtcPowRules::Regtest);
+            let max_bits = Compact::new(545259519);
+
+            // more than twice the target spacing, allow min difficulty
+            let b1 = mine_header(&genesis, genesis.time + 20 * 60 + 1, max_bits);
+            assert_ok!(XGatewayBitcoin::apply_push_header(b1.clone()));
+            let b2 = mine_header(&b1, b1.time + 20 * 60 + 1, max_bits);
+            assert_ok!(XGatewayBitcoin::apply_push_header(b2.clone()));
+            assert_eq!(
+                XGatewayBitcoin::headers(&b2.hash()).unwrap().normal_bits,
+                genesis.bits
+            );
+
+            // back to the bits of the last block which is not a min-difficulty block
+            let b3 = mine_header(&b2, b2.time + 1, max_bits);
+            assert_noop!(
+                XGatewayBitcoin::apply_push_header(b3),
+                XGatewayBitcoinErr::HeaderNBitsNotMatch
+            );
+            let b3 = mine_header(&b2, b2.time + 1, genesis.bits);
+            assert_ok!(XGatewayBitcoin::apply_push_header(b3));
+        });
+
+    // the nBits of the testnet headers are not checked
+    ExtBuilder::default()
+        .build_mock((genesis.clone(), 0), Network::Testnet)
+        .execute_with(|| {
+            set_regtest_params(BtcPowRules::Testnet);
+            let b1 = mine_header(&genesis, genesis.time + 1, Compact::new(545259519));
+            assert_ok!(XGatewayBitcoin::apply_push_header(b1));
+        });
+}
+
+#[test]
+fn test_pow_rules_follow_network_id() {
+    let (base_height, c1, _) = generate_blocks_478557_478563();
+    ExtBuilder::default()
+        .build_mock((c1.get(0).unwrap().clone(), base_height), Network::Testnet)
+        .execute_with(|| {
+            assert_eq!(XGatewayBitcoin::pow_rules(), BtcPowRules::Testnet);
+        });
+    ExtBuilder::default()
+        .build_mock((c1.get(0).unwrap().clone(), base_height), Network::Mainnet)
+        .execute_with(|| {
+            assert_eq!(XGatewayBitcoin::pow_rules(), BtcPowRules::Mainnet);
+        });
+}
+
+#[test]
+fn test_fork_choice_by_chain_work() {
+    // e.g.
+    // g --- b1 --- b2 --- b3 --- b4   (min difficulty blocks)
+    // | --- f1                        (normal difficulty block)
+    let genesis = BlockHeader {
+        bits: Compact::new(0x2000ffff),
+        ..regtest_genesis()
+    };
+    ExtBuilder::default()
+        .build_mock((genesis.clone(), 0), Network::Testnet)
+        .execute_with(|| {
+            set_regtest_params(BtcPowRules::Testnet);
+            let max_bits = Compact::new(545259519);
+
+            let mut chain = vec![genesis.clone()];
+            for _ in 0..4 {
+                let prev = chain.last().unwrap().clone();
+                // more than twice the target spacing, allow min difficulty
+                let b = mine_header(&prev, prev.time + 20 * 60 + 1, max_bits);
+                assert_ok!(XGatewayBitcoin::apply_push_header(b.clone()));
+                chain.push(b);
+            }
+            assert_eq!(XGatewayBitcoin::best_index().hash, chain[4].hash());
+            let confirmed_index = XGatewayBitcoin::confirmed_index().unwrap();
+            assert_eq!(confirmed_index.hash, chain[1].hash());
+            // the processed tx in the confirmed header would be reported when orphaned
+            BlockTxs::insert(&chain[1].hash(), vec![sp_core::H256::repeat_byte(1)]);
+
+            // the short fork has more work than the long chain of min difficulty blocks
+            let f1 = mine_header(&genesis, genesis.time + 1, genesis.bits);
+            assert_ok!(XGatewayBitcoin::apply_push_header(f1.clone()));
+            assert_eq!(XGatewayBitcoin::best_index().hash, f1.hash());
+            should_in_mainchain(&[genesis.clone(), f1.clone()], true);
+            should_in_mainchain(&chain[1..], false);
+            // the old confirmed header is orphaned, fallback to the common ancestor
+            let confirmed_index = XGatewayBitcoin::confirmed_index().unwrap();
+            assert_eq!(confirmed_index.hash, genesis.hash());
+
+            // the low-work long chain can't displace the real chain
+            let prev = chain.last().unwrap().clone();
+            let b6 = mine_header(&prev, prev.time + 20 * 60 + 1, max_bits);
+            assert_noop!(
+                XGatewayBitcoin::apply_push_header(b6),
+                XGatewayBitcoinErr::AncientFork
+            );
+            assert_eq!(XGatewayBitcoin::best_index().hash, f1.hash());
+        })
+}
+
+#[test]
+fn test_reject_deep_reorg() {
+    let genesis = BlockHeader {
+        bits: Compact::new(0x2000ffff),
+        ..regtest_genesis()
+    };
+    ExtBuilder::default()
+        .build_mock((genesis.clone(), 0), Network::Testnet)
+        .execute_with(|| {
+            set_regtest_params(BtcPowRules::Testnet);
+            let max_bits = Compact::new(545259519);
+
+            let mut prev = genesis.clone();
+            for _ in 0..5 {
+                let b = mine_header(&prev, prev.time + 20 * 60 + 1, max_bits);
+                assert_ok!(XGatewayBitcoin::apply_push_header(b.clone()));
+                prev = b;
+            }
+
+            // the fork would orphan 5 headers, more than the confirmation number (4)
+            let f1 = mine_header(&genesis, genesis.time + 1, genesis.bits);
+            assert_noop!(
+                XGatewayBitcoin::apply_push_header(f1),
+                XGatewayBitcoinErr::ReorgTooDeep
+            );
+            assert_eq!(XGatewayBitcoin::best_index().hash, prev.hash());
+        })
+}
+
+#[test]
+fn test_migrate_headers_chain_work() {
+    let (base_height, c1, _) = generate_blocks_478557_478563();
+    ExtBuilder::default()
+        .build_mock((c1[0].clone(), base_height), Network::Mainnet)
+        .execute_with(|| {
+            for header in c1[1..4].iter() {
+                assert_ok!(XGatewayBitcoin::apply_push_header(header.clone()));
+            }
+            let expected = c1[..4]
+                .iter()
+                .map(|header| XGatewayBitcoin::headers(&header.hash()).unwrap())
+                .collect::<Vec<_>>();
+
+            // Rewind the headers to the format without chain work.
+            for info in expected.iter() {
+                let hash = info.header.hash();
+                unhashed::put(
+                    &Headers::hashed_key_for(&hash),
+                    &OldBtcHeaderInfo {
+                        header: info.header,
+                        height: info.height,
+                    },
+                );
+                assert!(XGatewayBitcoin::headers(&hash).is_none());
+            }
+
+            migrate_headers_chain_work::<Test>();
+            for info in expected.iter() {
+                assert_eq!(
+                    XGatewayBitcoin::headers(&info.header.hash()).as_ref(),
+                    Some(info)
+                );
+            }
+        })
+}
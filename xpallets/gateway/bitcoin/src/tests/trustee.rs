@@ -1,6 +1,9 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::{assert_noop, assert_ok, storage::StorageValue};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{StorageMap, StorageValue},
+};
 use frame_system::RawOrigin;
 use hex_literal::hex;
 
@@ -12,17 +15,24 @@ use light_bitcoin::{
     serialization::{self, Reader},
 };
 
-use xpallet_gateway_common::traits::TrusteeForChain;
+use xpallet_assets::Chain;
+use xpallet_gateway_common::{
+    traits::TrusteeForChain,
+    types::{TrusteeLiveness, TrusteeSlashParams},
+};
+use xpallet_gateway_records::{
+    NextWithdrawalRecordId, PendingWithdrawals, WithdrawalDelay, WithdrawalRecord, WithdrawalState,
+};
 
 use crate::mock::{
-    alice, bob, AccountId, ExtBuilder, Test, XGatewayBitcoin, XGatewayBitcoinErr, XGatewayCommon,
-    XGatewayRecords, X_BTC,
+    alice, bob, charlie, AccountId, Balances, ExtBuilder, Test, XGatewayBitcoin,
+    XGatewayBitcoinErr, XGatewayCommon, XGatewayRecords, X_BTC, X_ETH,
 };
 use crate::{
-    trustee::create_multi_address,
-    tx::validator::parse_and_check_signed_tx_impl,
-    types::{BtcTxVerifier, BtcWithdrawalProposal, VoteResult},
-    Verifier, WithdrawalProposal,
+    trustee::{create_multi_address, MAX_WITHDRAWAL_SCAN},
+    tx::validator::{parse_and_check_signed_tx_impl, parse_signers},
+    types::{BtcTxVerifier, BtcWithdrawalBatchLimits, BtcWithdrawalProposal, VoteResult},
+    ProposalSigningSince, Verifier, WithdrawalProposal,
 };
 
 #[test]
@@ -157,6 +167,7 @@ fn test_verify_tx_sign() {
             let bytes = hex::decode(tx_hex).unwrap();
             let tx: Transaction = serialization::deserialize(Reader::new(&bytes)).unwrap();
             let script = script_hex.parse().unwrap();
+            assert_eq!(parse_signers::<Test>(&tx, &script).len(), expect as usize);
             let got = parse_and_check_signed_tx_impl::<Test>(&tx, script);
             assert_eq!(got, Ok(expect));
         }
@@ -217,3 +228,204 @@ fn force_replace_withdraw() {
         assert_eq!(XGatewayBitcoin::withdrawal_proposal().unwrap().tx, new_withdraw);
     });
 }
+
+#[test]
+fn test_withdrawal_batch() {
+    ExtBuilder::default().build_and_execute(|| {
+        let alice = alice();
+        let bob = bob();
+        let balance = 10000000;
+        XGatewayRecords::deposit(&alice, X_BTC, 3 * balance).unwrap();
+        XGatewayRecords::deposit(&bob, X_BTC, balance).unwrap();
+        for _ in 0..3 {
+            XGatewayRecords::withdraw(
+                &alice,
+                X_BTC,
+                balance,
+                b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
+                b"".to_vec().into(),
+            )
+            .unwrap();
+        }
+        XGatewayRecords::withdraw(
+            &bob,
+            X_BTC,
+            balance,
+            b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(),
+            b"".to_vec().into(),
+        )
+        .unwrap();
+
+        // fifo order
+        assert_eq!(XGatewayBitcoin::withdrawal_batch(), vec![0, 1, 2, 3]);
+
+        // per account limit, the third withdrawal of alice waits for the next batch
+        assert_ok!(XGatewayBitcoin::set_withdrawal_batch_limits(
+            RawOrigin::Root.into(),
+            BtcWithdrawalBatchLimits {
+                max_tx_size: 100_000,
+                max_per_account: 2
+            },
+        ));
+        assert_eq!(XGatewayBitcoin::withdrawal_batch(), vec![0, 1, 3]);
+
+        // target tx size, only two outputs
+        assert_ok!(XGatewayBitcoin::set_withdrawal_batch_limits(
+            RawOrigin::Root.into(),
+            BtcWithdrawalBatchLimits {
+                max_tx_size: 10 + 34 * 2,
+                max_per_account: 2
+            },
+        ));
+        assert_eq!(XGatewayBitcoin::withdrawal_batch(), vec![0, 1]);
+
+        // could not skip older withdrawals
+        assert_noop!(
+            XGatewayBitcoin::apply_create_withdraw(alice.clone(), Transaction::default(), vec![1]),
+            XGatewayBitcoinErr::SkippedWithdrawal
+        );
+        assert_noop!(
+            XGatewayBitcoin::apply_create_withdraw(alice, Transaction::default(), vec![0, 3]),
+            XGatewayBitcoinErr::SkippedWithdrawal
+        );
+    });
+}
+
+#[test]
+fn test_withdrawal_batch_skips_pending_records_of_others() {
+    ExtBuilder::default().build_and_execute(|| {
+        let alice = alice();
+        let addr = b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec();
+        XGatewayRecords::deposit(&alice, X_BTC, 1_000_000_000).unwrap();
+
+        // an X-ETH withdrawal shares the id space
+        PendingWithdrawals::<Test>::insert(
+            0,
+            WithdrawalRecord::new(
+                alice.clone(),
+                X_ETH,
+                100,
+                addr.clone(),
+                b"".to_vec().into(),
+                1,
+            ),
+        );
+        NextWithdrawalRecordId::put(1);
+
+        // a frozen delayed withdrawal
+        assert_ok!(XGatewayRecords::set_withdrawal_delay(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(WithdrawalDelay {
+                threshold: 500_000_000,
+                delay: 10
+            })
+        ));
+        assert_ok!(XGatewayRecords::withdraw(
+            &alice,
+            X_BTC,
+            600_000_000,
+            addr.clone(),
+            b"".to_vec().into()
+        ));
+        assert_eq!(XGatewayRecords::state_of(1), Some(WithdrawalState::Delayed));
+        assert_ok!(XGatewayRecords::freeze_withdrawal(
+            RawOrigin::Root.into(),
+            1
+        ));
+
+        // more than `MAX_WITHDRAWAL_SCAN` withdrawals are finished after them
+        let finished = (2..2 + MAX_WITHDRAWAL_SCAN + 44).collect::<Vec<_>>();
+        for _ in finished.iter() {
+            assert_ok!(XGatewayRecords::withdraw(
+                &alice,
+                X_BTC,
+                1000,
+                addr.clone(),
+                b"".to_vec().into()
+            ));
+        }
+        assert_ok!(XGatewayRecords::process_withdrawals(
+            &finished,
+            Chain::Bitcoin
+        ));
+        assert_ok!(XGatewayRecords::finish_withdrawals(&finished, None));
+
+        let id = XGatewayRecords::id();
+        assert_ok!(XGatewayRecords::withdraw(
+            &alice,
+            X_BTC,
+            1000,
+            addr,
+            b"".to_vec().into()
+        ));
+        assert_eq!(XGatewayBitcoin::withdrawal_batch(), vec![id]);
+    });
+}
+
+#[test]
+fn test_signing_deadline() {
+    ExtBuilder::default().build_and_execute(|| {
+        let alice = alice();
+        let bob = bob();
+        Balances::make_free_balance_be(&bob, 1000);
+        assert_ok!(XGatewayCommon::bond_trustee(
+            RawOrigin::Signed(bob.clone()).into(),
+            100
+        ));
+        assert_ok!(XGatewayCommon::set_trustee_slash_config(
+            RawOrigin::Root.into(),
+            TrusteeSlashParams {
+                missed_signing_penalty: 10,
+                ..Default::default()
+            }
+        ));
+        assert_ok!(XGatewayBitcoin::set_signing_deadline(
+            RawOrigin::Root.into(),
+            10
+        ));
+
+        let balance = 10000000;
+        XGatewayRecords::deposit(&alice, X_BTC, balance).unwrap();
+        XGatewayRecords::withdraw(
+            &alice,
+            X_BTC,
+            balance,
+            b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
+            b"".to_vec().into(),
+        )
+        .unwrap();
+        XGatewayRecords::process_withdrawals(&[0], Chain::Bitcoin).unwrap();
+        WithdrawalProposal::<Test>::put(BtcWithdrawalProposal::<AccountId> {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list: vec![0],
+            tx: Transaction::default(),
+            trustee_list: vec![(alice, true), (charlie(), false)],
+        });
+        ProposalSigningSince::<Test>::put(1);
+
+        XGatewayBitcoin::check_signing_deadline(10);
+        assert!(XGatewayBitcoin::withdrawal_proposal().is_some());
+        assert_eq!(XGatewayCommon::trustee_bond_of(&bob), 100);
+
+        // bob has not voted within the deadline, the proposal expires
+        XGatewayBitcoin::check_signing_deadline(11);
+        assert!(XGatewayBitcoin::withdrawal_proposal().is_none());
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Applying)
+        );
+        assert_eq!(XGatewayCommon::trustee_bond_of(&bob), 90);
+        assert_eq!(
+            XGatewayCommon::trustee_liveness_of(Chain::Bitcoin, &bob),
+            TrusteeLiveness {
+                missed: 1,
+                consecutive_missed: 1
+            }
+        );
+        assert_eq!(
+            XGatewayCommon::trustee_liveness_of(Chain::Bitcoin, &charlie()),
+            TrusteeLiveness::default()
+        );
+    });
+}
@@ -2,7 +2,10 @@
 
 #![allow(non_upper_case_globals)]
 
-use frame_support::{assert_noop, assert_ok, storage::StorageValue};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{StorageMap, StorageValue},
+};
 use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat};
 
 use light_bitcoin::{
@@ -19,13 +22,14 @@ use crate::mock::{
     XGatewayBitcoinErr, XGatewayCommon, X_BTC,
 };
 use crate::{
-    tx::process_tx,
+    tx::{freeze_orphaned_deposit, process_tx, prune_deposit_records, DEPOSIT_RECORD_DEPTH},
     types::{
-        BtcDepositCache, BtcRelayedTxInfo, BtcTxResult, BtcTxState, BtcWithdrawalProposal,
-        VoteResult,
+        BtcDepositCache, BtcDepositLimits, BtcHeldDeposit, BtcRelayedTxInfo, BtcTxResult,
+        BtcTxState, BtcWithdrawalProposal, VoteResult,
     },
-    Trait, WithdrawalProposal,
+    BlockHashFor, BlockTxs, DepositRecordsAt, Trait, TxState, WithdrawalProposal,
 };
+use xpallet_assets::AssetType;
 
 const DEPOSIT_HOT_ADDR: &str = "3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF";
 const DEPOSIT_COLD_ADDR: &str = "3FLBhPfEqmw4Wn5EQMeUzPLrQtJMprgwnw";
@@ -319,6 +323,230 @@ fn test_process_tx() {
     })
 }
 
+#[test]
+fn test_orphaned_deposit() {
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = deposit3_0.hash();
+        let orphaned_block = sp_core::H256::repeat_byte(1);
+        let r = mock_process_tx::<Test>(deposit3_0.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+        assert_eq!(
+            XGatewayBitcoin::deposit_records(&txid),
+            Some((account3.clone(), 100000000))
+        );
+
+        // the minted X-BTC is frozen when the block is orphaned
+        freeze_orphaned_deposit::<Test>(orphaned_block, txid);
+        assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 0);
+        assert_eq!(
+            XGatewayBitcoin::orphaned_deposits(&txid).unwrap().frozen,
+            100000000
+        );
+
+        // release to the depositor
+        assert_ok!(XGatewayBitcoin::resolve_orphaned_deposit(
+            frame_system::RawOrigin::Root.into(),
+            txid,
+            false
+        ));
+        assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 100000000);
+        assert!(XGatewayBitcoin::orphaned_deposits(&txid).is_none());
+        assert_noop!(
+            XGatewayBitcoin::resolve_orphaned_deposit(
+                frame_system::RawOrigin::Root.into(),
+                txid,
+                false
+            ),
+            XGatewayBitcoinErr::NoOrphanedDeposit
+        );
+
+        // claw back
+        freeze_orphaned_deposit::<Test>(orphaned_block, txid);
+        assert_ok!(XGatewayBitcoin::resolve_orphaned_deposit(
+            frame_system::RawOrigin::Root.into(),
+            txid,
+            true
+        ));
+        assert_eq!(XAssets::all_type_asset_balance(&account3, &X_BTC), 0);
+        assert_eq!(XGatewayBitcoin::deposit_records(&txid), None);
+        assert_eq!(XGatewayBitcoin::tx_state(&txid), None);
+    })
+}
+
+#[test]
+fn test_orphaned_deposit_shortfall() {
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = deposit3_0.hash();
+        let orphaned_block = sp_core::H256::repeat_byte(1);
+        let other: AccountId = Default::default();
+        let r = mock_process_tx::<Test>(deposit3_0.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+
+        // part of the X-BTC is spent before the reorg
+        assert_ok!(XAssets::move_usable_balance(
+            &X_BTC, &account3, &other, 40000000
+        ));
+        freeze_orphaned_deposit::<Test>(orphaned_block, txid);
+        assert_eq!(
+            XAssets::asset_balance_of(&account3, &X_BTC, AssetType::ReservedOrphaned),
+            60000000
+        );
+        assert_eq!(
+            XAssets::asset_balance_of(&account3, &X_BTC, AssetType::ReservedWithdrawal),
+            0
+        );
+
+        // the spent part is recorded as the shortfall once clawed back
+        assert_ok!(XGatewayBitcoin::resolve_orphaned_deposit(
+            frame_system::RawOrigin::Root.into(),
+            txid,
+            true
+        ));
+        assert_eq!(XAssets::all_type_asset_balance(&account3, &X_BTC), 0);
+        assert_eq!(XGatewayBitcoin::deposit_shortfalls(&account3), 40000000);
+
+        // the next deposit repays the shortfall first
+        let r = mock_process_tx::<Test>(deposit3_0.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+        assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 60000000);
+        assert_eq!(XGatewayBitcoin::deposit_shortfalls(&account3), 0);
+    })
+}
+
+#[test]
+fn test_prune_deposit_records() {
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = deposit3_0.hash();
+        let r = mock_process_tx::<Test>(deposit3_0.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+        DepositRecordsAt::insert(10, vec![txid]);
+        let block_hash = sp_core::H256::repeat_byte(1);
+        BlockHashFor::insert(10, vec![block_hash]);
+        BlockTxs::insert(&block_hash, vec![txid]);
+
+        prune_deposit_records::<Test>(10 + DEPOSIT_RECORD_DEPTH - 1);
+        assert!(XGatewayBitcoin::deposit_records(&txid).is_some());
+        assert_eq!(XGatewayBitcoin::block_txs(&block_hash), vec![txid]);
+        prune_deposit_records::<Test>(10 + DEPOSIT_RECORD_DEPTH);
+        assert_eq!(XGatewayBitcoin::deposit_records(&txid), None);
+        assert!(XGatewayBitcoin::deposit_records_at(10).is_empty());
+        assert!(XGatewayBitcoin::block_txs(&block_hash).is_empty());
+    })
+}
+
+#[test]
+fn test_deposit_limits() {
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = deposit3_0.hash();
+        assert_ok!(XGatewayBitcoin::set_deposit_limits(
+            frame_system::RawOrigin::Root.into(),
+            BtcDepositLimits {
+                per_block: None,
+                per_day: None,
+                per_account_per_day: Some(50000000),
+            },
+        ));
+
+        // exceed the daily limit of the account, the deposit is held
+        let r = mock_process_tx::<Test>(deposit3_0.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+        TxState::insert(&txid, r);
+        assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 0);
+        assert_eq!(
+            XGatewayBitcoin::held_deposits(&txid),
+            Some(BtcHeldDeposit {
+                who: account3.clone(),
+                balance: 100000000
+            })
+        );
+
+        // release the held deposit
+        assert_ok!(XGatewayBitcoin::release_held_deposit(
+            frame_system::RawOrigin::Root.into(),
+            txid
+        ));
+        assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 100000000);
+        assert_eq!(XGatewayBitcoin::held_deposits(&txid), None);
+        assert_noop!(
+            XGatewayBitcoin::release_held_deposit(frame_system::RawOrigin::Root.into(), txid),
+            XGatewayBitcoinErr::NoHeldDeposit
+        );
+    })
+}
+
+#[test]
+fn test_orphaned_held_deposit() {
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = deposit3_0.hash();
+        let orphaned_block = sp_core::H256::repeat_byte(1);
+        assert_ok!(XGatewayBitcoin::set_deposit_limits(
+            frame_system::RawOrigin::Root.into(),
+            BtcDepositLimits {
+                per_block: None,
+                per_day: None,
+                per_account_per_day: Some(50000000),
+            },
+        ));
+        let r = mock_process_tx::<Test>(deposit3_0.clone(), None);
+        TxState::insert(&txid, r);
+        assert!(XGatewayBitcoin::held_deposits(&txid).is_some());
+
+        // the held deposit is dropped when the block is orphaned
+        freeze_orphaned_deposit::<Test>(orphaned_block, txid);
+        assert_eq!(XGatewayBitcoin::held_deposits(&txid), None);
+        assert_eq!(XGatewayBitcoin::tx_state(&txid), None);
+        assert!(XGatewayBitcoin::orphaned_deposits(&txid).is_none());
+        assert_noop!(
+            XGatewayBitcoin::release_held_deposit(frame_system::RawOrigin::Root.into(), txid),
+            XGatewayBitcoinErr::NoHeldDeposit
+        );
+
+        // a held deposit whose tx is not processed in the main chain can not be released
+        let r = mock_process_tx::<Test>(deposit3_0.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+        assert_noop!(
+            XGatewayBitcoin::release_held_deposit(frame_system::RawOrigin::Root.into(), txid),
+            XGatewayBitcoinErr::NotInMainChain
+        );
+        assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 0);
+    })
+}
+
+#[test]
+fn test_orphaned_pending_deposit() {
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = deposit4_0.hash();
+        let orphaned_block = sp_core::H256::repeat_byte(1);
+        let r = mock_process_tx::<Test>(deposit4_0.clone(), Some(deposit4_0_prev.clone()));
+        assert_eq!(r.result, BtcTxResult::Success);
+        TxState::insert(&txid, r);
+        assert_eq!(
+            XGatewayBitcoin::pending_deposit_address_of(&txid),
+            Some(deposit4_addr.to_vec())
+        );
+
+        // the unclaimed deposit is dropped when the block is orphaned
+        freeze_orphaned_deposit::<Test>(orphaned_block, txid);
+        assert_eq!(
+            XGatewayBitcoin::pending_deposits(&deposit4_addr.to_vec()),
+            vec![]
+        );
+        assert_eq!(XGatewayBitcoin::pending_deposit_address_of(&txid), None);
+        assert_eq!(XGatewayBitcoin::tx_state(&txid), None);
+
+        // binding the address later only mints the deposit in the main chain
+        let r = mock_process_tx::<Test>(deposit4_1.clone(), Some(deposit4_1_prev.clone()));
+        assert_eq!(r.result, BtcTxResult::Success);
+        assert_eq!(XAssets::usable_balance(&account4, &X_BTC), 387439);
+    })
+}
+
 #[test]
 fn test_push_tx_call() {
     set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
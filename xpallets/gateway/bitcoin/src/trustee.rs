@@ -3,10 +3,16 @@
 use frame_support::{
     debug::native,
     dispatch::{DispatchError, DispatchResult},
-    ensure, StorageValue,
+    ensure,
+    traits::Get,
+    weights::Weight,
+    StorageValue,
 };
-use sp_runtime::SaturatedConversion;
-use sp_std::{convert::TryFrom, prelude::*};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
+use sp_std::{collections::btree_map::BTreeMap, convert::TryFrom, prelude::*};
 
 use light_bitcoin::{
     chain::Transaction,
@@ -18,20 +24,31 @@ use light_bitcoin::{
 
 use xp_gateway_bitcoin::extract_output_addr;
 use xp_logging::{debug, error, info};
-use xpallet_assets::Chain;
+use xpallet_assets::{Chain, ChainT};
 use xpallet_gateway_common::{
-    traits::{TrusteeForChain, TrusteeSession},
+    traits::{TrusteeForChain, TrusteeMisbehaviour, TrusteeSession, TrusteeSigningRecorder},
     trustees::bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeType},
-    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeOffence, TrusteeSessionInfo},
     utils::two_thirds_unsafe,
 };
+use xpallet_gateway_records::WithdrawalRecordId;
 
 use crate::{
-    tx::{addr2vecu8, ensure_identical, validator::parse_and_check_signed_tx},
+    tx::{
+        addr2vecu8, ensure_identical,
+        validator::{parse_and_check_signed_tx, parse_signers},
+    },
     types::{BtcWithdrawalProposal, VoteResult},
-    Error, Event, Module, Trait, WithdrawalProposal,
+    Error, Event, Module, ProposalSigningSince, Trait, WeightInfo, WithdrawalProposal,
 };
 
+/// The estimated size of the version, input/output counts and locktime of a transaction.
+const TX_BASE_SIZE: u32 = 10;
+/// The estimated size of a withdrawal (P2PKH) output, P2SH output is a bit smaller.
+const TX_OUTPUT_SIZE: u32 = 34;
+/// The max number of withdrawal records visited to propose the next withdrawal batch.
+pub const MAX_WITHDRAWAL_SCAN: u32 = 256;
+
 pub fn current_trustee_session<T: Trait>(
 ) -> Result<TrusteeSessionInfo<T::AccountId, BtcTrusteeAddrInfo>, DispatchError> {
     T::TrusteeSessionProvider::current_trustee_session()
@@ -252,6 +269,17 @@ impl<T: Trait> Module<T> {
         withdrawal_id_list.sort();
         withdrawal_id_list.dedup();
 
+        // the list must be the front part of the next withdrawal batch, thus older eligible
+        // withdrawals could not be skipped
+        let batch = Self::withdrawal_batch();
+        if withdrawal_id_list.is_empty() || !batch.starts_with(&withdrawal_id_list) {
+            error!(
+                "[apply_create_withdraw] Current list:{:?} skips older withdrawals, the next batch:{:?}",
+                withdrawal_id_list, batch
+            );
+            return Err(Error::<T>::SkippedWithdrawal.into());
+        }
+
         check_withdraw_tx::<T>(&tx, &withdrawal_id_list)?;
         info!(
             "[apply_create_withdraw] Create new withdraw, id_list:{:?}",
@@ -290,6 +318,8 @@ impl<T: Trait> Module<T> {
             who.clone(),
             withdrawal_id_list,
         ));
+        ProposalSigningSince::<T>::put(frame_system::Module::<T>::block_number());
+        T::TrusteeSigningRecorder::note_proposal(Chain::Bitcoin);
 
         if apply_sig {
             info!("[apply_create_withdraw] Apply sign after creating proposal");
@@ -307,6 +337,45 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Propose the next withdrawal batch in FIFO order.
+    ///
+    /// The batch is limited by `MaxWithdrawalCount` and the target tx size, and an account could
+    /// only have `max_per_account` withdrawals in a batch, the rest would wait for the next batch.
+    /// Withdrawals with invalid bitcoin address are skipped.
+    ///
+    /// Only the first `MAX_WITHDRAWAL_SCAN` `Applying` X-BTC withdrawals are visited, thus the
+    /// withdrawals of the other chains and the `Delayed` ones never hold the batch back.
+    pub fn withdrawal_batch() -> Vec<WithdrawalRecordId> {
+        let max_count = Self::max_withdrawal_count() as usize;
+        let limits = Self::withdrawal_batch_limits();
+        let asset_id = <Module<T> as ChainT<_>>::ASSET_ID;
+
+        let mut tx_size = TX_BASE_SIZE;
+        let mut account_count = BTreeMap::<T::AccountId, u32>::new();
+        let mut batch = Vec::new();
+        let applying = xpallet_gateway_records::Module::<T>::applying_withdrawals_of(asset_id);
+        for id in applying.into_iter().take(MAX_WITHDRAWAL_SCAN as usize) {
+            if batch.len() >= max_count || tx_size + TX_OUTPUT_SIZE > limits.max_tx_size {
+                break;
+            }
+            let record = match xpallet_gateway_records::Module::<T>::pending_withdrawals(id) {
+                Some(record) => record,
+                None => continue,
+            };
+            if Self::verify_btc_address(record.addr()).is_err() {
+                continue;
+            }
+            let count = account_count.entry(record.applicant().clone()).or_default();
+            if *count >= limits.max_per_account {
+                continue;
+            }
+            *count += 1;
+            tx_size += TX_OUTPUT_SIZE;
+            batch.push(id);
+        }
+        batch
+    }
+
     pub fn apply_sig_withdraw(who: T::AccountId, tx: Option<Transaction>) -> DispatchResult {
         let mut proposal: BtcWithdrawalProposal<T::AccountId> =
             Self::withdrawal_proposal().ok_or(Error::<T>::NoProposal)?;
@@ -352,6 +421,10 @@ impl<T: Trait> Module<T> {
                     // mark as finish, can't do anything for this proposal
                     info!("[apply_sig_withdraw] Signature completed:{}", sigs_count);
                     proposal.sig_state = VoteResult::Finish;
+                    T::TrusteeMisbehaviour::note_completed_votes(
+                        Chain::Bitcoin,
+                        &proposal.trustee_list,
+                    );
 
                     Self::deposit_event(Event::<T>::WithdrawalProposalCompleted(tx.hash()))
                 } else {
@@ -438,6 +511,74 @@ impl<T: Trait> Module<T> {
     }
 }
 
+impl<T: Trait> Module<T> {
+    /// Expire the proposal which is not signed within the signing deadline, the withdrawals
+    /// are recovered for the next proposal and the trustees who have not voted are reported.
+    pub(crate) fn check_signing_deadline(now: T::BlockNumber) -> Weight {
+        let deadline = Self::signing_deadline();
+        if deadline.is_zero() {
+            return T::DbWeight::get().reads(1);
+        }
+        let proposal = match Self::withdrawal_proposal() {
+            Some(proposal) if proposal.sig_state == VoteResult::Unfinish => proposal,
+            _ => return T::DbWeight::get().reads(2),
+        };
+        if now < Self::proposal_signing_since().saturating_add(deadline) {
+            return <T as Trait>::WeightInfo::check_signing_deadline();
+        }
+
+        let absentees = current_trustee_session::<T>()
+            .map(|info| info.trustee_list)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|who| !proposal.trustee_list.iter().any(|(voter, _)| voter == who))
+            .collect::<Vec<_>>();
+        info!(
+            "[check_signing_deadline] Proposal expired, withdrawal_ids:{:?}, absentees:{:?}",
+            proposal.withdrawal_id_list, absentees
+        );
+        T::TrusteeMisbehaviour::note_missed_signing(Chain::Bitcoin, &absentees);
+
+        for id in proposal.withdrawal_id_list.iter() {
+            let _ = xpallet_gateway_records::Module::<T>::recover_withdrawal(*id, Chain::Bitcoin);
+        }
+        WithdrawalProposal::<T>::kill();
+        ProposalSigningSince::<T>::kill();
+
+        let withdrawals = proposal.withdrawal_id_list.len() as u32;
+        Self::deposit_event(Event::<T>::WithdrawalProposalExpired(
+            proposal.withdrawal_id_list,
+        ));
+
+        <T as Trait>::WeightInfo::expire_withdrawal_proposal(withdrawals)
+    }
+}
+
+/// Report the trustees of the current session who signed a withdrawal tx which does not match
+/// the proposal, i.e. the tx was never proposed on chain.
+pub(crate) fn report_invalid_signers<T: Trait>(tx: &Transaction) {
+    let info = match current_trustee_session::<T>() {
+        Ok(info) => info,
+        Err(_) => return,
+    };
+    let redeem_script: Script = info.hot_address.redeem_script.into();
+    // the pubkeys in the redeem script are in the same order as the trustee list
+    for index in parse_signers::<T>(tx, &redeem_script) {
+        if let Some(who) = info.trustee_list.get(index) {
+            error!(
+                "[report_invalid_signers] Trustee {:?} signed the invalid withdrawal tx:{:?}",
+                who,
+                tx.hash()
+            );
+            T::TrusteeMisbehaviour::report_offence(
+                Chain::Bitcoin,
+                who,
+                TrusteeOffence::InvalidSignature,
+            );
+        }
+    }
+}
+
 /// Get the required number of signatures
 /// sig_num: Number of signatures required
 /// trustee_num: Total number of multiple signatures
@@ -513,6 +654,10 @@ fn insert_trustee_vote_state<T: Trait>(
                 "[insert_trustee_vote_state] Insert new vote, who:{:?}, state:{}",
                 who, state
             );
+            let latency = frame_system::Module::<T>::block_number()
+                .saturating_sub(Module::<T>::proposal_signing_since())
+                .saturated_into::<u32>();
+            T::TrusteeSigningRecorder::note_vote(Chain::Bitcoin, who, state, latency);
         }
     }
     Module::<T>::deposit_event(Event::<T>::WithdrawalProposalVoted(who.clone(), state));
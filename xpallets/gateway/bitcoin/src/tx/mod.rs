@@ -3,9 +3,11 @@
 mod secp256k1_verifier;
 pub mod validator;
 
-use frame_support::{debug::native, dispatch::DispatchResult, StorageMap, StorageValue};
+use frame_support::{
+    debug::native, dispatch::DispatchResult, ensure, traits::UnixTime, StorageMap, StorageValue,
+};
 use sp_runtime::{traits::Zero, SaturatedConversion};
-use sp_std::prelude::*;
+use sp_std::{cmp, prelude::*};
 
 use light_bitcoin::{
     chain::Transaction,
@@ -17,17 +19,31 @@ use chainx_primitives::AssetId;
 use xp_gateway_bitcoin::{BtcDepositInfo, BtcTxMetaType, BtcTxTypeDetector};
 use xp_gateway_common::AccountExtractor;
 use xp_logging::{debug, error, info, warn};
-use xpallet_assets::ChainT;
+use xpallet_assets::{AssetType, ChainT};
 use xpallet_gateway_common::traits::{AddressBinding, ReferralBinding};
 use xpallet_support::try_str;
 
 pub use self::validator::validate_transaction;
 use crate::{
     native,
-    types::{AccountInfo, BtcAddress, BtcDepositCache, BtcTxResult, BtcTxState},
-    BalanceOf, Error, Event, Module, PendingDeposits, Trait, WithdrawalProposal,
+    trustee::report_invalid_signers,
+    types::{
+        AccountInfo, BtcAddress, BtcDepositCache, BtcHeldDeposit, BtcOrphanedDeposit, BtcTxResult,
+        BtcTxState,
+    },
+    AccountDailyDeposited, BalanceOf, BlockDeposited, BlockTxs, DailyDeposited, DepositRecords,
+    DepositRecordsAt, DepositShortfalls, Error, Event, HeldDeposits, Module, OrphanedDeposits,
+    PendingDepositAddressOf, PendingDeposits, Trait, TxState, WithdrawalProposal,
 };
 
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The deposit records are pruned once the confirmed header is this deep past the minting,
+/// the deposits orphaned by a deeper reorg are not frozen anymore.
+pub const DEPOSIT_RECORD_DEPTH: u32 = 144;
+/// The max number of the processed txs of a block, which bounds the deposits frozen by a reorg.
+pub const MAX_BLOCK_TXS: u32 = 128;
+
 pub fn process_tx<T: Trait>(
     tx: Transaction,
     prev_tx: Option<Transaction>,
@@ -127,12 +143,60 @@ fn deposit<T: Trait>(txid: H256, deposit_info: BtcDepositInfo<T::AccountId>) ->
 }
 
 fn deposit_token<T: Trait>(txid: H256, who: &T::AccountId, balance: u64) -> DispatchResult {
+    if exceed_deposit_limits::<T>(who, balance) {
+        // hold the deposit, wait for governance or trustees to release it
+        HeldDeposits::<T>::insert(
+            &txid,
+            BtcHeldDeposit {
+                who: who.clone(),
+                balance,
+            },
+        );
+        warn!(
+            "[deposit_token] Deposit exceeds the limits, hold it for review, tx:{:?}, who:{:?}, balance:{}",
+            hash_rev(txid),
+            who,
+            balance
+        );
+        Module::<T>::deposit_event(Event::<T>::DepositHeld(
+            txid,
+            who.clone(),
+            balance.saturated_into(),
+        ));
+        return Ok(());
+    }
+    note_deposit::<T>(who, balance);
+    mint_deposit::<T>(txid, who, balance)
+}
+
+fn mint_deposit<T: Trait>(txid: H256, who: &T::AccountId, balance: u64) -> DispatchResult {
     let id: AssetId = <Module<T> as ChainT<_>>::ASSET_ID;
 
     let value: BalanceOf<T> = balance.saturated_into();
-    match <xpallet_gateway_records::Module<T>>::deposit(&who, id, value) {
+    // the shortfall of the clawed back deposits is repaid first
+    let repaid = cmp::min(Module::<T>::deposit_shortfalls(who), value);
+    let minted = value - repaid;
+    let result = if minted.is_zero() {
+        Ok(())
+    } else {
+        <xpallet_gateway_records::Module<T>>::deposit(&who, id, minted)
+    };
+    match result {
         Ok(()) => {
-            Module::<T>::deposit_event(Event::<T>::Deposited(txid, who.clone(), value));
+            if !repaid.is_zero() {
+                DepositShortfalls::<T>::mutate_exists(who, |shortfall| {
+                    *shortfall = shortfall
+                        .map(|shortfall| shortfall.saturating_sub(repaid))
+                        .filter(|shortfall| !shortfall.is_zero());
+                });
+                Module::<T>::deposit_event(Event::<T>::DepositShortfallRepaid(who.clone(), repaid));
+            }
+            // record the minted deposit, in case the block is orphaned later
+            DepositRecords::<T>::insert(&txid, (who.clone(), balance));
+            if let Some(confirmed) = Module::<T>::confirmed_index() {
+                DepositRecordsAt::append(confirmed.height, txid);
+            }
+            Module::<T>::deposit_event(Event::<T>::Deposited(txid, who.clone(), minted));
             Ok(())
         }
         Err(err) => {
@@ -145,10 +209,83 @@ fn deposit_token<T: Trait>(txid: H256, who: &T::AccountId, balance: u64) -> Disp
     }
 }
 
+/// Return the current day index, deposits in the same day share the daily caps.
+fn current_day<T: Trait>() -> u64 {
+    T::UnixTime::now().as_secs() / SECS_PER_DAY
+}
+
+/// Return the deposited value in the `current` period, reset if the period is passed.
+fn deposited_in<P: PartialEq>((period, value): (P, u64), current: &P) -> u64 {
+    if period == *current {
+        value
+    } else {
+        0
+    }
+}
+
+fn exceed_deposit_limits<T: Trait>(who: &T::AccountId, balance: u64) -> bool {
+    let limits = Module::<T>::deposit_limits();
+    let exceed = |deposited: u64, limit: Option<u64>| match limit {
+        Some(limit) => deposited.saturating_add(balance) > limit,
+        None => false,
+    };
+
+    let block_number = frame_system::Module::<T>::block_number();
+    let day = current_day::<T>();
+    exceed(
+        deposited_in(BlockDeposited::<T>::get(), &block_number),
+        limits.per_block,
+    ) || exceed(deposited_in(DailyDeposited::get(), &day), limits.per_day)
+        || exceed(
+            deposited_in(AccountDailyDeposited::<T>::get(who), &day),
+            limits.per_account_per_day,
+        )
+}
+
+fn note_deposit<T: Trait>(who: &T::AccountId, balance: u64) {
+    let block_number = frame_system::Module::<T>::block_number();
+    let day = current_day::<T>();
+    BlockDeposited::<T>::mutate(|deposited| {
+        *deposited = (
+            block_number,
+            deposited_in(*deposited, &block_number).saturating_add(balance),
+        )
+    });
+    DailyDeposited::mutate(|deposited| {
+        *deposited = (day, deposited_in(*deposited, &day).saturating_add(balance))
+    });
+    AccountDailyDeposited::<T>::mutate(who, |deposited| {
+        *deposited = (day, deposited_in(*deposited, &day).saturating_add(balance))
+    });
+}
+
+/// Mint a held deposit after review, the deposit caps are not applied.
+///
+/// The state of tx is removed once its block is orphaned, so the held deposit can only be
+/// released while the tx is still in the main chain.
+pub fn release_held_deposit<T: Trait>(txid: H256) -> DispatchResult {
+    let deposit = HeldDeposits::<T>::get(&txid).ok_or(Error::<T>::NoHeldDeposit)?;
+    ensure!(
+        Module::<T>::tx_state(&txid).map(|state| state.result) == Some(BtcTxResult::Success),
+        Error::<T>::NotInMainChain
+    );
+    mint_deposit::<T>(txid, &deposit.who, deposit.balance)?;
+    HeldDeposits::<T>::remove(&txid);
+    info!(
+        "[release_held_deposit] Release held deposit, tx:{:?}, who:{:?}, balance:{}",
+        hash_rev(txid),
+        deposit.who,
+        deposit.balance
+    );
+    Module::<T>::deposit_event(Event::<T>::HeldDepositReleased(txid));
+    Ok(())
+}
+
 pub fn remove_pending_deposit<T: Trait>(input_address: &BtcAddress, who: &T::AccountId) {
     // notice this would delete this cache
     let records = PendingDeposits::take(input_address);
     for record in records {
+        PendingDepositAddressOf::remove(&record.txid);
         // ignore error
         let _ = deposit_token::<T>(record.txid, who, record.balance);
         info!(
@@ -180,12 +317,153 @@ fn insert_pending_deposit<T: Trait>(input_address: &Address, txid: H256, balance
                 balance
             );
             list.push(cache);
+            PendingDepositAddressOf::insert(&txid, &addr_bytes);
 
             Module::<T>::deposit_event(Event::<T>::UnclaimedDeposit(txid, addr_bytes.clone()));
         }
     });
 }
 
+/// Remove the records of the deposits minted when the confirmed height was
+/// `DEPOSIT_RECORD_DEPTH` below `confirmed_height`, and the processed txs of the blocks at
+/// that height.
+pub fn prune_deposit_records<T: Trait>(confirmed_height: u32) {
+    if let Some(height) = confirmed_height.checked_sub(DEPOSIT_RECORD_DEPTH) {
+        for txid in DepositRecordsAt::take(height) {
+            DepositRecords::<T>::remove(&txid);
+        }
+        for hash in Module::<T>::block_hash_for(height) {
+            BlockTxs::remove(&hash);
+        }
+    }
+}
+
+/// Freeze the minted X-BTC of a deposit whose block was orphaned, the frozen X-BTC would be
+/// released or clawed back by governance later.
+///
+/// The depositor could have spent some of the X-BTC, the part not frozen is recorded as a
+/// shortfall if the deposit is clawed back.
+pub fn freeze_orphaned_deposit<T: Trait>(block_hash: H256, txid: H256) {
+    if let Some(held) = HeldDeposits::<T>::take(&txid) {
+        // nothing was minted, allow the tx to be processed again if it's packed in the main
+        // chain later.
+        TxState::remove(&txid);
+        warn!(
+            "[freeze_orphaned_deposit] Held deposit tx ({:?}) is orphaned, block:{:?}, who:{:?}, balance:{}",
+            hash_rev(txid),
+            hash_rev(block_hash),
+            held.who,
+            held.balance
+        );
+        Module::<T>::deposit_event(Event::<T>::HeldDepositOrphaned(txid, block_hash));
+        return;
+    }
+    if let Some(addr) = PendingDepositAddressOf::take(&txid) {
+        // nothing was minted, drop it thus a later binding of the address would not mint it.
+        PendingDeposits::mutate(&addr, |list| list.retain(|cache| cache.txid != txid));
+        TxState::remove(&txid);
+        warn!(
+            "[freeze_orphaned_deposit] Unclaimed deposit tx ({:?}) is orphaned, block:{:?}, addr:{:?}",
+            hash_rev(txid),
+            hash_rev(block_hash),
+            try_str(&addr)
+        );
+        Module::<T>::deposit_event(Event::<T>::PendingDepositOrphaned(txid, block_hash));
+        return;
+    }
+
+    let (who, balance) = match Module::<T>::deposit_records(&txid) {
+        Some(record) => record,
+        // not a minted deposit
+        None => return,
+    };
+    if OrphanedDeposits::<T>::contains_key(&txid) {
+        return;
+    }
+
+    let id: AssetId = <Module<T> as ChainT<_>>::ASSET_ID;
+    let usable = xpallet_assets::Module::<T>::usable_balance(&who, &id);
+    let mut frozen = cmp::min(usable, balance.saturated_into());
+    if let Err(err) = xpallet_assets::Module::<T>::move_balance(
+        &id,
+        &who,
+        AssetType::Usable,
+        &who,
+        AssetType::ReservedOrphaned,
+        frozen,
+    ) {
+        error!(
+            "[freeze_orphaned_deposit] Freeze deposit (tx:{:?}) error:{:?}, must use root to fix it",
+            hash_rev(txid),
+            err
+        );
+        frozen = Zero::zero();
+    }
+    warn!(
+        "[freeze_orphaned_deposit] Deposit tx ({:?}) is orphaned, block:{:?}, who:{:?}, balance:{}, frozen:{:?}",
+        hash_rev(txid),
+        hash_rev(block_hash),
+        who,
+        balance,
+        frozen
+    );
+
+    OrphanedDeposits::<T>::insert(
+        &txid,
+        BtcOrphanedDeposit {
+            block_hash,
+            who: who.clone(),
+            balance,
+            frozen,
+        },
+    );
+    Module::<T>::deposit_event(Event::<T>::DepositOrphaned(txid, block_hash, who, frozen));
+}
+
+/// Release the frozen X-BTC of an orphaned deposit to the depositor, or claw back it.
+pub fn resolve_orphaned_deposit<T: Trait>(txid: H256, claw_back: bool) -> DispatchResult {
+    let deposit = OrphanedDeposits::<T>::get(&txid).ok_or(Error::<T>::NoOrphanedDeposit)?;
+
+    let id: AssetId = <Module<T> as ChainT<_>>::ASSET_ID;
+    if claw_back {
+        xpallet_assets::Module::<T>::destroy_reserved_orphaned(&id, &deposit.who, deposit.frozen)?;
+        // the spent X-BTC is unbacked now, it would be repaid by the next deposits
+        let shortfall = deposit
+            .balance
+            .saturated_into::<BalanceOf<T>>()
+            .saturating_sub(deposit.frozen);
+        if !shortfall.is_zero() {
+            DepositShortfalls::<T>::mutate(&deposit.who, |total| {
+                *total = total.saturating_add(shortfall)
+            });
+        }
+        // the deposit is reverted, allow the tx to be processed again if it's packed in the
+        // main chain later.
+        DepositRecords::<T>::remove(&txid);
+        TxState::remove(&txid);
+    } else {
+        xpallet_assets::Module::<T>::move_balance(
+            &id,
+            &deposit.who,
+            AssetType::ReservedOrphaned,
+            &deposit.who,
+            AssetType::Usable,
+            deposit.frozen,
+        )
+        .map_err::<xpallet_assets::Error<T>, _>(Into::into)?;
+    }
+    OrphanedDeposits::<T>::remove(&txid);
+    info!(
+        "[resolve_orphaned_deposit] Resolve orphaned deposit tx ({:?}), who:{:?}, frozen:{:?}, claw_back:{}",
+        hash_rev(txid),
+        deposit.who,
+        deposit.frozen,
+        claw_back
+    );
+    Module::<T>::deposit_event(Event::<T>::OrphanedDepositResolved(txid, claw_back));
+    Ok(())
+}
+
 fn withdraw<T: Trait>(tx: Transaction) -> BtcTxResult {
     if let Some(proposal) = WithdrawalProposal::<T>::take() {
         native::debug!(
@@ -207,6 +485,11 @@ fn withdraw<T: Trait>(tx: Transaction) -> BtcTxResult {
                         .unwrap_or(BalanceOf::<T>::zero());
                 total += withdraw_balance;
 
+                // keep the txid in the withdrawal history, in the byte order of block explorers
+                xpallet_gateway_records::Module::<T>::note_withdrawal_tx(
+                    *number,
+                    hash_rev(tx_hash).as_bytes().to_vec(),
+                );
                 match xpallet_gateway_records::Module::<T>::finish_withdrawal(*number, None) {
                     Ok(_) => {
                         info!("[withdraw] Withdrawal ({}) completion", *number);
@@ -235,6 +518,11 @@ fn withdraw<T: Trait>(tx: Transaction) -> BtcTxResult {
                 "[withdraw] Withdraw error: mismatch (tx_hash:{:?}, proposal_hash:{:?}), id_list:{:?}, must use root to fix it",
                 tx_hash, proposal_hash, proposal.withdrawal_id_list
             );
+            // the signatures may differ from the proposal, only slash the signers of a tx
+            // which spends differently
+            if ensure_identical::<T>(&tx, &proposal.tx).is_err() {
+                report_invalid_signers::<T>(&tx);
+            }
             // re-store proposal into storage.
             WithdrawalProposal::<T>::put(proposal);
 
@@ -247,6 +535,7 @@ fn withdraw<T: Trait>(tx: Transaction) -> BtcTxResult {
             tx.hash()
         );
         // no proposal, but find a withdraw tx, it's a fatal error in withdrawal
+        report_invalid_signers::<T>(&tx);
         Module::<T>::deposit_event(Event::<T>::WithdrawalFatalErr(
             tx.hash(),
             Default::default(),
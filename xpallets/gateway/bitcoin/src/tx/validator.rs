@@ -116,3 +116,37 @@ pub fn parse_and_check_signed_tx_impl<T: Trait>(
         Err(Error::<T>::InvalidSignCount.into())
     }
 }
+
+/// Returns the indexes of the pubkeys in the redeem `script` which signed any input of the tx,
+/// the inputs whose signatures could not be parsed are skipped.
+pub fn parse_signers<T: Trait>(tx: &Transaction, script: &Script) -> Vec<usize> {
+    let pubkeys = match script.parse_redeem_script() {
+        Some((pubkeys, _, _)) => pubkeys,
+        None => return Vec::new(),
+    };
+    let bytes_redeem_script = script.to_bytes();
+
+    let mut signers = Vec::new();
+    for i in 0..tx.inputs.len() {
+        let script: Script = tx.inputs[i].script_sig.clone().into();
+        if script.len() < 2 {
+            continue;
+        }
+        let sigs = match script.extract_multi_scriptsig() {
+            Ok((sigs, _)) => sigs,
+            Err(_) => continue,
+        };
+        for sig in sigs.iter() {
+            let signer = pubkeys.iter().position(|pubkey| {
+                super::secp256k1_verifier::verify_sig::<T>(sig, pubkey, tx, &bytes_redeem_script, i)
+                    .is_ok()
+            });
+            if let Some(index) = signer {
+                if !signers.contains(&index) {
+                    signers.push(index);
+                }
+            }
+        }
+    }
+    signers
+}
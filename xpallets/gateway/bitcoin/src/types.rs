@@ -11,7 +11,7 @@ use light_bitcoin::{
     chain::{BlockHeader as BtcHeader, Transaction as BtcTransaction},
     keys::Address,
     merkle::PartialMerkleTree,
-    primitives::{Compact, H256},
+    primitives::{Compact, H256, U256},
 };
 
 use chainx_primitives::ReferralId;
@@ -49,6 +49,11 @@ impl BtcRelayedTxInfo {
 pub struct BtcHeaderInfo {
     pub header: BtcHeader,
     pub height: u32,
+    /// Cumulative work of the chain from the genesis header (checkpoint) to this header.
+    pub chain_work: U256,
+    /// The bits of the last block which is not a min-difficulty block up to this header,
+    /// required by the min-difficulty rule of testnet and regtest.
+    pub normal_bits: Compact,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
@@ -83,6 +88,61 @@ pub struct BtcDepositCache {
     pub balance: u64,
 }
 
+/// Deposit caps of X-BTC (satoshi), `None` means no limit.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BtcDepositLimits {
+    /// Total deposit value in a ChainX block.
+    pub per_block: Option<u64>,
+    /// Total deposit value in a day.
+    pub per_day: Option<u64>,
+    /// Deposit value of an account in a day.
+    pub per_account_per_day: Option<u64>,
+}
+
+/// A deposit exceeding the deposit caps, waiting for review.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct BtcHeldDeposit<AccountId> {
+    pub who: AccountId,
+    /// The deposit value (satoshi).
+    pub balance: u64,
+}
+
+/// Limits used to propose the next withdrawal batch.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BtcWithdrawalBatchLimits {
+    /// The target size (bytes) of the withdrawal transaction, the inputs are not counted,
+    /// due to the inputs are picked by trustees.
+    pub max_tx_size: u32,
+    /// The max withdrawal count of an account in a batch.
+    pub max_per_account: u32,
+}
+
+impl Default for BtcWithdrawalBatchLimits {
+    fn default() -> Self {
+        Self {
+            max_tx_size: 100_000,
+            max_per_account: 10,
+        }
+    }
+}
+
+/// A minted deposit whose block was orphaned by a reorg deeper than the confirmation.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct BtcOrphanedDeposit<AccountId, Balance> {
+    /// The orphaned block which contains the deposit tx.
+    pub block_hash: H256,
+    pub who: AccountId,
+    /// The deposit value (satoshi).
+    pub balance: u64,
+    /// The frozen X-BTC, may be less than the deposit value if the depositor has spent some,
+    /// the rest is recorded as a shortfall once clawed back.
+    pub frozen: Balance,
+}
+
 #[derive(PartialEq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct BtcWithdrawalProposal<AccountId> {
     pub sig_state: VoteResult,
@@ -161,6 +221,9 @@ impl BtcParams {
     pub fn target_timespan_seconds(&self) -> u32 {
         self.target_timespan_seconds
     }
+    pub fn target_spacing_seconds(&self) -> u32 {
+        self.target_spacing_seconds
+    }
 
     pub fn retargeting_interval(&self) -> u32 {
         self.retargeting_interval
@@ -174,6 +237,48 @@ impl BtcParams {
     }
 }
 
+/// The proof-of-work rules which the header verifier follows.
+///
+/// Regtest and Signet share the base58 address prefixes of Testnet, so the `NetworkId`
+/// of them is still `Testnet`, only the difficulty rules differ.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum BtcPowRules {
+    Mainnet,
+    Testnet,
+    Regtest,
+    /// Note the block signature of signet is in the coinbase witness, which can't be verified
+    /// by the header relay.
+    Signet,
+}
+
+impl Default for BtcPowRules {
+    fn default() -> Self {
+        BtcPowRules::Mainnet
+    }
+}
+
+impl BtcPowRules {
+    /// Whether the difficulty is never adjusted at the retarget height.
+    pub fn no_retargeting(&self) -> bool {
+        matches!(self, BtcPowRules::Regtest)
+    }
+
+    /// Whether a block is allowed to use the minimum difficulty if its timestamp is more than
+    /// twice the target spacing later than the previous block.
+    pub fn allow_min_difficulty_blocks(&self) -> bool {
+        matches!(self, BtcPowRules::Testnet | BtcPowRules::Regtest)
+    }
+
+    /// Whether the nBits of the header are checked against the difficulty rules.
+    ///
+    /// The testnet headers relayed to the existing chains (e.g. malan) were never checked,
+    /// keep it that way, only mainnet and the dedicated regtest and signet chains check them.
+    pub fn check_work(&self) -> bool {
+        !matches!(self, BtcPowRules::Testnet)
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum BtcTxVerifier {
@@ -41,6 +41,13 @@ pub trait WeightInfo {
     fn force_replace_proposal_tx() -> Weight;
     fn set_btc_withdrawal_fee() -> Weight;
     fn set_btc_deposit_limit() -> Weight;
+    fn resolve_orphaned_deposit() -> Weight;
+    fn set_deposit_limits() -> Weight;
+    fn release_held_deposit() -> Weight;
+    fn set_withdrawal_batch_limits() -> Weight;
+    fn set_signing_deadline() -> Weight;
+    fn check_signing_deadline() -> Weight;
+    fn expire_withdrawal_proposal(w: u32) -> Weight;
 }
 
 /// Weights for xpallet_gateway_bitcoin using the Substrate node and recommended hardware.
@@ -91,6 +98,36 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_btc_deposit_limit() -> Weight {
         (4_570_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn resolve_orphaned_deposit() -> Weight {
+        (167_431_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn set_deposit_limits() -> Weight {
+        (4_873_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn release_held_deposit() -> Weight {
+        (184_562_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn set_withdrawal_batch_limits() -> Weight {
+        (4_652_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_signing_deadline() -> Weight {
+        (4_391_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn check_signing_deadline() -> Weight {
+        (9_204_000 as Weight).saturating_add(T::DbWeight::get().reads(3 as Weight))
+    }
+    fn expire_withdrawal_proposal(w: u32) -> Weight {
+        (221_373_000 as Weight)
+            .saturating_add((27_861_000 as Weight).saturating_mul(w as Weight))
+            .saturating_add(T::DbWeight::get().reads(19 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+            .saturating_add(T::DbWeight::get().writes(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(w as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -140,4 +177,34 @@ impl WeightInfo for () {
     fn set_btc_deposit_limit() -> Weight {
         (4_570_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn resolve_orphaned_deposit() -> Weight {
+        (167_431_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn set_deposit_limits() -> Weight {
+        (4_873_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn release_held_deposit() -> Weight {
+        (184_562_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn set_withdrawal_batch_limits() -> Weight {
+        (4_652_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_signing_deadline() -> Weight {
+        (4_391_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn check_signing_deadline() -> Weight {
+        (9_204_000 as Weight).saturating_add(RocksDbWeight::get().reads(3 as Weight))
+    }
+    fn expire_withdrawal_proposal(w: u32) -> Weight {
+        (221_373_000 as Weight)
+            .saturating_add((27_861_000 as Weight).saturating_mul(w as Weight))
+            .saturating_add(RocksDbWeight::get().reads(19 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(w as Weight)))
+    }
 }
@@ -15,7 +15,10 @@ pub use xp_runtime::Memo;
 pub use xpallet_assets::{Chain, WithdrawalLimit};
 pub use xpallet_gateway_common::{
     trustees,
-    types::{GenericTrusteeIntentionProps, GenericTrusteeSessionInfo},
+    types::{
+        AddrFormat, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeLiveness,
+        TrusteeSigningStats,
+    },
 };
 
 sp_api::decl_runtime_apis! {
@@ -34,10 +37,23 @@ sp_api::decl_runtime_apis! {
         /// Get all trustee multisig.
         fn trustee_multisigs() -> BTreeMap<Chain, AccountId>;
 
+        /// Get the address format of all the supported chains.
+        fn addr_formats() -> BTreeMap<Chain, AddrFormat>;
+
         fn trustee_properties(chain: Chain, who: AccountId) -> Option<GenericTrusteeIntentionProps>;
 
         fn trustee_session_info(chain: Chain) -> Option<GenericTrusteeSessionInfo<AccountId>>;
 
         fn generate_trustee_session_info(chain: Chain, Vec<AccountId>) -> Result<GenericTrusteeSessionInfo<AccountId>, DispatchError>;
+
+        /// Propose the next withdrawal batch (withdrawal id list) in FIFO order.
+        fn withdrawal_batch(chain: Chain) -> Result<Vec<u32>, DispatchError>;
+
+        /// Get the liveness statistics of the trustees of the current session.
+        fn trustee_liveness(chain: Chain) -> Vec<(AccountId, TrusteeLiveness)>;
+
+        /// Get the withdrawal signing statistics of the trustees in the session, the current
+        /// session is used if `session_number` is None.
+        fn trustee_signing_stats(chain: Chain, session_number: Option<u32>) -> Vec<(AccountId, TrusteeSigningStats)>;
     }
 }
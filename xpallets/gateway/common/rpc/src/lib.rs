@@ -24,7 +24,8 @@ use xpallet_gateway_common_rpc_runtime_api::trustees::bitcoin::{
     BtcTrusteeIntentionProps, BtcTrusteeSessionInfo,
 };
 use xpallet_gateway_common_rpc_runtime_api::{
-    AssetId, Chain, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, WithdrawalLimit,
+    AddrFormat, AssetId, Chain, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo,
+    TrusteeLiveness, TrusteeSigningStats, WithdrawalLimit,
     XGatewayCommonApi as XGatewayCommonRuntimeApi,
 };
 
@@ -87,6 +88,27 @@ where
         candidates: Vec<AccountId>,
         at: Option<BlockHash>,
     ) -> Result<BtcTrusteeSessionInfo<AccountId>>;
+
+    /// Return the next bitcoin withdrawal batch (withdrawal id list) in FIFO order, trustees should create the withdrawal proposal for this batch (or the front part of it).
+    #[rpc(name = "xgatewaycommon_bitcoinWithdrawalBatch")]
+    fn btc_withdrawal_batch(&self, at: Option<BlockHash>) -> Result<Vec<u32>>;
+
+    /// Return the liveness statistics of the trustees of the current session, i.e. how many withdrawal proposals expired without their signatures.
+    #[rpc(name = "xgatewaycommon_trusteeLiveness")]
+    fn trustee_liveness(
+        &self,
+        chain: Chain,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(AccountId, TrusteeLiveness)>>;
+
+    /// Return the withdrawal signing statistics (proposals seen, approved, vetoed and the average latency in blocks) of the trustees in a session, the current session is used if `session_number` is not passed.
+    #[rpc(name = "xgatewaycommon_trusteeStats")]
+    fn trustee_stats(
+        &self,
+        chain: Chain,
+        session_number: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(AccountId, TrusteeSigningStats)>>;
 }
 
 /// A struct that implements the [`XStakingApi`].
@@ -186,19 +208,17 @@ where
         let result = api
             .bound_addrs(&at, who)
             .map_err(runtime_error_into_rpc_err)?;
+        let formats = api.addr_formats(&at).map_err(runtime_error_into_rpc_err)?;
 
         let result = result
             .into_iter()
             .filter_map(|(chain, addrs)| {
-                let convert: Box<dyn Fn(Vec<u8>) -> String> = match chain {
-                    Chain::Bitcoin => {
-                        Box::new(|addr: Vec<u8>| String::from_utf8_lossy(&addr).into_owned())
-                    }
-                    Chain::Ethereum => Box::new(hex::encode),
-                    _ => return None,
+                let convert: fn(Vec<u8>) -> String = match formats.get(&chain)? {
+                    AddrFormat::Utf8 => |addr: Vec<u8>| String::from_utf8_lossy(&addr).into_owned(),
+                    AddrFormat::Hex => |addr: Vec<u8>| hex::encode(addr),
                 };
 
-                Some((chain, addrs.into_iter().map(|addr| convert(addr)).collect()))
+                Some((chain, addrs.into_iter().map(convert).collect()))
             })
             .collect();
 
@@ -290,4 +310,47 @@ where
         let info = self.generate_generic_trustee_session_info(Chain::Bitcoin, candidates, at)?;
         BtcTrusteeSessionInfo::<_>::try_from(info).map_err(trustee_decode_error_into_rpc_err)
     }
+
+    fn btc_withdrawal_batch(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u32>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let result = api
+            .withdrawal_batch(&at, Chain::Bitcoin)
+            .map_err(runtime_error_into_rpc_err)?
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result)
+    }
+
+    fn trustee_liveness(
+        &self,
+        chain: Chain,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(AccountId, TrusteeLiveness)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let result = api
+            .trustee_liveness(&at, chain)
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result)
+    }
+
+    fn trustee_stats(
+        &self,
+        chain: Chain,
+        session_number: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(AccountId, TrusteeSigningStats)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let result = api
+            .trustee_signing_stats(&at, chain, session_number)
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result)
+    }
 }
@@ -1,18 +1,20 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use codec::{Decode, Encode};
-use frame_benchmarking::benchmarks;
-use frame_support::storage::StorageMap;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{storage::StorageMap, traits::Currency};
 use frame_system::RawOrigin;
 use sp_core::crypto::AccountId32;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{StaticLookup, Zero};
 use sp_std::prelude::*;
 
 use chainx_primitives::AssetId;
 use xpallet_assets::{BalanceOf, Chain};
 use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalRecordId, WithdrawalState};
 
-use crate::{types::*, Call, Module, Trait, TrusteeMultiSigAddr};
+use crate::{
+    types::*, Call, Module, PendingTrusteesOf, Trait, TrusteeMultiSigAddr, MAX_WITHDRAWAL_OUTPUTS,
+};
 
 const ASSET_ID: AssetId = xp_protocol::X_BTC;
 
@@ -83,6 +85,31 @@ benchmarks! {
         );
     }
 
+    batch_withdraw {
+        let n in 1 .. MAX_WITHDRAWAL_OUTPUTS;
+
+        let caller: T::AccountId = alice::<T>();
+        let amount: BalanceOf<T> = 10_00000000.into();
+        XGatewayRecords::<T>::deposit(&caller, ASSET_ID, amount * n.into()).unwrap();
+
+        let first = XGatewayRecords::<T>::id();
+        let outputs = (0..n)
+            .map(|_| WithdrawalOutput {
+                addr: b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec(),
+                value: amount,
+                memo: b"".to_vec().into(),
+            })
+            .collect::<Vec<_>>();
+    }: _(RawOrigin::Signed(caller.clone()), ASSET_ID, outputs)
+    verify {
+        for id in first..first + n {
+            assert_eq!(
+                XGatewayRecords::<T>::state_of(id),
+                Some(WithdrawalState::Applying)
+            );
+        }
+    }
+
     cancel_withdrawal {
         let caller: T::AccountId = alice::<T>();
         let amount: BalanceOf<T> = 10_00000000.into();
@@ -142,6 +169,23 @@ benchmarks! {
         assert!(Module::<T>::trustee_session_info_of(Chain::Bitcoin, 0).is_some());
     }
 
+    apply_trustee_transition {
+        let caller: T::AccountId = alice::<T>();
+        TrusteeMultiSigAddr::<T>::insert(Chain::Bitcoin, caller.clone());
+
+        let mut candidators = vec![];
+        for (account, about, hot, cold) in new_trustees::<T>() {
+            Module::<T>::setup_trustee_impl(account.clone(), Chain::Bitcoin, about, hot, cold).unwrap();
+            candidators.push(account);
+        }
+        PendingTrusteesOf::<T>::insert(Chain::Bitcoin, candidators);
+
+    }: _(RawOrigin::Signed(caller.clone()), Chain::Bitcoin)
+    verify {
+        assert_eq!(Module::<T>::trustee_session_info_len(Chain::Bitcoin), 1);
+        assert!(Module::<T>::pending_trustees_of(Chain::Bitcoin).is_none());
+    }
+
     set_withdrawal_state {
         let caller: T::AccountId = alice::<T>();
         TrusteeMultiSigAddr::<T>::insert(Chain::Bitcoin, caller.clone());
@@ -178,6 +222,45 @@ benchmarks! {
         assert_eq!(Module::<T>::trustee_info_config_of(Chain::Bitcoin), config);
     }
 
+    set_trustee_transition_eras {
+        let eras = 24u32;
+    }: _(RawOrigin::Root, eras)
+    verify {
+        assert_eq!(Module::<T>::trustee_transition_eras(), eras);
+    }
+
+    bond_trustee {
+        let caller: T::AccountId = whitelisted_caller();
+        let value: BalanceOf<T> = 1000u32.into();
+        <T as xpallet_assets::Trait>::Currency::make_free_balance_be(&caller, value);
+    }: _(RawOrigin::Signed(caller.clone()), value)
+    verify {
+        assert_eq!(Module::<T>::trustee_bond_of(&caller), value);
+    }
+
+    unbond_trustee {
+        let caller: T::AccountId = whitelisted_caller();
+        let value: BalanceOf<T> = 1000u32.into();
+        <T as xpallet_assets::Trait>::Currency::make_free_balance_be(&caller, value);
+        Module::<T>::bond_trustee(RawOrigin::Signed(caller.clone()).into(), value).unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), value)
+    verify {
+        assert!(Module::<T>::trustee_bond_of(&caller).is_zero());
+    }
+
+    set_trustee_slash_config {
+        let config = TrusteeSlashParams::<BalanceOf<T>> {
+            min_bond: 1000u32.into(),
+            max_consecutive_vetoes: 3,
+            veto_penalty: 10u32.into(),
+            missed_signing_penalty: 10u32.into(),
+            invalid_signature_penalty: 1000u32.into(),
+        };
+    }: _(RawOrigin::Root, config.clone())
+    verify {
+        assert_eq!(Module::<T>::trustee_slash_config(), config);
+    }
+
     force_set_referral_binding {
         let who: T::AccountId = alice::<T>();
         let who_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(who.clone());
@@ -197,11 +280,17 @@ mod tests {
     fn test_benchmarks() {
         ExtBuilder::default().build().execute_with(|| {
             assert_ok!(test_benchmark_withdraw::<Test>());
+            assert_ok!(test_benchmark_batch_withdraw::<Test>());
             assert_ok!(test_benchmark_cancel_withdrawal::<Test>());
             assert_ok!(test_benchmark_setup_trustee::<Test>());
             assert_ok!(test_benchmark_transition_trustee_session::<Test>());
+            assert_ok!(test_benchmark_apply_trustee_transition::<Test>());
             assert_ok!(test_benchmark_set_withdrawal_state::<Test>());
             assert_ok!(test_benchmark_set_trustee_info_config::<Test>());
+            assert_ok!(test_benchmark_set_trustee_transition_eras::<Test>());
+            assert_ok!(test_benchmark_bond_trustee::<Test>());
+            assert_ok!(test_benchmark_unbond_trustee::<Test>());
+            assert_ok!(test_benchmark_set_trustee_slash_config::<Test>());
             assert_ok!(test_benchmark_force_set_referral_binding::<Test>());
         });
     }
@@ -24,26 +24,35 @@ pub mod weights;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
-    ensure, IterableStorageMap,
+    ensure,
+    traits::{BalanceStatus, ReservableCurrency},
+    IterableStorageMap,
 };
 use frame_system::{ensure_root, ensure_signed};
-use sp_runtime::traits::StaticLookup;
-use sp_std::{collections::btree_map::BTreeMap, convert::TryFrom, prelude::*};
+use orml_utilities::with_transaction_result;
+use sp_runtime::traits::{Saturating, StaticLookup, Zero};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use chainx_primitives::{AddrStr, AssetId, ChainAddress, Text};
-use xp_logging::{error, info};
+use xp_logging::{error, info, warn};
 use xp_runtime::Memo;
-use xpallet_assets::{AssetRestrictions, BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_assets::{AssetRestrictions, BalanceOf, Chain, WithdrawalLimit};
 use xpallet_gateway_records::{WithdrawalRecordId, WithdrawalState};
-use xpallet_support::traits::{MultisigAddressFor, Validator};
+use xpallet_support::traits::{
+    MultisigAddressFor, OnNewEra, TreasuryAccount, Validator, ValidatorSlasher,
+};
 
-use self::traits::TrusteeForChain;
+use self::traits::{GatewayRegistry, TrusteeMisbehaviour, TrusteeSigningRecorder};
 use self::types::{
-    GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
-    TrusteeIntentionProps,
+    AddrFormat, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
+    TrusteeIntentionProps, TrusteeLiveness, TrusteeOffence, TrusteeSigningCounters,
+    TrusteeSigningStats, TrusteeSlashParams, WithdrawalOutput,
 };
 pub use self::weights::WeightInfo;
 
+/// The maximum number of the outputs in a `batch_withdraw`.
+pub const MAX_WITHDRAWAL_OUTPUTS: u32 = 16;
+
 pub trait Trait: xpallet_gateway_records::Trait {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
@@ -51,13 +60,11 @@ pub trait Trait: xpallet_gateway_records::Trait {
 
     type DetermineMultisigAddress: MultisigAddressFor<Self::AccountId>;
 
-    // for bitcoin
-    type Bitcoin: ChainT<BalanceOf<Self>>;
-    type BitcoinTrustee: TrusteeForChain<
-        Self::AccountId,
-        trustees::bitcoin::BtcTrusteeType,
-        trustees::bitcoin::BtcTrusteeAddrInfo,
-    >;
+    /// The gateways of the supported chains, e.g. `(XGatewayBitcoin, XGatewayEthereum)`.
+    type Gateways: GatewayRegistry<Self::AccountId, BalanceOf<Self>>;
+
+    /// Slash the trustees as the validators once their bonds are not enough for the penalty.
+    type Slasher: ValidatorSlasher<Self::AccountId, BalanceOf<Self>>;
 
     type WeightInfo: WeightInfo;
 }
@@ -65,6 +72,7 @@ pub trait Trait: xpallet_gateway_records::Trait {
 decl_event!(
     pub enum Event<T> where
         <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>,
     {
         /// A (potential) trustee set the required properties. [who, chain, trustee_props]
         SetTrusteeProps(AccountId, Chain, GenericTrusteeIntentionProps),
@@ -72,6 +80,15 @@ decl_event!(
         ReferralBinded(AccountId, Chain, AccountId),
         /// The trustee set of a chain was changed. [chain, session_number, session_info]
         TrusteeSetChanged(Chain, u32, GenericTrusteeSessionInfo<AccountId>),
+        /// New trustees were elected and wait for the current trustees to hand over the
+        /// session. [chain, new_trustees]
+        TrusteeTransitionPending(Chain, Vec<AccountId>),
+        /// A (potential) trustee reserved some balance as the bond. [who, value]
+        TrusteeBonded(AccountId, Balance),
+        /// A (potential) trustee unreserved some balance of the bond. [who, value]
+        TrusteeUnbonded(AccountId, Balance),
+        /// A trustee was slashed for the offence. [who, chain, offence, slashed]
+        TrusteeSlashed(AccountId, Chain, TrusteeOffence, Balance),
     }
 );
 
@@ -96,6 +113,16 @@ decl_error! {
         NotRegistered,
         /// just allow validator to register trustee
         NotValidator,
+        /// the trustee in office could not unbond
+        TrusteeInOffice,
+        /// unbond more than the bond
+        InsufficientBond,
+        /// the outputs of the batch withdrawal are empty or too many
+        InvalidWithdrawalOutputs,
+        /// no elected trustees are waiting for the transition
+        NoPendingTrustees,
+        /// the trustee session could not be changed while a withdrawal proposal is pending
+        WithdrawalProposalPending,
     }
 }
 
@@ -109,6 +136,36 @@ decl_storage! {
         pub TrusteeInfoConfigOf get(fn trustee_info_config_of):
             map hasher(twox_64_concat) Chain => TrusteeInfoConfig;
 
+        /// The trustees are elected from the validators every `TrusteeTransitionEras` eras,
+        /// zero means the election is disabled.
+        pub TrusteeTransitionEras get(fn trustee_transition_eras): u32 = 0;
+
+        /// The elected trustees of the chain waiting for the current trustees to transfer the
+        /// funds to the new hot/cold address and apply the transition.
+        pub PendingTrusteesOf get(fn pending_trustees_of):
+            map hasher(twox_64_concat) Chain => Option<Vec<T::AccountId>>;
+
+        /// The bond requirement and the penalties of the trustees.
+        pub TrusteeSlashConfig get(fn trustee_slash_config): TrusteeSlashParams<BalanceOf<T>>;
+
+        /// The balance reserved by the (potential) trustee as the bond.
+        pub TrusteeBondOf get(fn trustee_bond_of):
+            map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
+        /// The liveness statistics of the trustee of the corresponding chain.
+        pub TrusteeLivenessOf get(fn trustee_liveness_of):
+            double_map hasher(twox_64_concat) Chain, hasher(blake2_128_concat) T::AccountId
+            => TrusteeLiveness;
+
+        /// The withdrawal signing counters of the trustee in the corresponding chain and session.
+        pub TrusteeSigningCountersOf get(fn trustee_signing_counters_of):
+            double_map hasher(twox_64_concat) (Chain, u32), hasher(blake2_128_concat) T::AccountId
+            => TrusteeSigningCounters;
+
+        /// The consecutive vetoes of the trustee on the proposals completed by the others.
+        pub TrusteeVetoesOf get(fn trustee_vetoes_of):
+            double_map hasher(twox_64_concat) Chain, hasher(blake2_128_concat) T::AccountId => u32;
+
         /// Next Trustee session info number of the chain.
         ///
         /// Auto generate a new session number (0) when generate new trustee of a chain.
@@ -178,7 +235,8 @@ decl_module! {
         ///
         /// WithdrawalRecord State: `Applying`
         ///
-        /// NOTE: `ext` is for the compatibility purpose, e.g., EOS requires a memo when doing the transfer.
+        /// NOTE: `ext` is the memo of the withdrawal which is checked by the gateway of the target
+        /// chain, e.g., it's the calldata for Ethereum.
         #[weight = <T as Trait>::WeightInfo::withdraw()]
         pub fn withdraw(
             origin,
@@ -199,9 +257,47 @@ decl_module! {
             Ok(())
         }
 
+        /// Create a withdrawal for each of the outputs, which pays several addresses of the
+        /// target chain in one call. All the outputs are created or none of them.
+        ///
+        /// WithdrawalRecord State: `Applying`
+        #[weight = <T as Trait>::WeightInfo::batch_withdraw(outputs.len() as u32)]
+        pub fn batch_withdraw(
+            origin,
+            #[compact] asset_id: AssetId,
+            outputs: Vec<WithdrawalOutput<BalanceOf<T>>>
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                !outputs.is_empty() && outputs.len() <= MAX_WITHDRAWAL_OUTPUTS as usize,
+                Error::<T>::InvalidWithdrawalOutputs
+            );
+
+            ensure!(
+                xpallet_assets::Module::<T>::can_do(&asset_id, AssetRestrictions::WITHDRAW),
+                xpallet_assets::Error::<T>::ActionNotAllowed,
+            );
+            for output in outputs.iter() {
+                Self::verify_withdrawal(asset_id, output.value, &output.addr, &output.memo)?;
+            }
+
+            with_transaction_result(|| {
+                for output in outputs {
+                    xpallet_gateway_records::Module::<T>::withdraw(
+                        &who,
+                        asset_id,
+                        output.value,
+                        output.addr,
+                        output.memo,
+                    )?;
+                }
+                Ok(())
+            })
+        }
+
         /// Cancel the withdrawal by the applicant.
         ///
-        /// WithdrawalRecord State: `Applying` ==> `NormalCancel`
+        /// WithdrawalRecord State: `Applying`|`Delayed` ==> `NormalCancel`
         #[weight = <T as Trait>::WeightInfo::cancel_withdrawal()]
         pub fn cancel_withdrawal(origin, id: WithdrawalRecordId) -> DispatchResult {
             let from = ensure_signed(origin)?;
@@ -248,6 +344,36 @@ decl_module! {
             Self::transition_trustee_session_impl(chain, new_trustees)
         }
 
+        /// Apply the pending trustees elected in the new era, by the current trustees after
+        /// they have signed the transition transaction.
+        #[weight = <T as Trait>::WeightInfo::apply_trustee_transition(
+            Module::<T>::trustee_info_config_of(chain).max_trustee_count
+        )]
+        pub fn apply_trustee_transition(origin, chain: Chain) -> DispatchResult {
+            match ensure_signed(origin.clone()) {
+                Ok(who) => {
+                    if who != Self::trustee_multisig_addr(chain) {
+                        return Err(Error::<T>::InvalidMultisig.into());
+                    }
+                },
+                Err(_) => {
+                    ensure_root(origin)?;
+                },
+            };
+
+            let new_trustees = Self::pending_trustees_of(chain).ok_or(Error::<T>::NoPendingTrustees)?;
+            ensure!(
+                !T::Gateways::has_pending_proposal(chain).unwrap_or(false),
+                Error::<T>::WithdrawalProposalPending
+            );
+            info!(
+                "[apply_trustee_transition] Try to transition trustees, chain:{:?}, new_trustees:{:?}",
+                chain,
+                new_trustees
+            );
+            Self::transition_trustee_session_impl(chain, new_trustees)
+        }
+
         /// Set the state of withdraw record by the trustees.
         #[weight = <T as Trait>::WeightInfo::set_withdrawal_state()]
         pub fn set_withdrawal_state(
@@ -276,6 +402,51 @@ decl_module! {
             Ok(())
         }
 
+        /// Set the number of eras between the trustee elections, zero disables the election.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_trustee_transition_eras()]
+        pub fn set_trustee_transition_eras(origin, #[compact] eras: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            TrusteeTransitionEras::put(eras);
+            Ok(())
+        }
+
+        /// Reserve some balance as the trustee bond, only the candidates whose bond reaches
+        /// the `min_bond` would be elected as the trustees.
+        #[weight = <T as Trait>::WeightInfo::bond_trustee()]
+        pub fn bond_trustee(origin, #[compact] value: BalanceOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            <T as xpallet_assets::Trait>::Currency::reserve(&who, value)?;
+            Self::set_trustee_bond(&who, Self::trustee_bond_of(&who).saturating_add(value));
+            Self::deposit_event(Event::<T>::TrusteeBonded(who, value));
+            Ok(())
+        }
+
+        /// Unreserve some balance of the trustee bond, the trustees in office could not unbond.
+        #[weight = <T as Trait>::WeightInfo::unbond_trustee()]
+        pub fn unbond_trustee(origin, #[compact] value: BalanceOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::is_trustee_in_office(&who), Error::<T>::TrusteeInOffice);
+            let bond = Self::trustee_bond_of(&who);
+            ensure!(value <= bond, Error::<T>::InsufficientBond);
+
+            <T as xpallet_assets::Trait>::Currency::unreserve(&who, value);
+            Self::set_trustee_bond(&who, bond - value);
+            Self::deposit_event(Event::<T>::TrusteeUnbonded(who, value));
+            Ok(())
+        }
+
+        /// Set the bond requirement and the penalties of the trustees.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_trustee_slash_config()]
+        pub fn set_trustee_slash_config(origin, config: TrusteeSlashParams<BalanceOf<T>>) -> DispatchResult {
+            ensure_root(origin)?;
+            TrusteeSlashConfig::<T>::put(config);
+            Ok(())
+        }
+
         /// Set the referral binding of corresponding chain and account.
         ///
         /// This is a root-only operation.
@@ -301,10 +472,8 @@ impl<T: Trait> Module<T> {
         asset_id: &AssetId,
     ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
         let chain = xpallet_assets_registrar::Module::<T>::chain_of(asset_id)?;
-        match chain {
-            Chain::Bitcoin => T::Bitcoin::withdrawal_limit(&asset_id),
-            _ => Err(Error::<T>::NotSupportedChain.into()),
-        }
+        T::Gateways::withdrawal_limit(chain, asset_id)
+            .unwrap_or_else(|| Err(Error::<T>::NotSupportedChain.into()))
     }
 
     pub fn verify_withdrawal(
@@ -313,16 +482,11 @@ impl<T: Trait> Module<T> {
         addr: &[u8],
         ext: &Memo,
     ) -> DispatchResult {
-        ext.check_validity()?;
-
         let chain = xpallet_assets_registrar::Module::<T>::chain_of(&asset_id)?;
-        match chain {
-            Chain::Bitcoin => {
-                // bitcoin do not need memo
-                T::Bitcoin::check_addr(&addr, b"")?;
-            }
-            _ => return Err(Error::<T>::NotSupportedChain.into()),
-        };
+        // the memo is checked by the gateway, e.g. it's the calldata for Ethereum
+        T::Gateways::check_memo(chain, ext).ok_or(Error::<T>::NotSupportedChain)??;
+        T::Gateways::check_addr(chain, &addr, ext.as_ref())
+            .ok_or(Error::<T>::NotSupportedChain)??;
         // we could only split withdrawal limit due to a runtime-api would call `withdrawal_limit`
         // to export `WithdrawalLimit` for an asset.
         let limit = Self::withdrawal_limit(&asset_id)?;
@@ -354,14 +518,10 @@ impl<T: Trait> Module<T> {
     ) -> DispatchResult {
         is_valid_about::<T>(&about)?;
 
-        let (hot, cold) = match chain {
-            Chain::Bitcoin => {
-                let hot = T::BitcoinTrustee::check_trustee_entity(&hot_entity)?;
-                let cold = T::BitcoinTrustee::check_trustee_entity(&cold_entity)?;
-                (hot.into(), cold.into())
-            }
-            _ => return Err(Error::<T>::NotSupportedChain.into()),
-        };
+        let hot = T::Gateways::check_trustee_entity(chain, &hot_entity)
+            .ok_or(Error::<T>::NotSupportedChain)??;
+        let cold = T::Gateways::check_trustee_entity(chain, &cold_entity)
+            .ok_or(Error::<T>::NotSupportedChain)??;
 
         let props = GenericTrusteeIntentionProps(TrusteeIntentionProps::<Vec<u8>> {
             about,
@@ -399,24 +559,8 @@ impl<T: Trait> Module<T> {
             })?;
             props.push((accountid, p));
         }
-        let info = match chain {
-            Chain::Bitcoin => {
-                let props = props
-                    .into_iter()
-                    .map(|(id, prop)| {
-                        (
-                            id,
-                            TrusteeIntentionProps::<_>::try_from(prop)
-                                .expect("must decode succss from storage data"),
-                        )
-                    })
-                    .collect();
-                let session_info = T::BitcoinTrustee::generate_trustee_session_info(props, config)?;
-
-                session_info.into()
-            }
-            _ => return Err(Error::<T>::NotSupportedChain.into()),
-        };
+        let info = T::Gateways::generate_trustee_session_info(chain, props, config)
+            .ok_or(Error::<T>::NotSupportedChain)??;
         Ok(info)
     }
 
@@ -434,6 +578,7 @@ impl<T: Trait> Module<T> {
         TrusteeSessionInfoLen::insert(chain, next_number);
         TrusteeSessionInfoOf::<T>::insert(chain, session_number, info.clone());
         TrusteeMultiSigAddr::<T>::insert(chain, multi_addr);
+        PendingTrusteesOf::<T>::remove(chain);
 
         Self::deposit_event(Event::<T>::TrusteeSetChanged(chain, session_number, info));
         Ok(())
@@ -457,14 +602,277 @@ impl<T: Trait> Module<T> {
         Ok(multi_addr)
     }
 
+    /// Elect the trustees of the chain from the validators ranked by the total nominations,
+    /// the validators who have not set the trustee properties of the chain or whose bond is
+    /// less than the `min_bond` are skipped.
+    pub fn elect_trustees(chain: Chain) -> Option<Vec<T::AccountId>> {
+        let config = Self::trustee_info_config_of(chain);
+        let min_bond = Self::trustee_slash_config().min_bond;
+        let trustees = T::Validator::ranked_validators()
+            .into_iter()
+            .filter(|who| TrusteeIntentionPropertiesOf::<T>::contains_key(who, chain))
+            .filter(|who| Self::trustee_bond_of(who) >= min_bond)
+            .take(config.max_trustee_count as usize)
+            .collect::<Vec<_>>();
+        if (trustees.len() as u32) < config.min_trustee_count {
+            warn!(
+                "[elect_trustees] Not enough candidates for chain:{:?}, candidates:{:?}, min:{}",
+                chain, trustees, config.min_trustee_count
+            );
+            return None;
+        }
+        Some(trustees)
+    }
+
+    /// Elect the trustees of the chain in the new era.
+    ///
+    /// The first trustees take office immediately, otherwise the elected trustees are kept as
+    /// pending until the current trustees move the funds to the new hot/cold address and
+    /// apply the transition, the same as the manual `transition_trustee_session`.
+    fn rotate_trustees(chain: Chain) {
+        if T::Gateways::has_pending_proposal(chain).unwrap_or(false) {
+            info!(
+                "[rotate_trustees] Skip the rotation while a withdrawal proposal is pending, chain:{:?}",
+                chain
+            );
+            return;
+        }
+        let mut new_trustees = match Self::elect_trustees(chain) {
+            Some(trustees) => trustees,
+            None => return,
+        };
+        let current = Self::trustee_session_info_len(chain)
+            .checked_sub(1)
+            .and_then(|number| Self::trustee_session_info_of(chain, number));
+        let GenericTrusteeSessionInfo(info) = match current {
+            Some(info) => info,
+            None => {
+                info!(
+                    "[rotate_trustees] Setup the first trustees, chain:{:?}, new_trustees:{:?}",
+                    chain, new_trustees
+                );
+                if let Err(err) = Self::transition_trustee_session_impl(chain, new_trustees) {
+                    error!(
+                        "[rotate_trustees] Transition trustees failed, chain:{:?}, error:{:?}",
+                        chain, err
+                    );
+                }
+                return;
+            }
+        };
+        // the current trustees are re-elected, keep the current session
+        let mut current_trustees = info.trustee_list;
+        current_trustees.sort();
+        new_trustees.sort();
+        if current_trustees == new_trustees {
+            PendingTrusteesOf::<T>::remove(chain);
+            return;
+        }
+        if Self::pending_trustees_of(chain).as_ref() == Some(&new_trustees) {
+            return;
+        }
+
+        info!(
+            "[rotate_trustees] Wait for the current trustees to apply the transition, chain:{:?}, new_trustees:{:?}",
+            chain, new_trustees
+        );
+        PendingTrusteesOf::<T>::insert(chain, &new_trustees);
+        Self::deposit_event(Event::<T>::TrusteeTransitionPending(chain, new_trustees));
+    }
+
     fn set_referral_binding(chain: Chain, who: T::AccountId, referral: T::AccountId) {
         ReferralBindingOf::<T>::insert(&who, &chain, referral.clone());
         Self::deposit_event(Event::<T>::ReferralBinded(who, chain, referral))
     }
 }
 
+// slashing
+impl<T: Trait> Module<T> {
+    /// Returns true if the account is in the current trustee session of any chain.
+    pub fn is_trustee_in_office(who: &T::AccountId) -> bool {
+        TrusteeSessionInfoLen::iter().any(|(chain, len)| {
+            len.checked_sub(1)
+                .and_then(|number| Self::trustee_session_info_of(chain, number))
+                .map(|info| info.0.trustee_list.contains(who))
+                .unwrap_or(false)
+        })
+    }
+
+    fn set_trustee_bond(who: &T::AccountId, bond: BalanceOf<T>) {
+        if bond.is_zero() {
+            TrusteeBondOf::<T>::remove(who);
+        } else {
+            TrusteeBondOf::<T>::insert(who, bond);
+        }
+    }
+
+    /// Slash the trustee for the offence, the penalty is taken from the trustee bond first,
+    /// the rest is slashed from the reward pot of the trustee as a validator.
+    ///
+    /// The slashed balance goes to the treasury.
+    pub fn slash_trustee(chain: Chain, who: &T::AccountId, offence: TrusteeOffence) {
+        let penalty = Self::trustee_slash_config().penalty_of(offence);
+        if penalty.is_zero() {
+            return;
+        }
+
+        let bond = Self::trustee_bond_of(who);
+        let from_bond = penalty.min(bond);
+        let treasury = <T as xpallet_assets::Trait>::TreasuryAccount::treasury_account();
+        // returns the balance which could not be repatriated
+        let remaining = <T as xpallet_assets::Trait>::Currency::repatriate_reserved(
+            who,
+            &treasury,
+            from_bond,
+            BalanceStatus::Free,
+        )
+        .unwrap_or(from_bond);
+        let slashed_bond = from_bond - remaining;
+        Self::set_trustee_bond(who, bond - slashed_bond);
+
+        let slashed = slashed_bond.saturating_add(T::Slasher::slash(who, penalty - slashed_bond));
+        info!(
+            "[slash_trustee] Slash trustee:{:?} of chain:{:?} for {:?}, penalty:{:?}, slashed:{:?}",
+            who, chain, offence, penalty, slashed
+        );
+        Self::deposit_event(Event::<T>::TrusteeSlashed(
+            who.clone(),
+            chain,
+            offence,
+            slashed,
+        ));
+    }
+}
+
+impl<T: Trait> TrusteeMisbehaviour<T::AccountId> for Module<T> {
+    fn report_offence(chain: Chain, who: &T::AccountId, offence: TrusteeOffence) {
+        Self::slash_trustee(chain, who, offence)
+    }
+
+    fn note_missed_signing(chain: Chain, absentees: &[T::AccountId]) {
+        for who in absentees {
+            TrusteeLivenessOf::<T>::mutate(chain, who, |liveness| {
+                liveness.missed = liveness.missed.saturating_add(1);
+                liveness.consecutive_missed = liveness.consecutive_missed.saturating_add(1);
+            });
+            Self::slash_trustee(chain, who, TrusteeOffence::MissedSigning);
+        }
+    }
+
+    fn note_completed_votes(chain: Chain, votes: &[(T::AccountId, bool)]) {
+        let max_vetoes = Self::trustee_slash_config().max_consecutive_vetoes;
+        for (who, approve) in votes {
+            if Self::trustee_liveness_of(chain, who).consecutive_missed != 0 {
+                TrusteeLivenessOf::<T>::mutate(chain, who, |liveness| {
+                    liveness.consecutive_missed = 0
+                });
+            }
+            if *approve {
+                TrusteeVetoesOf::<T>::remove(chain, who);
+                continue;
+            }
+            let vetoes = Self::trustee_vetoes_of(chain, who).saturating_add(1);
+            if max_vetoes != 0 && vetoes >= max_vetoes {
+                TrusteeVetoesOf::<T>::remove(chain, who);
+                Self::slash_trustee(chain, who, TrusteeOffence::RepeatedVeto);
+            } else {
+                TrusteeVetoesOf::<T>::insert(chain, who, vetoes);
+            }
+        }
+    }
+}
+
+impl<T: Trait> TrusteeSigningRecorder<T::AccountId> for Module<T> {
+    fn note_proposal(chain: Chain) {
+        let number = match Self::trustee_session_info_len(chain).checked_sub(1) {
+            Some(number) => number,
+            None => return,
+        };
+        if let Some(info) = Self::trustee_session_info_of(chain, number) {
+            for who in info.0.trustee_list.iter() {
+                TrusteeSigningCountersOf::<T>::mutate((chain, number), who, |counters| {
+                    counters.seen = counters.seen.saturating_add(1)
+                });
+            }
+        }
+    }
+
+    fn note_vote(chain: Chain, who: &T::AccountId, approve: bool, latency: u32) {
+        let number = match Self::trustee_session_info_len(chain).checked_sub(1) {
+            Some(number) => number,
+            None => return,
+        };
+        TrusteeSigningCountersOf::<T>::mutate((chain, number), who, |counters| {
+            if approve {
+                counters.approved = counters.approved.saturating_add(1);
+            } else {
+                counters.vetoed = counters.vetoed.saturating_add(1);
+            }
+            counters.total_latency = counters.total_latency.saturating_add(latency.into());
+        });
+    }
+}
+
+impl<T: Trait> OnNewEra for Module<T> {
+    fn on_new_era(era_index: u32) {
+        let eras = Self::trustee_transition_eras();
+        if eras == 0 || era_index == 0 || era_index % eras != 0 {
+            return;
+        }
+        for (chain, _) in TrusteeInfoConfigOf::iter() {
+            Self::rotate_trustees(chain);
+        }
+    }
+}
+
 impl<T: Trait> Module<T> {
     pub fn trustee_multisigs() -> BTreeMap<Chain, T::AccountId> {
         TrusteeMultiSigAddr::<T>::iter().collect()
     }
+
+    pub fn addr_formats() -> BTreeMap<Chain, AddrFormat> {
+        T::Gateways::addr_formats().into_iter().collect()
+    }
+
+    /// Returns the withdrawal signing statistics of the trustees of the session, the current
+    /// session is used if `session_number` is None.
+    pub fn trustee_signing_stats(
+        chain: Chain,
+        session_number: Option<u32>,
+    ) -> Vec<(T::AccountId, TrusteeSigningStats)> {
+        session_number
+            .or_else(|| Self::trustee_session_info_len(chain).checked_sub(1))
+            .and_then(|number| {
+                Self::trustee_session_info_of(chain, number).map(|info| (number, info))
+            })
+            .map(|(number, info)| {
+                info.0
+                    .trustee_list
+                    .into_iter()
+                    .map(|who| {
+                        let counters = Self::trustee_signing_counters_of((chain, number), &who);
+                        (who, counters.into())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the liveness statistics of the trustees of the current session.
+    pub fn trustee_liveness(chain: Chain) -> Vec<(T::AccountId, TrusteeLiveness)> {
+        Self::trustee_session_info_len(chain)
+            .checked_sub(1)
+            .and_then(|number| Self::trustee_session_info_of(chain, number))
+            .map(|info| {
+                info.0
+                    .trustee_list
+                    .into_iter()
+                    .map(|who| {
+                        let liveness = Self::trustee_liveness_of(chain, &who);
+                        (who, liveness)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
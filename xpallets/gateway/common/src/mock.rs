@@ -16,12 +16,12 @@ use sp_runtime::{
 
 use chainx_primitives::AssetId;
 pub use xp_protocol::{X_BTC, X_ETH};
-use xpallet_assets::{AssetRestrictions, BalanceOf, ChainT, WithdrawalLimit};
+use xpallet_assets::{AssetRestrictions, BalanceOf, WithdrawalLimit};
 use xpallet_assets_registrar::{AssetInfo, Chain};
 use xpallet_support::traits::{MultisigAddressFor, Validator};
 
 use crate::{
-    traits::TrusteeForChain,
+    traits::{ChainGateway, TrusteeForChain},
     trustees::bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeMultisig, BtcTrusteeType},
     types::*,
 };
@@ -108,6 +108,7 @@ impl xpallet_assets::Trait for Test {
 
 impl xpallet_gateway_records::Trait for Test {
     type Event = ();
+    type GuardianOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -137,6 +138,8 @@ impl xpallet_gateway_bitcoin::Trait for Test {
     type TrusteeOrigin = EnsureSignedBy<BtcTrusteeMultisig<Test>, AccountId>;
     type ReferralBinding = ();
     type AddressBinding = ();
+    type TrusteeMisbehaviour = ();
+    type TrusteeSigningRecorder = ();
     type WeightInfo = ();
 }
 
@@ -147,6 +150,9 @@ impl MultisigAddressFor<AccountId> for MultisigAddr {
         AccountId::decode(&mut &entropy[..]).unwrap_or_default()
     }
 }
+thread_local! {
+    pub static VALIDATORS: RefCell<Vec<AccountId>> = RefCell::new(Vec::new());
+}
 pub struct AlwaysValidator;
 impl Validator<AccountId> for AlwaysValidator {
     fn is_validator(_who: &AccountId) -> bool {
@@ -156,22 +162,45 @@ impl Validator<AccountId> for AlwaysValidator {
     fn validator_for(_: &[u8]) -> Option<AccountId> {
         None
     }
+
+    fn ranked_validators() -> Vec<AccountId> {
+        VALIDATORS.with(|v| v.borrow().clone())
+    }
 }
 pub struct MockBitcoin<T: xpallet_gateway_bitcoin::Trait>(sp_std::marker::PhantomData<T>);
-impl<T: xpallet_gateway_bitcoin::Trait> ChainT<BalanceOf<T>> for MockBitcoin<T> {
-    const ASSET_ID: u32 = X_BTC;
-
+impl<T: xpallet_gateway_bitcoin::Trait> ChainGateway<T::AccountId, BalanceOf<T>>
+    for MockBitcoin<T>
+{
     fn chain() -> Chain {
         Chain::Bitcoin
     }
 
-    fn check_addr(_: &[u8], _: &[u8]) -> DispatchResult {
-        Ok(())
+    fn addr_format() -> AddrFormat {
+        AddrFormat::Utf8
     }
 
     fn withdrawal_limit(asset_id: &u32) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
         xpallet_gateway_bitcoin::Module::<T>::withdrawal_limit(asset_id)
     }
+
+    fn check_addr(_: &[u8], _: &[u8]) -> DispatchResult {
+        Ok(())
+    }
+
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<Vec<u8>, DispatchError> {
+        Self::check_generic_trustee_entity(raw_addr)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, GenericTrusteeIntentionProps)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<GenericTrusteeSessionInfo<T::AccountId>, DispatchError> {
+        Self::generate_generic_trustee_session_info(props, config)
+    }
+
+    fn has_pending_proposal() -> bool {
+        xpallet_gateway_bitcoin::Module::<T>::withdrawal_proposal().is_some()
+    }
 }
 impl<T: xpallet_gateway_bitcoin::Trait>
     TrusteeForChain<T::AccountId, BtcTrusteeType, BtcTrusteeAddrInfo> for MockBitcoin<T>
@@ -205,8 +234,8 @@ impl crate::Trait for Test {
     type Event = ();
     type Validator = AlwaysValidator;
     type DetermineMultisigAddress = MultisigAddr;
-    type Bitcoin = MockBitcoin<Test>;
-    type BitcoinTrustee = MockBitcoin<Test>;
+    type Gateways = (MockBitcoin<Test>,);
+    type Slasher = ();
     type WeightInfo = ();
 }
 
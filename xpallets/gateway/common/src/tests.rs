@@ -1,8 +1,359 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use crate::mock::ExtBuilder;
+use frame_support::{assert_noop, assert_ok, traits::Currency, StorageValue};
+use frame_system::RawOrigin;
+use sp_core::{crypto::UncheckedInto, H256};
+
+use light_bitcoin::chain::Transaction;
+use xpallet_assets::Chain;
+use xpallet_gateway_bitcoin::{BtcWithdrawalProposal, VoteResult, WithdrawalProposal};
+use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalState};
+use xpallet_support::traits::OnNewEra;
+
+use crate::mock::{AccountId, Balances, ExtBuilder, Test, VALIDATORS, X_BTC};
+use crate::traits::{TrusteeMisbehaviour, TrusteeSigningRecorder};
+use crate::types::{
+    AddrFormat, TrusteeLiveness, TrusteeOffence, TrusteeSigningStats, TrusteeSlashParams,
+    WithdrawalOutput,
+};
+use crate::{Error, Module};
+
+fn account(byte: u8) -> AccountId {
+    H256::repeat_byte(byte).unchecked_into()
+}
 
 #[test]
 fn base() {
     ExtBuilder::default().build().execute_with(|| {})
 }
+
+#[test]
+fn test_gateway_registry() {
+    ExtBuilder::default().build().execute_with(|| {
+        let formats = Module::<Test>::addr_formats();
+        assert_eq!(formats.get(&Chain::Bitcoin), Some(&AddrFormat::Utf8));
+        assert_eq!(formats.get(&Chain::Ethereum), None);
+
+        // the chain without a gateway is not supported
+        assert_noop!(
+            Module::<Test>::setup_trustee_impl(
+                account(1),
+                Chain::Ethereum,
+                b"".to_vec(),
+                vec![0; 20],
+                vec![0; 20],
+            ),
+            Error::<Test>::NotSupportedChain
+        );
+    })
+}
+
+#[test]
+fn test_elect_trustees() {
+    ExtBuilder::default().build().execute_with(|| {
+        // account 4 has not set the trustee properties of bitcoin
+        VALIDATORS.with(|v| *v.borrow_mut() = vec![account(3), account(4), account(1)]);
+        assert_eq!(Module::<Test>::elect_trustees(Chain::Bitcoin), None);
+
+        VALIDATORS.with(|v| v.borrow_mut().push(account(2)));
+        assert_eq!(
+            Module::<Test>::elect_trustees(Chain::Bitcoin),
+            Some(vec![account(3), account(1), account(2)])
+        );
+    })
+}
+
+#[test]
+fn test_rotate_trustees_on_new_era() {
+    ExtBuilder::default().build().execute_with(|| {
+        VALIDATORS.with(|v| *v.borrow_mut() = vec![account(1), account(2), account(3)]);
+
+        // the election is disabled by default
+        Module::<Test>::on_new_era(2);
+        assert_eq!(Module::<Test>::trustee_session_info_len(Chain::Bitcoin), 0);
+
+        assert_ok!(Module::<Test>::set_trustee_transition_eras(
+            RawOrigin::Root.into(),
+            2
+        ));
+        Module::<Test>::on_new_era(1);
+        assert_eq!(Module::<Test>::trustee_session_info_len(Chain::Bitcoin), 0);
+        Module::<Test>::on_new_era(2);
+        assert_eq!(Module::<Test>::trustee_session_info_len(Chain::Bitcoin), 1);
+        let info = Module::<Test>::trustee_session_info_of(Chain::Bitcoin, 0).unwrap();
+        assert_eq!(
+            info.0.trustee_list,
+            vec![account(1), account(2), account(3)]
+        );
+
+        // the same trustees are elected, keep the current session
+        VALIDATORS.with(|v| *v.borrow_mut() = vec![account(3), account(2), account(1)]);
+        Module::<Test>::on_new_era(4);
+        assert_eq!(Module::<Test>::trustee_session_info_len(Chain::Bitcoin), 1);
+        assert_eq!(Module::<Test>::pending_trustees_of(Chain::Bitcoin), None);
+    })
+}
+
+#[test]
+fn test_apply_trustee_transition() {
+    ExtBuilder::default().build().execute_with(|| {
+        VALIDATORS.with(|v| *v.borrow_mut() = vec![account(1), account(2), account(3)]);
+        assert_ok!(Module::<Test>::set_trustee_transition_eras(
+            RawOrigin::Root.into(),
+            1
+        ));
+        Module::<Test>::on_new_era(1);
+        assert_eq!(Module::<Test>::trustee_session_info_len(Chain::Bitcoin), 1);
+        let multisig = Module::<Test>::trustee_multisig_addr(Chain::Bitcoin);
+
+        assert_ok!(Module::<Test>::setup_trustee_impl(
+            account(4),
+            Chain::Bitcoin,
+            b"".to_vec(),
+            hex::decode("02df92e88c4380778c9c48268460a124a8f4e7da883f80477deaa644ced486efc6")
+                .unwrap(),
+            hex::decode("0386b58f51da9b37e59c40262153173bdb59d7e4e45b73994b99eec4d964ee7e88")
+                .unwrap(),
+        ));
+        VALIDATORS.with(|v| *v.borrow_mut() = vec![account(1), account(2), account(4)]);
+
+        // no rotation while a withdrawal proposal is pending
+        WithdrawalProposal::<Test>::put(BtcWithdrawalProposal::new(
+            VoteResult::Unfinish,
+            vec![0],
+            Transaction::default(),
+            vec![],
+        ));
+        Module::<Test>::on_new_era(2);
+        assert_eq!(Module::<Test>::pending_trustees_of(Chain::Bitcoin), None);
+        WithdrawalProposal::<Test>::kill();
+
+        // the new trustees wait for the current trustees to hand over the session
+        Module::<Test>::on_new_era(3);
+        assert_eq!(
+            Module::<Test>::pending_trustees_of(Chain::Bitcoin),
+            Some(vec![account(1), account(2), account(4)])
+        );
+        assert_eq!(Module::<Test>::trustee_session_info_len(Chain::Bitcoin), 1);
+
+        assert_noop!(
+            Module::<Test>::apply_trustee_transition(
+                RawOrigin::Signed(account(1)).into(),
+                Chain::Bitcoin
+            ),
+            Error::<Test>::InvalidMultisig
+        );
+        WithdrawalProposal::<Test>::put(BtcWithdrawalProposal::new(
+            VoteResult::Unfinish,
+            vec![0],
+            Transaction::default(),
+            vec![],
+        ));
+        assert_noop!(
+            Module::<Test>::apply_trustee_transition(
+                RawOrigin::Signed(multisig.clone()).into(),
+                Chain::Bitcoin
+            ),
+            Error::<Test>::WithdrawalProposalPending
+        );
+        WithdrawalProposal::<Test>::kill();
+
+        assert_ok!(Module::<Test>::apply_trustee_transition(
+            RawOrigin::Signed(multisig).into(),
+            Chain::Bitcoin
+        ));
+        assert_eq!(Module::<Test>::trustee_session_info_len(Chain::Bitcoin), 2);
+        assert_eq!(Module::<Test>::pending_trustees_of(Chain::Bitcoin), None);
+        assert_noop!(
+            Module::<Test>::apply_trustee_transition(RawOrigin::Root.into(), Chain::Bitcoin),
+            Error::<Test>::NoPendingTrustees
+        );
+    })
+}
+
+#[test]
+fn test_trustee_bond_and_slash() {
+    ExtBuilder::default().build().execute_with(|| {
+        let treasury = AccountId::default();
+        for i in 1..=3 {
+            Balances::make_free_balance_be(&account(i), 1000);
+        }
+        VALIDATORS.with(|v| *v.borrow_mut() = vec![account(1), account(2), account(3)]);
+        assert_ok!(Module::<Test>::set_trustee_slash_config(
+            RawOrigin::Root.into(),
+            TrusteeSlashParams {
+                min_bond: 500,
+                max_consecutive_vetoes: 2,
+                veto_penalty: 100,
+                missed_signing_penalty: 10,
+                invalid_signature_penalty: 1000,
+            }
+        ));
+
+        // the candidates without enough bond are not elected
+        assert_ok!(Module::<Test>::bond_trustee(
+            RawOrigin::Signed(account(1)).into(),
+            600
+        ));
+        assert_ok!(Module::<Test>::bond_trustee(
+            RawOrigin::Signed(account(2)).into(),
+            500
+        ));
+        assert_ok!(Module::<Test>::bond_trustee(
+            RawOrigin::Signed(account(3)).into(),
+            400
+        ));
+        assert_eq!(Balances::reserved_balance(&account(1)), 600);
+        assert_eq!(Module::<Test>::elect_trustees(Chain::Bitcoin), None);
+        assert_ok!(Module::<Test>::bond_trustee(
+            RawOrigin::Signed(account(3)).into(),
+            100
+        ));
+        assert_ok!(Module::<Test>::set_trustee_transition_eras(
+            RawOrigin::Root.into(),
+            1
+        ));
+        Module::<Test>::on_new_era(1);
+        assert_eq!(Module::<Test>::trustee_session_info_len(Chain::Bitcoin), 1);
+
+        // the trustees in office could not unbond
+        assert_noop!(
+            Module::<Test>::unbond_trustee(RawOrigin::Signed(account(1)).into(), 100),
+            Error::<Test>::TrusteeInOffice
+        );
+
+        // the vetoes are reset by approving
+        let votes = vec![(account(1), false), (account(2), true)];
+        Module::<Test>::note_completed_votes(Chain::Bitcoin, &votes);
+        Module::<Test>::note_completed_votes(Chain::Bitcoin, &[(account(1), true)]);
+        assert_eq!(
+            Module::<Test>::trustee_vetoes_of(Chain::Bitcoin, account(1)),
+            0
+        );
+        Module::<Test>::note_completed_votes(Chain::Bitcoin, &votes);
+        Module::<Test>::note_completed_votes(Chain::Bitcoin, &votes);
+        assert_eq!(Module::<Test>::trustee_bond_of(account(1)), 500);
+        assert_eq!(Balances::free_balance(&treasury), 100);
+
+        // the penalty is more than the bond
+        Module::<Test>::report_offence(
+            Chain::Bitcoin,
+            &account(2),
+            TrusteeOffence::InvalidSignature,
+        );
+        assert_eq!(Module::<Test>::trustee_bond_of(account(2)), 0);
+        assert_eq!(Balances::reserved_balance(&account(2)), 0);
+        assert_eq!(Balances::free_balance(&treasury), 600);
+
+        // the missed signings are recorded in the liveness of the current trustees
+        Module::<Test>::note_missed_signing(Chain::Bitcoin, &[account(3)]);
+        assert_eq!(Module::<Test>::trustee_bond_of(account(3)), 490);
+        let liveness = Module::<Test>::trustee_liveness(Chain::Bitcoin);
+        assert_eq!(liveness.len(), 3);
+        assert_eq!(
+            liveness[2],
+            (
+                account(3),
+                TrusteeLiveness {
+                    missed: 1,
+                    consecutive_missed: 1
+                }
+            )
+        );
+    })
+}
+
+#[test]
+fn test_trustee_signing_stats() {
+    ExtBuilder::default().build().execute_with(|| {
+        Module::<Test>::note_proposal(Chain::Bitcoin);
+        Module::<Test>::note_vote(Chain::Bitcoin, &account(1), true, 0);
+        Module::<Test>::note_vote(Chain::Bitcoin, &account(2), true, 3);
+        Module::<Test>::note_proposal(Chain::Bitcoin);
+        Module::<Test>::note_vote(Chain::Bitcoin, &account(2), false, 5);
+
+        let stats = Module::<Test>::trustee_signing_stats(Chain::Bitcoin, None);
+        assert_eq!(
+            stats,
+            Module::<Test>::trustee_signing_stats(Chain::Bitcoin, Some(0))
+        );
+        assert_eq!(
+            stats[1],
+            (
+                account(2),
+                TrusteeSigningStats {
+                    seen: 2,
+                    approved: 1,
+                    vetoed: 1,
+                    average_latency: 4,
+                }
+            )
+        );
+        // the trustee has not voted yet
+        assert_eq!(stats[2].1.seen, 2);
+        assert_eq!(stats[2].1.average_latency, 0);
+
+        // no statistics for the session which does not exist
+        assert!(Module::<Test>::trustee_signing_stats(Chain::Bitcoin, Some(1)).is_empty());
+    })
+}
+
+#[test]
+fn test_batch_withdraw() {
+    ExtBuilder::default().build().execute_with(|| {
+        let who = account(1);
+        let origin = || RawOrigin::Signed(account(1)).into();
+        let output = |addr: &[u8], value, memo: &[u8]| WithdrawalOutput {
+            addr: addr.to_vec(),
+            value,
+            memo: memo.into(),
+        };
+        assert_ok!(XGatewayRecords::<Test>::deposit(&who, X_BTC, 1000));
+
+        assert_noop!(
+            Module::<Test>::batch_withdraw(origin(), X_BTC, vec![]),
+            Error::<Test>::InvalidWithdrawalOutputs
+        );
+        // none of the outputs is created if any of them is invalid
+        assert_noop!(
+            Module::<Test>::batch_withdraw(
+                origin(),
+                X_BTC,
+                vec![output(b"addr1", 300, b"1"), output(b"addr2", 300, b"<2>")]
+            ),
+            "'<' and '>' are not allowed, which could be abused off-chain."
+        );
+        assert_noop!(
+            Module::<Test>::batch_withdraw(
+                origin(),
+                X_BTC,
+                vec![output(b"addr1", 600, b"1"), output(b"addr2", 600, b"2")]
+            ),
+            xpallet_assets::Error::<Test>::InsufficientBalance
+        );
+
+        assert_ok!(Module::<Test>::batch_withdraw(
+            origin(),
+            X_BTC,
+            vec![output(b"addr1", 300, b"1"), output(b"addr2", 200, b"2")]
+        ));
+        for (id, (addr, value, memo)) in vec![(b"addr1", 300, b"1"), (b"addr2", 200, b"2")]
+            .into_iter()
+            .enumerate()
+        {
+            let record = XGatewayRecords::<Test>::pending_withdrawals(id as u32).unwrap();
+            assert_eq!(record.applicant(), &who);
+            assert_eq!(record.addr(), &addr.to_vec());
+            assert_eq!(record.balance(), value);
+            assert_eq!(record.ext().as_ref(), memo);
+            assert_eq!(
+                XGatewayRecords::<Test>::state_of(id as u32),
+                Some(WithdrawalState::Applying)
+            );
+        }
+        assert_eq!(
+            xpallet_assets::Module::<Test>::usable_balance(&who, &X_BTC),
+            500
+        );
+    })
+}
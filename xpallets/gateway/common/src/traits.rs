@@ -1,12 +1,16 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::dispatch::DispatchError;
+use frame_support::dispatch::{DispatchError, DispatchResult};
 use sp_std::{convert::TryFrom, prelude::Vec};
 
 use chainx_primitives::{AssetId, ReferralId};
-use xpallet_assets::Chain;
+use xp_runtime::Memo;
+use xpallet_assets::{Chain, WithdrawalLimit};
 
-use crate::types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo};
+use crate::types::{
+    AddrFormat, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
+    TrusteeIntentionProps, TrusteeOffence, TrusteeSessionInfo,
+};
 
 pub trait BytesLike: Into<Vec<u8>> + TryFrom<Vec<u8>> {}
 impl<T: Into<Vec<u8>> + TryFrom<Vec<u8>>> BytesLike for T {}
@@ -22,6 +26,191 @@ pub trait TrusteeForChain<AccountId, TrusteeEntity: BytesLike, TrusteeAddress: B
         props: Vec<(AccountId, TrusteeIntentionProps<TrusteeEntity>)>,
         config: TrusteeInfoConfig,
     ) -> Result<TrusteeSessionInfo<AccountId, TrusteeAddress>, DispatchError>;
+
+    /// `check_trustee_entity` in the generic form, used to implement the `ChainGateway`.
+    fn check_generic_trustee_entity(raw_addr: &[u8]) -> Result<Vec<u8>, DispatchError> {
+        Self::check_trustee_entity(raw_addr).map(Into::into)
+    }
+
+    /// `generate_trustee_session_info` in the generic form, used to implement the `ChainGateway`.
+    fn generate_generic_trustee_session_info(
+        props: Vec<(AccountId, GenericTrusteeIntentionProps)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<GenericTrusteeSessionInfo<AccountId>, DispatchError> {
+        let props = props
+            .into_iter()
+            .map(|(id, prop)| {
+                (
+                    id,
+                    TrusteeIntentionProps::<_>::try_from(prop)
+                        .expect("must decode succss from storage data"),
+                )
+            })
+            .collect();
+        let session_info = Self::generate_trustee_session_info(props, config)?;
+        Ok(session_info.into())
+    }
+}
+
+impl<AccountId, TrusteeEntity: BytesLike, TrusteeAddress: BytesLike>
+    TrusteeForChain<AccountId, TrusteeEntity, TrusteeAddress> for ()
+{
+    fn check_trustee_entity(_: &[u8]) -> Result<TrusteeEntity, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<TrusteeEntity>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, TrusteeAddress>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+/// The gateway of a chain, which is plugged into the gateway common module by the runtime
+/// through `Trait::Gateways`.
+pub trait ChainGateway<AccountId, Balance> {
+    /// The chain of this gateway.
+    fn chain() -> Chain;
+
+    /// The display format of the addresses of the chain.
+    fn addr_format() -> AddrFormat;
+
+    fn withdrawal_limit(asset_id: &AssetId) -> Result<WithdrawalLimit<Balance>, DispatchError>;
+
+    fn check_addr(addr: &[u8], ext: &[u8]) -> DispatchResult;
+
+    /// Check the memo of a withdrawal, which is a readable note by default. The chain which
+    /// carries the memo to the destination (e.g. the calldata of Ethereum) checks its own format.
+    fn check_memo(memo: &Memo) -> DispatchResult {
+        memo.check_validity()
+    }
+
+    /// Check the hot/cold entity of a trustee, the chain without trustees would refuse all.
+    fn check_trustee_entity(_raw_addr: &[u8]) -> Result<Vec<u8>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _props: Vec<(AccountId, GenericTrusteeIntentionProps)>,
+        _config: TrusteeInfoConfig,
+    ) -> Result<GenericTrusteeSessionInfo<AccountId>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    /// Whether a withdrawal proposal signed by the current trustees is still pending, the
+    /// trustee session must not be changed in the meantime.
+    fn has_pending_proposal() -> bool {
+        false
+    }
+}
+
+/// The gateways of all the supported chains, implemented for the tuple of `ChainGateway`s,
+/// e.g. `(XGatewayBitcoin, XGatewayEthereum)`.
+///
+/// Returns `None` if the chain is not supported by any gateway.
+pub trait GatewayRegistry<AccountId, Balance> {
+    /// The supported chains and the display format of their addresses.
+    fn addr_formats() -> Vec<(Chain, AddrFormat)>;
+
+    fn withdrawal_limit(
+        chain: Chain,
+        asset_id: &AssetId,
+    ) -> Option<Result<WithdrawalLimit<Balance>, DispatchError>>;
+
+    fn check_addr(chain: Chain, addr: &[u8], ext: &[u8]) -> Option<DispatchResult>;
+
+    fn check_memo(chain: Chain, memo: &Memo) -> Option<DispatchResult>;
+
+    fn check_trustee_entity(
+        chain: Chain,
+        raw_addr: &[u8],
+    ) -> Option<Result<Vec<u8>, DispatchError>>;
+
+    fn generate_trustee_session_info(
+        chain: Chain,
+        props: Vec<(AccountId, GenericTrusteeIntentionProps)>,
+        config: TrusteeInfoConfig,
+    ) -> Option<Result<GenericTrusteeSessionInfo<AccountId>, DispatchError>>;
+
+    fn has_pending_proposal(chain: Chain) -> Option<bool>;
+}
+
+#[allow(unused_variables)]
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+#[tuple_types_no_default_trait_bound]
+impl<AccountId, Balance> GatewayRegistry<AccountId, Balance> for Tuple {
+    for_tuples!( where #( Tuple: ChainGateway<AccountId, Balance> )* );
+
+    fn addr_formats() -> Vec<(Chain, AddrFormat)> {
+        let mut formats = Vec::new();
+        for_tuples!( #( formats.push((Tuple::chain(), Tuple::addr_format())); )* );
+        formats
+    }
+
+    fn withdrawal_limit(
+        chain: Chain,
+        asset_id: &AssetId,
+    ) -> Option<Result<WithdrawalLimit<Balance>, DispatchError>> {
+        for_tuples!( #(
+            if Tuple::chain() == chain {
+                return Some(Tuple::withdrawal_limit(asset_id));
+            }
+        )* );
+        None
+    }
+
+    fn check_addr(chain: Chain, addr: &[u8], ext: &[u8]) -> Option<DispatchResult> {
+        for_tuples!( #(
+            if Tuple::chain() == chain {
+                return Some(Tuple::check_addr(addr, ext));
+            }
+        )* );
+        None
+    }
+
+    fn check_memo(chain: Chain, memo: &Memo) -> Option<DispatchResult> {
+        for_tuples!( #(
+            if Tuple::chain() == chain {
+                return Some(Tuple::check_memo(memo));
+            }
+        )* );
+        None
+    }
+
+    fn check_trustee_entity(
+        chain: Chain,
+        raw_addr: &[u8],
+    ) -> Option<Result<Vec<u8>, DispatchError>> {
+        for_tuples!( #(
+            if Tuple::chain() == chain {
+                return Some(Tuple::check_trustee_entity(raw_addr));
+            }
+        )* );
+        None
+    }
+
+    fn generate_trustee_session_info(
+        chain: Chain,
+        props: Vec<(AccountId, GenericTrusteeIntentionProps)>,
+        config: TrusteeInfoConfig,
+    ) -> Option<Result<GenericTrusteeSessionInfo<AccountId>, DispatchError>> {
+        for_tuples!( #(
+            if Tuple::chain() == chain {
+                return Some(Tuple::generate_trustee_session_info(props, config));
+            }
+        )* );
+        None
+    }
+
+    fn has_pending_proposal(chain: Chain) -> Option<bool> {
+        for_tuples!( #(
+            if Tuple::chain() == chain {
+                return Some(Tuple::has_pending_proposal());
+            }
+        )* );
+        None
+    }
 }
 
 pub trait TrusteeSession<AccountId, TrusteeAddress: BytesLike> {
@@ -59,6 +248,39 @@ impl<AccountId, TrusteeAddress: BytesLike> TrusteeSession<AccountId, TrusteeAddr
     fn genesis_trustee(_: Chain, _: &[AccountId]) {}
 }
 
+/// Report the misbehaviours of the trustees to the slashing, used by the chain gateways.
+pub trait TrusteeMisbehaviour<AccountId> {
+    /// The trustee committed an offence in the chain gateway.
+    fn report_offence(chain: Chain, who: &AccountId, offence: TrusteeOffence);
+
+    /// The trustees did not vote for a proposal before it expired.
+    fn note_missed_signing(chain: Chain, absentees: &[AccountId]);
+
+    /// The votes `(trustee, approve)` of a proposal which was completed, the trustees vetoing
+    /// it too many times in a row would be slashed.
+    fn note_completed_votes(chain: Chain, votes: &[(AccountId, bool)]);
+}
+
+impl<AccountId> TrusteeMisbehaviour<AccountId> for () {
+    fn report_offence(_: Chain, _: &AccountId, _: TrusteeOffence) {}
+    fn note_missed_signing(_: Chain, _: &[AccountId]) {}
+    fn note_completed_votes(_: Chain, _: &[(AccountId, bool)]) {}
+}
+
+/// Record the withdrawal signing activities of the trustees, used by the chain gateways.
+pub trait TrusteeSigningRecorder<AccountId> {
+    /// A proposal was created and would be seen by all the trustees of the current session.
+    fn note_proposal(chain: Chain);
+
+    /// The trustee approved/vetoed the proposal `latency` blocks after it was created.
+    fn note_vote(chain: Chain, who: &AccountId, approve: bool, latency: u32);
+}
+
+impl<AccountId> TrusteeSigningRecorder<AccountId> for () {
+    fn note_proposal(_: Chain) {}
+    fn note_vote(_: Chain, _: &AccountId, _: bool, _: u32) {}
+}
+
 pub trait ReferralBinding<AccountId> {
     fn update_binding(asset_id: &AssetId, who: &AccountId, referral_name: Option<ReferralId>);
     fn referral(asset_id: &AssetId, who: &AccountId) -> Option<AccountId>;
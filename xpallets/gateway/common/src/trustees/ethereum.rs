@@ -0,0 +1,89 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode, Error as CodecError};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_core::H160;
+use sp_runtime::RuntimeDebug;
+use sp_std::{convert::TryFrom, prelude::Vec};
+
+use xpallet_assets::Chain;
+
+use super::{TrusteeMultisigProvider, TrusteeSessionManager};
+use crate::traits::ChainProvider;
+use crate::types::{TrusteeIntentionProps, TrusteeSessionInfo};
+
+pub type EthAddress = H160;
+pub type EthTrusteeSessionInfo<AccountId> = TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>;
+pub type EthTrusteeIntentionProps = TrusteeIntentionProps<EthTrusteeType>;
+pub type EthTrusteeSessionManager<T> = TrusteeSessionManager<T, EthTrusteeAddrInfo>;
+pub type EthTrusteeMultisig<T> = TrusteeMultisigProvider<T, EthTrusteeType>;
+
+/// The signer set of the bridge contract, withdrawals need `threshold` ECDSA signatures
+/// from the `signers`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthTrusteeAddrInfo {
+    /// The signer addresses, in the same order as the trustee list.
+    pub signers: Vec<EthAddress>,
+    pub threshold: u16,
+}
+
+impl From<EthTrusteeAddrInfo> for Vec<u8> {
+    fn from(value: EthTrusteeAddrInfo) -> Self {
+        value.encode()
+    }
+}
+
+impl TryFrom<Vec<u8>> for EthTrusteeAddrInfo {
+    type Error = CodecError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Decode::decode(&mut &value[..])
+    }
+}
+
+impl ChainProvider for EthTrusteeAddrInfo {
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+
+/// The ethereum address of a trustee signing key.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EthTrusteeType(pub EthAddress);
+
+impl From<EthTrusteeType> for Vec<u8> {
+    fn from(value: EthTrusteeType) -> Self {
+        value.0.as_bytes().to_vec()
+    }
+}
+
+impl TryFrom<Vec<u8>> for EthTrusteeType {
+    type Error = ();
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        if value.len() == 20 {
+            Ok(EthTrusteeType(H160::from_slice(&value)))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl ChainProvider for EthTrusteeType {
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+
+#[test]
+fn test_eth_trustee_type() {
+    let raw = vec![1u8; 20];
+    let trustee = EthTrusteeType::try_from(raw.clone()).unwrap();
+    assert_eq!(Vec::<u8>::from(trustee), raw);
+    assert!(EthTrusteeType::try_from(vec![1u8; 33]).is_err());
+}
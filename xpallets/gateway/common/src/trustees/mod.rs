@@ -1,6 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 pub mod bitcoin;
+pub mod ethereum;
 
 use frame_support::{dispatch::DispatchError, traits::Contains};
 use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
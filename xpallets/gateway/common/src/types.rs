@@ -7,10 +7,30 @@ use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::{convert::TryFrom, prelude::Vec};
 
-use chainx_primitives::Text;
+use chainx_primitives::{AddrStr, Text};
+use xp_runtime::Memo;
 
 use crate::traits::BytesLike;
 
+/// The display format of the addresses of a chain.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AddrFormat {
+    /// The address is a readable string, e.g. the base58 address of Bitcoin.
+    Utf8,
+    /// The address is raw bytes and displayed in hex, e.g. the address of Ethereum.
+    Hex,
+}
+
+/// An output of the withdrawal which pays several addresses in one call.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct WithdrawalOutput<Balance> {
+    pub addr: AddrStr,
+    pub value: Balance,
+    /// The memo of this output, e.g. the destination tag or the calldata of Ethereum.
+    pub memo: Memo,
+}
+
 /// The config of trustee info.
 #[derive(PartialEq, Clone, Encode, Decode, Default, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -109,3 +129,86 @@ impl<TrusteeEntity: BytesLike> TryFrom<GenericTrusteeIntentionProps>
         })
     }
 }
+
+/// The misbehaviours for which a trustee would be slashed.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TrusteeOffence {
+    /// Vetoed the proposals which were completed by the other trustees repeatedly.
+    RepeatedVeto,
+    /// Did not sign the proposal within the signing deadline.
+    MissedSigning,
+    /// Signed a transaction which was never proposed on chain.
+    InvalidSignature,
+}
+
+/// The bond requirement and the penalties of the trustees.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TrusteeSlashParams<Balance> {
+    /// The minimum bond for being elected as a trustee.
+    pub min_bond: Balance,
+    /// A trustee would be slashed for `RepeatedVeto` once reaching the consecutive vetoes,
+    /// zero means vetoing is never slashed.
+    pub max_consecutive_vetoes: u32,
+    pub veto_penalty: Balance,
+    pub missed_signing_penalty: Balance,
+    pub invalid_signature_penalty: Balance,
+}
+
+impl<Balance: Copy> TrusteeSlashParams<Balance> {
+    pub fn penalty_of(&self, offence: TrusteeOffence) -> Balance {
+        match offence {
+            TrusteeOffence::RepeatedVeto => self.veto_penalty,
+            TrusteeOffence::MissedSigning => self.missed_signing_penalty,
+            TrusteeOffence::InvalidSignature => self.invalid_signature_penalty,
+        }
+    }
+}
+
+/// The liveness statistics of a trustee.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TrusteeLiveness {
+    /// The number of the proposals which expired without the signature of the trustee.
+    pub missed: u32,
+    /// The missed proposals since the last time the trustee voted.
+    pub consecutive_missed: u32,
+}
+
+/// The withdrawal signing counters of a trustee in a trustee session.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct TrusteeSigningCounters {
+    /// The number of the proposals created in the session.
+    pub seen: u32,
+    pub approved: u32,
+    pub vetoed: u32,
+    /// The total blocks between the creation of the proposals and the votes of the trustee.
+    pub total_latency: u64,
+}
+
+/// The withdrawal signing statistics of a trustee in a trustee session.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TrusteeSigningStats {
+    pub seen: u32,
+    pub approved: u32,
+    pub vetoed: u32,
+    /// The average blocks the trustee took to vote since a proposal was created.
+    pub average_latency: u64,
+}
+
+impl From<TrusteeSigningCounters> for TrusteeSigningStats {
+    fn from(counters: TrusteeSigningCounters) -> Self {
+        let votes = u64::from(counters.approved) + u64::from(counters.vetoed);
+        Self {
+            seen: counters.seen,
+            approved: counters.approved,
+            vetoed: counters.vetoed,
+            average_latency: counters.total_latency.checked_div(votes).unwrap_or(0),
+        }
+    }
+}
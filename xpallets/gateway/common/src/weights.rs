@@ -31,11 +31,17 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for xpallet_gateway_common.
 pub trait WeightInfo {
     fn withdraw() -> Weight;
+    fn batch_withdraw(n: u32) -> Weight;
     fn cancel_withdrawal() -> Weight;
     fn setup_trustee() -> Weight;
     fn transition_trustee_session(u: u32) -> Weight;
+    fn apply_trustee_transition(u: u32) -> Weight;
     fn set_withdrawal_state() -> Weight;
     fn set_trustee_info_config() -> Weight;
+    fn set_trustee_transition_eras() -> Weight;
+    fn bond_trustee() -> Weight;
+    fn unbond_trustee() -> Weight;
+    fn set_trustee_slash_config() -> Weight;
     fn force_set_referral_binding() -> Weight;
 }
 
@@ -47,6 +53,14 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(10 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn batch_withdraw(n: u32) -> Weight {
+        (38_512_000 as Weight)
+            .saturating_add((195_336_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+    }
     fn cancel_withdrawal() -> Weight {
         (130_921_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(6 as Weight))
@@ -63,6 +77,12 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(8 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn apply_trustee_transition(u: u32) -> Weight {
+        (137_268_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
     fn set_withdrawal_state() -> Weight {
         (217_002_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(11 as Weight))
@@ -71,6 +91,22 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_trustee_info_config() -> Weight {
         (6_432_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_trustee_transition_eras() -> Weight {
+        (4_318_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn bond_trustee() -> Weight {
+        (52_146_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn unbond_trustee() -> Weight {
+        (61_873_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_trustee_slash_config() -> Weight {
+        (5_087_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn force_set_referral_binding() -> Weight {
         (30_667_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
@@ -83,6 +119,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(10 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    fn batch_withdraw(n: u32) -> Weight {
+        (38_512_000 as Weight)
+            .saturating_add((195_336_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+    }
     fn cancel_withdrawal() -> Weight {
         (130_921_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(6 as Weight))
@@ -99,6 +143,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(8 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn apply_trustee_transition(u: u32) -> Weight {
+        (137_268_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
     fn set_withdrawal_state() -> Weight {
         (217_002_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(11 as Weight))
@@ -107,6 +157,22 @@ impl WeightInfo for () {
     fn set_trustee_info_config() -> Weight {
         (6_432_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_trustee_transition_eras() -> Weight {
+        (4_318_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn bond_trustee() -> Weight {
+        (52_146_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn unbond_trustee() -> Weight {
+        (61_873_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_trustee_slash_config() -> Weight {
+        (5_087_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
     fn force_set_referral_binding() -> Weight {
         (30_667_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
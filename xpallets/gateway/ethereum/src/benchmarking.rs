@@ -0,0 +1,179 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::storage::{StorageMap, StorageValue};
+use frame_system::RawOrigin;
+use hex_literal::hex;
+use sp_core::{ecdsa, H160, H256, U256};
+use sp_io::hashing::blake2_256;
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use chainx_primitives::AssetId;
+use xpallet_assets::Module as XAssets;
+use xpallet_gateway_common::traits::TrusteeSession;
+use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalState};
+
+use crate::{
+    proof::{deposit_receipt, single_receipt_proof, withdrawn_receipt},
+    ApprovedWithdrawals, AssetTokens, BridgeContract, Call, EthHeaderAttestation, EthTokenInfo,
+    HeaderVoteKeys, HeaderVotes, Headers, Module, Tokens, Trait, WithdrawalSignatures,
+};
+
+const ASSET_ID: AssetId = xp_protocol::X_ETH;
+const ETH: u128 = 1_000_000_000_000_000_000;
+
+fn trustees<T: Trait>() -> Vec<T::AccountId> {
+    T::TrusteeSessionProvider::current_trustee_session()
+        .expect("the trustees of ethereum must be set")
+        .trustee_list
+}
+
+fn prepare_bridge<T: Trait>() {
+    BridgeContract::put(H160::repeat_byte(0xbb));
+    let info = EthTokenInfo {
+        asset_id: ASSET_ID,
+        decimals: 18,
+    };
+    Tokens::insert(H160::zero(), info);
+    AssetTokens::insert(ASSET_ID, H160::zero());
+}
+
+benchmarks! {
+    _{ }
+
+    attest_header {
+        let u in 1 .. 64 => ();
+        let trustees = trustees::<T>();
+        let header = EthHeaderAttestation {
+            hash: H256::repeat_byte(1),
+            number: 100,
+            receipts_root: H256::repeat_byte(2),
+        };
+        // the other attestations of the block which would be dropped
+        let mut vote_keys = (1..u)
+            .map(|i| {
+                let other = EthHeaderAttestation {
+                    receipts_root: H256::repeat_byte(i as u8),
+                    ..header
+                };
+                let vote_key = H256(blake2_256(&other.encode()));
+                HeaderVotes::<T>::insert(&vote_key, vec![account::<T::AccountId>("voter", i, 0)]);
+                vote_key
+            })
+            .collect::<Vec<_>>();
+        // the vote of the caller reaches the threshold
+        let vote_key = H256(blake2_256(&header.encode()));
+        HeaderVotes::<T>::insert(&vote_key, trustees[1..].to_vec());
+        vote_keys.push(vote_key);
+        HeaderVoteKeys::insert(&header.hash, vote_keys);
+        let caller = trustees[0].clone();
+    }: _(RawOrigin::Signed(caller), header)
+    verify {
+        assert_eq!(Module::<T>::headers(&header.hash), Some(header));
+        assert!(Module::<T>::header_vote_keys(&header.hash).is_empty());
+    }
+
+    push_deposit {
+        let caller: T::AccountId = whitelisted_caller();
+        prepare_bridge::<T>();
+        let receiver: T::AccountId = whitelisted_caller();
+        let recipient = H256::from_slice(&receiver.encode());
+        let receipt = deposit_receipt(H160::repeat_byte(0xbb), H160::zero(), recipient, U256::from(ETH));
+        let (receipts_root, proof) = single_receipt_proof(0, &receipt);
+        let header = EthHeaderAttestation {
+            hash: H256::repeat_byte(1),
+            number: 100,
+            receipts_root,
+        };
+        Headers::insert(&header.hash, header);
+    }: _(RawOrigin::Signed(caller), header.hash, 0, proof)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&receiver, &ASSET_ID), ETH.saturated_into());
+    }
+
+    sign_withdrawal {
+        let trustees = trustees::<T>();
+        prepare_bridge::<T>();
+        let who: T::AccountId = whitelisted_caller();
+        XGatewayRecords::<T>::deposit(&who, ASSET_ID, ETH.saturated_into()).unwrap();
+        XGatewayRecords::<T>::withdraw(&who, ASSET_ID, ETH.saturated_into(), vec![0xaa; 20], b"".to_vec().into()).unwrap();
+        xpallet_gateway_records::WithdrawalStateOf::insert(0, WithdrawalState::Processing);
+
+        // the signature of the second trustee has been submitted
+        let signature = ecdsa::Signature::from_raw(hex![
+            "e5740e63bad28081ed7cf654dd6c19029ca03382fc05ab5f5dda81f2c55b845b32667e56dd3f8147ad8276e2ae7ba612ed5fe8d4bbc4ab7d35e5070267b38bc701"
+        ]);
+        WithdrawalSignatures::<T>::insert(0, vec![(trustees[1].clone(), signature)]);
+
+        let caller = trustees[0].clone();
+        let signature = ecdsa::Signature::from_raw(hex![
+            "7592aab5d43618dda13fba71e3993cd7517a712d3da49664c06ee1bd3d1f70af108b78a90bcf4e3f5b8c66e3066b7a8bf90a1a41ac1d0485925048efc5c1946f00"
+        ]);
+    }: _(RawOrigin::Signed(caller), 0, signature)
+    verify {
+        assert!(Module::<T>::approved_withdrawals(0).is_some());
+    }
+
+    release_withdrawal {
+        let caller: T::AccountId = whitelisted_caller();
+        prepare_bridge::<T>();
+        let who: T::AccountId = whitelisted_caller();
+        XGatewayRecords::<T>::deposit(&who, ASSET_ID, ETH.saturated_into()).unwrap();
+        XGatewayRecords::<T>::withdraw(&who, ASSET_ID, ETH.saturated_into(), vec![0xaa; 20], b"".to_vec().into()).unwrap();
+        xpallet_gateway_records::WithdrawalStateOf::insert(0, WithdrawalState::Processing);
+
+        let record = XGatewayRecords::<T>::pending_withdrawals(0).unwrap();
+        let message = H256(Module::<T>::withdrawal_message(0, &record).unwrap());
+        ApprovedWithdrawals::insert(0, message);
+        let receipt = withdrawn_receipt(H160::repeat_byte(0xbb), message);
+        let (receipts_root, proof) = single_receipt_proof(0, &receipt);
+        let header = EthHeaderAttestation {
+            hash: H256::repeat_byte(1),
+            number: 100,
+            receipts_root,
+        };
+        Headers::insert(&header.hash, header);
+    }: _(RawOrigin::Signed(caller), 0, header.hash, 0, proof)
+    verify {
+        assert!(XGatewayRecords::<T>::pending_withdrawals(0).is_none());
+    }
+
+    register_token {
+        let token = H160::repeat_byte(0xcc);
+        let info = EthTokenInfo {
+            asset_id: ASSET_ID,
+            decimals: 6,
+        };
+    }: _(RawOrigin::Root, token, info)
+    verify {
+        assert_eq!(Module::<T>::tokens(&token), Some(info));
+    }
+
+    set_bridge_contract {
+        let contract = H160::repeat_byte(0xbb);
+    }: _(RawOrigin::Root, contract)
+    verify {
+        assert_eq!(Module::<T>::bridge_contract(), contract);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(test_benchmark_attest_header::<Test>());
+            assert_ok!(test_benchmark_push_deposit::<Test>());
+            assert_ok!(test_benchmark_sign_withdrawal::<Test>());
+            assert_ok!(test_benchmark_release_withdrawal::<Test>());
+            assert_ok!(test_benchmark_register_token::<Test>());
+            assert_ok!(test_benchmark_set_bridge_contract::<Test>());
+        });
+    }
+}
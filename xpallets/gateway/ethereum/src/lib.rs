@@ -0,0 +1,662 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! this module is for eth-bridge
+//!
+//! Ethereum headers are attested by the trustees (a trusted committee), deposits into the
+//! bridge contract are verified by the receipt proof against the attested receipts root, and
+//! withdrawals are approved by the ECDSA signatures of the trustees, which would be relayed to
+//! the bridge contract. The approved withdrawal is released only after the receipt of the
+//! payout by the bridge contract is relayed back. The memo of a withdrawal is the calldata for
+//! the recipient, which is also signed by the trustees.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod proof;
+mod rlp;
+mod types;
+pub mod weights;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use sp_core::{ecdsa, H160, H256, U256};
+use sp_io::hashing::{blake2_256, keccak_256};
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+};
+use frame_system::{ensure_root, ensure_signed};
+use orml_utilities::with_transaction_result;
+
+use chainx_primitives::AssetId;
+use xp_logging::{error, info};
+use xp_runtime::Memo;
+use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_gateway_common::{
+    traits::{ChainGateway, TrusteeForChain, TrusteeSession},
+    trustees::ethereum::{EthTrusteeAddrInfo, EthTrusteeType},
+    types::{
+        AddrFormat, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
+        TrusteeIntentionProps, TrusteeSessionInfo,
+    },
+    utils::two_thirds_unsafe,
+};
+use xpallet_gateway_records::{WithdrawalRecordId, WithdrawalRecordOf, WithdrawalState};
+
+pub use self::types::{EthHeaderAttestation, EthLog, EthTokenInfo};
+pub use self::weights::WeightInfo;
+
+/// keccak256("Deposit(address,bytes32,uint256)"), the event of the bridge contract:
+/// `event Deposit(address indexed token, bytes32 indexed recipient, uint256 amount)`
+pub const DEPOSIT_EVENT_TOPIC: [u8; 32] = [
+    0x19, 0xda, 0xcb, 0xf8, 0x3c, 0x5d, 0xe6, 0x65, 0x8e, 0x14, 0xcb, 0xf7, 0xbc, 0xae, 0x5c, 0x15,
+    0xec, 0xa2, 0xee, 0xde, 0xcf, 0x1c, 0x66, 0xfb, 0xca, 0x92, 0x8e, 0x4d, 0x35, 0x1b, 0xea, 0x0f,
+];
+
+/// keccak256("Withdrawn(bytes32)"), the event of the bridge contract after paying a withdrawal:
+/// `event Withdrawn(bytes32 indexed message)`, `message` is the signed withdrawal message.
+pub const WITHDRAWN_EVENT_TOPIC: [u8; 32] = [
+    0x44, 0x65, 0x46, 0x74, 0xe6, 0x6e, 0x9e, 0x53, 0xd3, 0x4a, 0xfd, 0xf3, 0xe9, 0x51, 0x3f, 0xb9,
+    0x4b, 0x4c, 0x8b, 0x94, 0x6e, 0x98, 0x0d, 0x69, 0x58, 0xef, 0x4e, 0x6a, 0x37, 0xa1, 0x53, 0xe3,
+];
+
+pub trait Trait: xpallet_assets::Trait + xpallet_gateway_records::Trait {
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+    type TrusteeSessionProvider: TrusteeSession<Self::AccountId, EthTrusteeAddrInfo>;
+    type WeightInfo: WeightInfo;
+}
+
+decl_error! {
+    /// Error for the XBridge Ethereum module
+    pub enum Error for Module<T: Trait> {
+        /// invalid ethereum address
+        InvalidAddress,
+        /// invalid trustee entity (signer address)
+        InvalidTrusteeEntity,
+        /// the count of trustees does not meet the config
+        InvalidTrusteeCount,
+        /// duplicated signer address
+        DuplicatedKeys,
+        /// not in the trustee list
+        NotTrustee,
+        /// duplicate vote or signature for the same trustee
+        DuplicateVote,
+        /// the header has been attested
+        ExistingHeader,
+        /// the header has not been attested by trustees
+        UnattestedHeader,
+        /// invalid merkle patricia trie proof of the receipt
+        InvalidReceiptProof,
+        /// can not decode the receipt
+        InvalidReceipt,
+        /// the receipt has been processed
+        ReplayedReceipt,
+        /// no deposit log of the bridge contract in the receipt
+        NoDepositLog,
+        /// the token is not registered
+        UnregisteredToken,
+        /// the asset of the token must belong to ethereum
+        InvalidTokenAsset,
+        /// the recipient of the deposit could not be decoded into an account
+        InvalidRecipient,
+        /// the amount overflows after converting the decimals
+        AmountOverflow,
+        /// no withdrawal record for this id
+        NoWithdrawalRecord,
+        /// the signature does not match the signer of the trustee
+        InvalidSignature,
+        /// the calldata in the memo has no function selector
+        InvalidCalldata,
+        /// the withdrawal has been approved by enough trustees
+        ApprovedWithdrawal,
+        /// the withdrawal has not been approved by enough trustees
+        UnapprovedWithdrawal,
+        /// no withdrawn log of the bridge contract for the withdrawal in the receipt
+        NoWithdrawnLog,
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>,
+    {
+        /// A trustee voted for an ethereum header. [trustee, block_hash]
+        HeaderVoted(AccountId, H256),
+        /// An ethereum header was attested by enough trustees. [block_hash, block_number]
+        HeaderAttested(H256, u64),
+        /// A deposit was minted. [block_hash, receipt_index, who, asset_id, amount]
+        Deposited(H256, u32, AccountId, AssetId, Balance),
+        /// A trustee signed a withdrawal. [withdrawal_id, trustee]
+        WithdrawalSigned(WithdrawalRecordId, AccountId),
+        /// A withdrawal was approved by enough trustees, the signatures should be relayed to the
+        /// bridge contract. [withdrawal_id, signatures]
+        WithdrawalApproved(WithdrawalRecordId, Vec<ecdsa::Signature>),
+        /// A withdrawal was paid by the bridge contract and released.
+        /// [withdrawal_id, block_hash, receipt_index]
+        Released(WithdrawalRecordId, H256, u32),
+        /// A token was registered. [token, token_info]
+        TokenRegistered(H160, EthTokenInfo),
+    }
+);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XGatewayEthereum {
+        /// the bridge contract on ethereum
+        pub BridgeContract get(fn bridge_contract): H160;
+        /// token address => token info, ETH is the zero address
+        pub Tokens get(fn tokens): map hasher(twox_64_concat) H160 => Option<EthTokenInfo>;
+        /// asset id => token address
+        pub AssetTokens get(fn asset_tokens): map hasher(twox_64_concat) AssetId => Option<H160>;
+        /// block hash => header attested by trustees
+        pub Headers get(fn headers): map hasher(identity) H256 => Option<EthHeaderAttestation>;
+        /// blake2_256 of the encoded attestation => trustees voted for this attestation
+        pub HeaderVotes get(fn header_votes): map hasher(identity) H256 => Vec<T::AccountId>;
+        /// block hash => the vote keys of the attestations for this block which are voting
+        pub HeaderVoteKeys get(fn header_vote_keys): map hasher(identity) H256 => Vec<H256>;
+        /// (block hash, receipt index) => whether the receipt has been processed
+        pub ProcessedReceipts get(fn processed_receipts):
+            double_map hasher(identity) H256, hasher(twox_64_concat) u32 => bool;
+        /// signatures of trustees for a withdrawal which has not been approved
+        pub WithdrawalSignatures get(fn withdrawal_signatures):
+            map hasher(twox_64_concat) WithdrawalRecordId => Vec<(T::AccountId, ecdsa::Signature)>;
+        /// withdrawal id => the message approved by trustees, which is waiting for the payout
+        pub ApprovedWithdrawals get(fn approved_withdrawals):
+            map hasher(twox_64_concat) WithdrawalRecordId => Option<H256>;
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        /// Trustees attest an ethereum header (only the fields which are used by the bridge),
+        /// the header is accepted when the votes reach the threshold of the trustees. A trustee
+        /// could only vote for one attestation of a block, the votes for the other attestations
+        /// of the block are dropped after the block is attested.
+        #[weight = <T as Trait>::WeightInfo::attest_header(
+            T::TrusteeSessionProvider::current_trustee_session()
+                .map(|session| session.trustee_list.len() as u32)
+                .unwrap_or(0)
+        )]
+        pub fn attest_header(origin, header: EthHeaderAttestation) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+            let session = Self::ensure_trustee(&from)?;
+            ensure!(!Headers::contains_key(&header.hash), Error::<T>::ExistingHeader);
+
+            let vote_key = H256(blake2_256(&header.encode()));
+            let mut vote_keys = Self::header_vote_keys(&header.hash);
+            ensure!(
+                vote_keys.iter().all(|key| !Self::header_votes(key).contains(&from)),
+                Error::<T>::DuplicateVote
+            );
+            let mut votes = Self::header_votes(&vote_key);
+            votes.push(from.clone());
+            Self::deposit_event(Event::<T>::HeaderVoted(from, header.hash));
+
+            if votes.len() >= session.threshold as usize {
+                info!("[attest_header] Header attested:{:?}", header);
+                Headers::insert(&header.hash, header);
+                for key in HeaderVoteKeys::take(&header.hash) {
+                    HeaderVotes::<T>::remove(&key);
+                }
+                Self::deposit_event(Event::<T>::HeaderAttested(header.hash, header.number));
+            } else {
+                if !vote_keys.contains(&vote_key) {
+                    vote_keys.push(vote_key);
+                    HeaderVoteKeys::insert(&header.hash, vote_keys);
+                }
+                HeaderVotes::<T>::insert(&vote_key, votes);
+            }
+            Ok(())
+        }
+
+        /// Relay a receipt of an attested header, mint the deposits into the bridge contract.
+        /// `proof` is the merkle patricia trie proof of the receipt in the receipts trie.
+        #[weight = <T as Trait>::WeightInfo::push_deposit()]
+        pub fn push_deposit(origin, block_hash: H256, receipt_index: u32, proof: Vec<Vec<u8>>) -> DispatchResult {
+            let _from = ensure_signed(origin)?;
+            Self::apply_push_deposit(block_hash, receipt_index, proof)
+        }
+
+        /// Trustees sign a withdrawal with the signer key. The withdrawal would be `Processing`
+        /// after the first signature, and approved when the signatures reach the threshold.
+        #[weight = <T as Trait>::WeightInfo::sign_withdrawal()]
+        pub fn sign_withdrawal(origin, id: WithdrawalRecordId, signature: ecdsa::Signature) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+            Self::apply_sign_withdrawal(from, id, signature)
+        }
+
+        /// Relay the receipt in which the bridge contract paid the approved withdrawal `id`,
+        /// release the withdrawal. `proof` is the merkle patricia trie proof of the receipt in
+        /// the receipts trie.
+        #[weight = <T as Trait>::WeightInfo::release_withdrawal()]
+        pub fn release_withdrawal(
+            origin,
+            id: WithdrawalRecordId,
+            block_hash: H256,
+            receipt_index: u32,
+            proof: Vec<Vec<u8>>
+        ) -> DispatchResult {
+            let _from = ensure_signed(origin)?;
+            Self::apply_release_withdrawal(id, block_hash, receipt_index, proof)
+        }
+
+        /// Register a token which could be deposited, ETH is the zero address.
+        #[weight = <T as Trait>::WeightInfo::register_token()]
+        pub fn register_token(origin, token: H160, info: EthTokenInfo) -> DispatchResult {
+            ensure_root(origin)?;
+            let chain = xpallet_assets_registrar::Module::<T>::chain_of(&info.asset_id)?;
+            ensure!(chain == Chain::Ethereum, Error::<T>::InvalidTokenAsset);
+
+            if let Some(old) = Self::tokens(&token) {
+                AssetTokens::remove(old.asset_id);
+            }
+            Tokens::insert(&token, info);
+            AssetTokens::insert(info.asset_id, token);
+            Self::deposit_event(Event::<T>::TokenRegistered(token, info));
+            Ok(())
+        }
+
+        /// Set the bridge contract on ethereum.
+        #[weight = <T as Trait>::WeightInfo::set_bridge_contract()]
+        pub fn set_bridge_contract(origin, contract: H160) -> DispatchResult {
+            ensure_root(origin)?;
+            BridgeContract::put(contract);
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    fn ensure_trustee(
+        who: &T::AccountId,
+    ) -> Result<TrusteeSessionInfo<T::AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        let session = T::TrusteeSessionProvider::current_trustee_session()?;
+        if session.trustee_list.contains(who) {
+            Ok(session)
+        } else {
+            error!(
+                "[ensure_trustee] Committer {:?} not in the trustee list:{:?}",
+                who, session.trustee_list
+            );
+            Err(Error::<T>::NotTrustee.into())
+        }
+    }
+
+    /// Verify the receipt in the attested header, return the logs of the receipt.
+    fn verified_receipt_logs(
+        block_hash: H256,
+        receipt_index: u32,
+        proof: Vec<Vec<u8>>,
+    ) -> Result<Vec<EthLog>, DispatchError> {
+        let header = Self::headers(&block_hash).ok_or(Error::<T>::UnattestedHeader)?;
+        let key = rlp::encode_u64(receipt_index as u64);
+        let receipt = proof::verify_trie_proof(header.receipts_root, &key, &proof)
+            .ok_or(Error::<T>::InvalidReceiptProof)?;
+        proof::decode_receipt_logs(&receipt).ok_or_else(|| Error::<T>::InvalidReceipt.into())
+    }
+
+    fn apply_push_deposit(
+        block_hash: H256,
+        receipt_index: u32,
+        proof: Vec<Vec<u8>>,
+    ) -> DispatchResult {
+        ensure!(
+            Headers::contains_key(&block_hash),
+            Error::<T>::UnattestedHeader
+        );
+        ensure!(
+            !Self::processed_receipts(&block_hash, receipt_index),
+            Error::<T>::ReplayedReceipt
+        );
+        let logs = Self::verified_receipt_logs(block_hash, receipt_index, proof)?;
+
+        let bridge = Self::bridge_contract();
+        let deposits = logs
+            .iter()
+            .filter(|log| {
+                log.address == bridge && log.topics.first() == Some(&H256(DEPOSIT_EVENT_TOPIC))
+            })
+            .map(Self::parse_deposit_log)
+            .collect::<Result<Vec<_>, DispatchError>>()?;
+        ensure!(!deposits.is_empty(), Error::<T>::NoDepositLog);
+
+        with_transaction_result(|| {
+            for (who, asset_id, balance) in deposits {
+                xpallet_gateway_records::Module::<T>::deposit(&who, asset_id, balance)?;
+                info!(
+                    "[apply_push_deposit] Deposit, block:{:?}, receipt:{}, who:{:?}, asset:{}, balance:{:?}",
+                    block_hash, receipt_index, who, asset_id, balance
+                );
+                Self::deposit_event(Event::<T>::Deposited(
+                    block_hash,
+                    receipt_index,
+                    who,
+                    asset_id,
+                    balance,
+                ));
+            }
+            ProcessedReceipts::insert(&block_hash, receipt_index, true);
+            Ok(())
+        })
+    }
+
+    /// Parse `Deposit(address indexed token, bytes32 indexed recipient, uint256 amount)`.
+    fn parse_deposit_log(
+        log: &EthLog,
+    ) -> Result<(T::AccountId, AssetId, BalanceOf<T>), DispatchError> {
+        if log.topics.len() != 3 || log.data.len() != 32 {
+            return Err(Error::<T>::InvalidReceipt.into());
+        }
+        let token = H160::from_slice(&log.topics[1][12..]);
+        let who = T::AccountId::decode(&mut log.topics[2].as_bytes())
+            .map_err(|_| Error::<T>::InvalidRecipient)?;
+        let amount = U256::from_big_endian(&log.data);
+
+        let info = Self::tokens(&token).ok_or_else(|| {
+            error!("[parse_deposit_log] Unregistered token:{:?}", token);
+            Error::<T>::UnregisteredToken
+        })?;
+        let asset_decimals =
+            xpallet_assets_registrar::Module::<T>::get_asset_info(&info.asset_id)?.decimals();
+        let balance = to_asset_amount(amount, info.decimals, asset_decimals)
+            .ok_or(Error::<T>::AmountOverflow)?;
+        Ok((who, info.asset_id, balance.saturated_into()))
+    }
+
+    fn apply_sign_withdrawal(
+        who: T::AccountId,
+        id: WithdrawalRecordId,
+        signature: ecdsa::Signature,
+    ) -> DispatchResult {
+        let session = Self::ensure_trustee(&who)?;
+        let signer = session
+            .trustee_list
+            .iter()
+            .position(|trustee| trustee == &who)
+            .and_then(|index| session.hot_address.signers.get(index))
+            .ok_or(Error::<T>::NotTrustee)?;
+
+        ensure!(
+            !ApprovedWithdrawals::contains_key(id),
+            Error::<T>::ApprovedWithdrawal
+        );
+        let record = xpallet_gateway_records::Module::<T>::pending_withdrawals(id)
+            .ok_or(Error::<T>::NoWithdrawalRecord)?;
+        let message = Self::withdrawal_message(id, &record)?;
+        if recover_signer(&signature, &message) != Some(*signer) {
+            error!(
+                "[apply_sign_withdrawal] Signature not match the signer:{:?}, withdrawal:{}",
+                signer, id
+            );
+            return Err(Error::<T>::InvalidSignature.into());
+        }
+
+        // the delayed withdrawal could not be signed until it matures
+        let state = xpallet_gateway_records::Module::<T>::state_of(id);
+        ensure!(
+            state == Some(WithdrawalState::Applying) || state == Some(WithdrawalState::Processing),
+            xpallet_gateway_records::Error::<T>::NotApplyingState
+        );
+
+        let mut signatures = Self::withdrawal_signatures(id);
+        ensure!(
+            signatures.iter().all(|(trustee, _)| trustee != &who),
+            Error::<T>::DuplicateVote
+        );
+        with_transaction_result(|| {
+            if state == Some(WithdrawalState::Applying) {
+                xpallet_gateway_records::Module::<T>::process_withdrawal(id, Chain::Ethereum)?;
+            }
+            signatures.push((who.clone(), signature));
+            Self::deposit_event(Event::<T>::WithdrawalSigned(id, who));
+
+            if signatures.len() >= session.hot_address.threshold as usize {
+                // keep the withdrawal processing until the payout is relayed back
+                ApprovedWithdrawals::insert(id, H256(message));
+                WithdrawalSignatures::<T>::remove(id);
+                info!("[apply_sign_withdrawal] Withdrawal approved, id:{}", id);
+                Self::deposit_event(Event::<T>::WithdrawalApproved(
+                    id,
+                    signatures.into_iter().map(|(_, sig)| sig).collect(),
+                ));
+            } else {
+                WithdrawalSignatures::<T>::insert(id, signatures);
+            }
+            Ok(())
+        })
+    }
+
+    fn apply_release_withdrawal(
+        id: WithdrawalRecordId,
+        block_hash: H256,
+        receipt_index: u32,
+        proof: Vec<Vec<u8>>,
+    ) -> DispatchResult {
+        let message = Self::approved_withdrawals(id).ok_or(Error::<T>::UnapprovedWithdrawal)?;
+        let logs = Self::verified_receipt_logs(block_hash, receipt_index, proof)?;
+
+        let bridge = Self::bridge_contract();
+        let paid = logs.iter().any(|log| {
+            log.address == bridge
+                && log.topics.len() == 2
+                && log.topics[0] == H256(WITHDRAWN_EVENT_TOPIC)
+                && log.topics[1] == message
+        });
+        if !paid {
+            error!(
+                "[apply_release_withdrawal] No withdrawn log, withdrawal:{}, message:{:?}, block:{:?}, receipt:{}",
+                id, message, block_hash, receipt_index
+            );
+            return Err(Error::<T>::NoWithdrawnLog.into());
+        }
+
+        with_transaction_result(|| {
+            xpallet_gateway_records::Module::<T>::finish_withdrawal(id, Some(Chain::Ethereum))?;
+            ApprovedWithdrawals::remove(id);
+            info!(
+                "[apply_release_withdrawal] Release withdrawal:{}, block:{:?}, receipt:{}",
+                id, block_hash, receipt_index
+            );
+            Self::deposit_event(Event::<T>::Released(id, block_hash, receipt_index));
+            Ok(())
+        })
+    }
+
+    /// The message which trustees sign for a withdrawal, the bridge contract would rebuild it:
+    /// `keccak256(abi.encodePacked(bridge, uint32(id), token, recipient, uint256(amount), data))`,
+    /// `data` is the calldata in the memo, which is empty for a plain transfer.
+    pub fn withdrawal_message(
+        id: WithdrawalRecordId,
+        record: &WithdrawalRecordOf<T>,
+    ) -> Result<[u8; 32], DispatchError> {
+        let asset_id = record.asset_id();
+        let token = Self::asset_tokens(asset_id).ok_or(Error::<T>::UnregisteredToken)?;
+        let info = Self::tokens(&token).ok_or(Error::<T>::UnregisteredToken)?;
+        let recipient = parse_address(&record.addr()).ok_or(Error::<T>::InvalidAddress)?;
+        let asset_decimals =
+            xpallet_assets_registrar::Module::<T>::get_asset_info(&asset_id)?.decimals();
+        let amount = to_token_amount(
+            record.balance().saturated_into(),
+            info.decimals,
+            asset_decimals,
+        )
+        .ok_or(Error::<T>::AmountOverflow)?;
+
+        let mut amount_bytes = [0u8; 32];
+        amount.to_big_endian(&mut amount_bytes);
+
+        let data = record.ext().as_ref();
+        let mut message = Vec::with_capacity(20 + 4 + 20 + 20 + 32 + data.len());
+        message.extend_from_slice(Self::bridge_contract().as_bytes());
+        message.extend_from_slice(&id.to_be_bytes());
+        message.extend_from_slice(token.as_bytes());
+        message.extend_from_slice(recipient.as_bytes());
+        message.extend_from_slice(&amount_bytes);
+        message.extend_from_slice(data);
+        Ok(keccak_256(&message))
+    }
+}
+
+/// Parse the ethereum address from the raw 20 bytes.
+fn parse_address(addr: &[u8]) -> Option<H160> {
+    if addr.len() == 20 {
+        Some(H160::from_slice(addr)).filter(|addr| !addr.is_zero())
+    } else {
+        None
+    }
+}
+
+/// Recover the ethereum address which signed the message.
+fn recover_signer(signature: &ecdsa::Signature, message: &[u8; 32]) -> Option<H160> {
+    let signature: &[u8; 65] = signature.as_ref();
+    sp_io::crypto::secp256k1_ecdsa_recover(signature, message)
+        .ok()
+        .map(|public| H160::from_slice(&keccak_256(&public)[12..]))
+}
+
+/// Convert the token amount on ethereum to the asset amount in ChainX, the dust is dropped.
+fn to_asset_amount(amount: U256, token_decimals: u8, asset_decimals: u8) -> Option<u128> {
+    let amount = if token_decimals >= asset_decimals {
+        amount / U256::exp10((token_decimals - asset_decimals) as usize)
+    } else {
+        amount.checked_mul(U256::exp10((asset_decimals - token_decimals) as usize))?
+    };
+    if amount > U256::from(u128::max_value()) {
+        None
+    } else {
+        Some(amount.low_u128())
+    }
+}
+
+/// Convert the asset amount in ChainX to the token amount on ethereum.
+fn to_token_amount(balance: u128, token_decimals: u8, asset_decimals: u8) -> Option<U256> {
+    let balance = U256::from(balance);
+    if token_decimals >= asset_decimals {
+        balance.checked_mul(U256::exp10((token_decimals - asset_decimals) as usize))
+    } else {
+        Some(balance / U256::exp10((asset_decimals - token_decimals) as usize))
+    }
+}
+
+impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
+    const ASSET_ID: AssetId = xp_protocol::X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        // the addr is the raw 20 bytes of the ethereum address
+        let address = parse_address(addr).ok_or(Error::<T>::InvalidAddress)?;
+        // do not allow withdraw to the bridge contract
+        if address == Self::bridge_contract() {
+            return Err(Error::<T>::InvalidAddress.into());
+        }
+        Ok(())
+    }
+}
+
+impl<T: Trait> TrusteeForChain<T::AccountId, EthTrusteeType, EthTrusteeAddrInfo> for Module<T> {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<EthTrusteeType, DispatchError> {
+        let address = parse_address(raw_addr).ok_or(Error::<T>::InvalidTrusteeEntity)?;
+        Ok(EthTrusteeType(address))
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, TrusteeIntentionProps<EthTrusteeType>)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<T::AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        let (trustees, props_info): (
+            Vec<T::AccountId>,
+            Vec<TrusteeIntentionProps<EthTrusteeType>>,
+        ) = props.into_iter().unzip();
+
+        if (trustees.len() as u32) < config.min_trustee_count
+            || (trustees.len() as u32) > config.max_trustee_count
+        {
+            error!(
+                "[generate_trustee_session_info] Trustees {:?} is less/more than {{min:{}, max:{}}} people",
+                trustees, config.min_trustee_count, config.max_trustee_count
+            );
+            return Err(Error::<T>::InvalidTrusteeCount.into());
+        }
+
+        let (hot_signers, cold_signers): (Vec<H160>, Vec<H160>) = props_info
+            .into_iter()
+            .map(|props| (props.hot_entity.0, props.cold_entity.0))
+            .unzip();
+        let has_duplicate =
+            |keys: &[H160]| (1..keys.len()).any(|i| keys[i..].contains(&keys[i - 1]));
+        if has_duplicate(&hot_signers) || has_duplicate(&cold_signers) {
+            error!("[generate_trustee_session_info] Signers contains duplicate address");
+            return Err(Error::<T>::DuplicatedKeys.into());
+        }
+
+        let threshold = two_thirds_unsafe(trustees.len() as u32) as u16;
+        Ok(TrusteeSessionInfo {
+            trustee_list: trustees,
+            threshold,
+            hot_address: EthTrusteeAddrInfo {
+                signers: hot_signers,
+                threshold,
+            },
+            cold_address: EthTrusteeAddrInfo {
+                signers: cold_signers,
+                threshold,
+            },
+        })
+    }
+}
+
+impl<T: Trait> ChainGateway<T::AccountId, BalanceOf<T>> for Module<T> {
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+
+    fn addr_format() -> AddrFormat {
+        AddrFormat::Hex
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        <Self as ChainT<_>>::withdrawal_limit(asset_id)
+    }
+
+    fn check_addr(addr: &[u8], ext: &[u8]) -> DispatchResult {
+        <Self as ChainT<_>>::check_addr(addr, ext)
+    }
+
+    /// The memo is the calldata for the recipient, it must contain the function selector if
+    /// it's not empty.
+    fn check_memo(memo: &Memo) -> DispatchResult {
+        memo.check_len()?;
+        let len = memo.as_ref().len();
+        ensure!(len == 0 || len >= 4, Error::<T>::InvalidCalldata);
+        Ok(())
+    }
+
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<Vec<u8>, DispatchError> {
+        Self::check_generic_trustee_entity(raw_addr)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, GenericTrusteeIntentionProps)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<GenericTrusteeSessionInfo<T::AccountId>, DispatchError> {
+        Self::generate_generic_trustee_session_info(props, config)
+    }
+}
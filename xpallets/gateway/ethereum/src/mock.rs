@@ -0,0 +1,226 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::collections::BTreeMap;
+
+use hex_literal::hex;
+
+use frame_support::{impl_outer_origin, parameter_types, sp_io, weights::Weight};
+use sp_core::{H160, H256};
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32, DispatchError, Perbill,
+};
+
+use chainx_primitives::AssetId;
+use xp_assets_registrar::Chain;
+pub use xp_protocol::X_ETH;
+use xpallet_assets::AssetRestrictions;
+use xpallet_assets_registrar::AssetInfo;
+use xpallet_gateway_common::{
+    traits::TrusteeSession, trustees::ethereum::EthTrusteeAddrInfo, types::TrusteeSessionInfo,
+};
+
+use crate::{Error, Module, Trait};
+
+/// The AccountId alias in this test module.
+pub(crate) type AccountId = AccountId32;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+pub(crate) type Amount = i128;
+
+impl_outer_origin! {
+    pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = ();
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 0;
+}
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+// assets
+parameter_types! {
+    pub const ChainXAssetId: AssetId = 0;
+}
+
+impl xpallet_assets_registrar::Trait for Test {
+    type Event = ();
+    type NativeAssetId = ChainXAssetId;
+    type RegistrarHandler = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_assets::Trait for Test {
+    type Event = ();
+    type Currency = Balances;
+    type Amount = Amount;
+    type TreasuryAccount = ();
+    type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
+    type OnAssetChanged = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_gateway_records::Trait for Test {
+    type Event = ();
+    type GuardianOrigin = frame_system::EnsureRoot<AccountId>;
+    type WeightInfo = ();
+}
+
+pub struct MockTrusteeSession;
+impl TrusteeSession<AccountId, EthTrusteeAddrInfo> for MockTrusteeSession {
+    fn trustee_session(
+        _: u32,
+    ) -> Result<TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        Self::current_trustee_session()
+    }
+
+    fn current_trustee_session(
+    ) -> Result<TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        let signers = trustee_signers();
+        Ok(TrusteeSessionInfo {
+            trustee_list: vec![alice(), bob(), charlie()],
+            threshold: 2,
+            hot_address: EthTrusteeAddrInfo {
+                signers: signers.clone(),
+                threshold: 2,
+            },
+            cold_address: EthTrusteeAddrInfo {
+                signers,
+                threshold: 2,
+            },
+        })
+    }
+
+    fn last_trustee_session(
+    ) -> Result<TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        Self::current_trustee_session()
+    }
+
+    fn genesis_trustee(_: Chain, _: &[AccountId]) {}
+}
+
+impl Trait for Test {
+    type Event = ();
+    type TrusteeSessionProvider = MockTrusteeSession;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type XAssets = xpallet_assets::Module<Test>;
+pub type XGatewayRecords = xpallet_gateway_records::Module<Test>;
+pub type XGatewayEthereum = Module<Test>;
+pub type XGatewayEthereumErr = Error<Test>;
+
+pub fn alice() -> AccountId {
+    AccountId32::new([1; 32])
+}
+pub fn bob() -> AccountId {
+    AccountId32::new([2; 32])
+}
+pub fn charlie() -> AccountId {
+    AccountId32::new([3; 32])
+}
+
+/// The ethereum addresses of the secret keys `0x01`, `0x02` and `0x03`.
+pub fn trustee_signers() -> Vec<H160> {
+    vec![
+        hex!["7e5f4552091a69125d5dfcb7b8c2659029395bdf"].into(),
+        hex!["2b5ad5c4795c026514f8317c7a215e218dccd6cf"].into(),
+        hex!["6813eb9362372eef6200f3b1dbc3f819671cba69"].into(),
+    ]
+}
+
+fn eth() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        X_ETH,
+        AssetInfo::new::<Test>(
+            b"X-ETH".to_vec(),
+            b"X-ETH".to_vec(),
+            Chain::Ethereum,
+            18,
+            b"ChainX's cross-chain Ethereum".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self
+    }
+}
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        let (asset_id, info, restrictions) = eth();
+        let _ = xpallet_assets_registrar::GenesisConfig {
+            assets: vec![(asset_id, info, true, true)],
+        }
+        .assimilate_storage::<Test>(&mut storage);
+
+        let _ = xpallet_assets::GenesisConfig::<Test> {
+            assets_restrictions: vec![(asset_id, restrictions)],
+            endowed: BTreeMap::new(),
+        }
+        .assimilate_storage(&mut storage);
+
+        sp_io::TestExternalities::new(storage)
+    }
+    pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
+        let mut ext = self.build();
+        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(test);
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Verify the receipt proof of the receipts trie (merkle patricia trie) and decode receipts.
+
+use sp_core::{H160, H256};
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+use crate::rlp::Rlp;
+use crate::types::EthLog;
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| vec![b >> 4, b & 0x0f]).collect()
+}
+
+/// Decode the hex-prefix encoded path of a leaf/extension node, return (is_leaf, path).
+fn decode_hex_prefix(encoded: &[u8]) -> Option<(bool, Vec<u8>)> {
+    let nibbles = to_nibbles(encoded);
+    let flag = *nibbles.first()?;
+    if flag > 3 {
+        return None;
+    }
+    let is_leaf = flag & 2 != 0;
+    let path = if flag & 1 != 0 {
+        nibbles[1..].to_vec()
+    } else {
+        nibbles.get(2..)?.to_vec()
+    };
+    Some((is_leaf, path))
+}
+
+/// Verify the merkle patricia trie proof for `key`, return the value of the key.
+///
+/// Return `None` if the proof is invalid or the key does not exist in the trie.
+pub fn verify_trie_proof(root: H256, key: &[u8], proof: &[Vec<u8>]) -> Option<Vec<u8>> {
+    let nibbles = to_nibbles(key);
+    let mut path = &nibbles[..];
+    let mut proof = proof.iter();
+
+    let mut node = proof.next()?.clone();
+    if H256(keccak_256(&node)) != root {
+        return None;
+    }
+    loop {
+        let items = Rlp::new(&node).items()?;
+        let child = match items.len() {
+            // branch node
+            17 => {
+                if path.is_empty() {
+                    return items[16].data().filter(|v| !v.is_empty()).map(Into::into);
+                }
+                let child = items[path[0] as usize];
+                path = &path[1..];
+                child
+            }
+            // leaf or extension node
+            2 => {
+                let (is_leaf, node_path) = decode_hex_prefix(items[0].data()?)?;
+                if is_leaf {
+                    return if node_path[..] == path[..] {
+                        items[1].data().map(Into::into)
+                    } else {
+                        None
+                    };
+                }
+                if !path.starts_with(&node_path) {
+                    return None;
+                }
+                path = &path[node_path.len()..];
+                items[1]
+            }
+            _ => return None,
+        };
+        let next = if child.is_list() {
+            // the node is embedded in its parent when the encoded node is less than 32 bytes
+            child.as_raw().to_vec()
+        } else {
+            let hash = child.data()?;
+            if hash.len() != 32 {
+                return None;
+            }
+            let next = proof.next()?.clone();
+            if keccak_256(&next)[..] != hash[..] {
+                return None;
+            }
+            next
+        };
+        node = next;
+    }
+}
+
+/// Decode the logs of a receipt, typed receipts (EIP-2718) are supported.
+pub fn decode_receipt_logs(receipt: &[u8]) -> Option<Vec<EthLog>> {
+    let receipt = match receipt.first()? {
+        // the first byte of a typed receipt is the transaction type
+        0x00..=0x7f => &receipt[1..],
+        _ => receipt,
+    };
+    let items = Rlp::new(receipt).items()?;
+    // [status, cumulative_gas_used, logs_bloom, logs]
+    if items.len() != 4 {
+        return None;
+    }
+    items[3]
+        .items()?
+        .into_iter()
+        .map(|log| {
+            // [address, topics, data]
+            let fields = log.items()?;
+            if fields.len() != 3 {
+                return None;
+            }
+            let address = fields[0].data().filter(|a| a.len() == 20)?;
+            let topics = fields[1]
+                .items()?
+                .into_iter()
+                .map(|topic| topic.data().filter(|t| t.len() == 32).map(H256::from_slice))
+                .collect::<Option<Vec<_>>>()?;
+            Some(EthLog {
+                address: H160::from_slice(address),
+                topics,
+                data: fields[2].data()?.to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// Build a receipts trie which only contains one receipt, return the root and the proof.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn single_receipt_proof(index: u64, receipt: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    use crate::rlp::{encode_bytes, encode_list, encode_u64};
+    // even length leaf path is prefixed by 0x20
+    let mut path = vec![0x20];
+    path.extend(encode_u64(index));
+    let leaf = encode_list(&[encode_bytes(&path), encode_bytes(receipt)]);
+    (H256(keccak_256(&leaf)), vec![leaf])
+}
+
+/// Build a legacy receipt which contains a `Deposit` log of the bridge contract.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn deposit_receipt(
+    bridge: H160,
+    token: H160,
+    recipient: H256,
+    amount: sp_core::U256,
+) -> Vec<u8> {
+    use crate::rlp::{encode_bytes, encode_list};
+    let mut token_topic = [0u8; 32];
+    token_topic[12..].copy_from_slice(token.as_bytes());
+    let mut data = [0u8; 32];
+    amount.to_big_endian(&mut data);
+
+    let topics = encode_list(&[
+        encode_bytes(&crate::DEPOSIT_EVENT_TOPIC),
+        encode_bytes(&token_topic),
+        encode_bytes(recipient.as_bytes()),
+    ]);
+    let log = encode_list(&[encode_bytes(bridge.as_bytes()), topics, encode_bytes(&data)]);
+    legacy_receipt(log)
+}
+
+/// Build a legacy receipt which contains a `Withdrawn` log of the bridge contract.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn withdrawn_receipt(bridge: H160, message: H256) -> Vec<u8> {
+    use crate::rlp::{encode_bytes, encode_list};
+    let topics = encode_list(&[
+        encode_bytes(&crate::WITHDRAWN_EVENT_TOPIC),
+        encode_bytes(message.as_bytes()),
+    ]);
+    let log = encode_list(&[encode_bytes(bridge.as_bytes()), topics, encode_bytes(&[])]);
+    legacy_receipt(log)
+}
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+fn legacy_receipt(log: Vec<u8>) -> Vec<u8> {
+    use crate::rlp::{encode_bytes, encode_list, encode_u64};
+    encode_list(&[
+        encode_bytes(&[1]),
+        encode_u64(21_000),
+        encode_bytes(&[0u8; 256]),
+        encode_list(&[log]),
+    ])
+}
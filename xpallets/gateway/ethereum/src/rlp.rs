@@ -0,0 +1,136 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! A minimal RLP codec, just enough to walk the receipt trie proof and decode receipts.
+
+use sp_std::prelude::*;
+
+/// A view of an RLP encoded item.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rlp<'a> {
+    raw: &'a [u8],
+}
+
+/// (is_list, offset of the payload, length of the payload)
+fn decode_header(raw: &[u8]) -> Option<(bool, usize, usize)> {
+    let first = *raw.first()?;
+    let (is_list, offset, len) = match first {
+        0x00..=0x7f => (false, 0, 1),
+        0x80..=0xb7 => (false, 1, (first - 0x80) as usize),
+        0xb8..=0xbf => {
+            let len_of_len = (first - 0xb7) as usize;
+            (
+                false,
+                1 + len_of_len,
+                decode_len(raw.get(1..1 + len_of_len)?)?,
+            )
+        }
+        0xc0..=0xf7 => (true, 1, (first - 0xc0) as usize),
+        0xf8..=0xff => {
+            let len_of_len = (first - 0xf7) as usize;
+            (
+                true,
+                1 + len_of_len,
+                decode_len(raw.get(1..1 + len_of_len)?)?,
+            )
+        }
+    };
+    if offset.checked_add(len)? > raw.len() {
+        return None;
+    }
+    Some((is_list, offset, len))
+}
+
+fn decode_len(bytes: &[u8]) -> Option<usize> {
+    if bytes.is_empty() || bytes.len() > 8 || bytes[0] == 0 {
+        return None;
+    }
+    Some(bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize))
+}
+
+impl<'a> Rlp<'a> {
+    pub fn new(raw: &'a [u8]) -> Self {
+        Self { raw }
+    }
+
+    /// The raw bytes of this item (including the rlp header).
+    pub fn as_raw(&self) -> &'a [u8] {
+        match decode_header(self.raw) {
+            Some((_, offset, len)) => &self.raw[..offset + len],
+            None => self.raw,
+        }
+    }
+
+    pub fn is_list(&self) -> bool {
+        matches!(decode_header(self.raw), Some((true, _, _)))
+    }
+
+    /// The payload of a string item.
+    pub fn data(&self) -> Option<&'a [u8]> {
+        match decode_header(self.raw)? {
+            (false, offset, len) => Some(&self.raw[offset..offset + len]),
+            (true, _, _) => None,
+        }
+    }
+
+    /// The items of a list item.
+    pub fn items(&self) -> Option<Vec<Rlp<'a>>> {
+        let (is_list, offset, len) = decode_header(self.raw)?;
+        if !is_list {
+            return None;
+        }
+        let mut payload = &self.raw[offset..offset + len];
+        let mut items = Vec::new();
+        while !payload.is_empty() {
+            let (_, item_offset, item_len) = decode_header(payload)?;
+            let (item, rest) = payload.split_at(item_offset + item_len);
+            items.push(Rlp::new(item));
+            payload = rest;
+        }
+        Some(items)
+    }
+}
+
+fn encode_header(len: usize, short: u8, long: u8) -> Vec<u8> {
+    if len < 56 {
+        vec![short + len as u8]
+    } else {
+        let len_bytes = len
+            .to_be_bytes()
+            .iter()
+            .skip_while(|b| **b == 0)
+            .copied()
+            .collect::<Vec<_>>();
+        let mut header = vec![long + len_bytes.len() as u8];
+        header.extend(len_bytes);
+        header
+    }
+}
+
+/// Encode bytes as a rlp string.
+pub fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut out = encode_header(bytes.len(), 0x80, 0xb7);
+    out.extend_from_slice(bytes);
+    out
+}
+
+/// Encode already encoded items as a rlp list.
+pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = encode_header(payload.len(), 0xc0, 0xf7);
+    out.extend(payload);
+    out
+}
+
+/// Encode an unsigned integer, e.g. the index of a receipt in the block.
+pub fn encode_u64(value: u64) -> Vec<u8> {
+    let bytes = value
+        .to_be_bytes()
+        .iter()
+        .skip_while(|b| **b == 0)
+        .copied()
+        .collect::<Vec<_>>();
+    encode_bytes(&bytes)
+}
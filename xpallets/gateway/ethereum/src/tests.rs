@@ -0,0 +1,386 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use hex_literal::hex;
+
+use frame_support::{assert_noop, assert_ok, storage::StorageMap};
+use frame_system::RawOrigin;
+use sp_core::{ecdsa, H160, H256, U256};
+
+use xp_assets_registrar::Chain;
+use xpallet_gateway_records::WithdrawalState;
+
+use crate::mock::*;
+use crate::proof::{deposit_receipt, single_receipt_proof, withdrawn_receipt};
+use crate::{ApprovedWithdrawals, EthHeaderAttestation, EthTokenInfo};
+
+const ETH: Balance = 1_000_000_000_000_000_000;
+
+fn bridge() -> H160 {
+    H160::repeat_byte(0xbb)
+}
+
+fn dave() -> AccountId {
+    AccountId::new([4; 32])
+}
+
+fn setup_bridge() {
+    assert_ok!(XGatewayEthereum::set_bridge_contract(
+        RawOrigin::Root.into(),
+        bridge()
+    ));
+    assert_ok!(XGatewayEthereum::register_token(
+        RawOrigin::Root.into(),
+        H160::zero(),
+        EthTokenInfo {
+            asset_id: X_ETH,
+            decimals: 18
+        }
+    ));
+}
+
+fn attest(header: EthHeaderAttestation) {
+    for trustee in vec![alice(), bob()] {
+        assert_ok!(XGatewayEthereum::attest_header(
+            RawOrigin::Signed(trustee).into(),
+            header
+        ));
+    }
+}
+
+#[test]
+fn test_attest_header() {
+    ExtBuilder::default().build_and_execute(|| {
+        let header = EthHeaderAttestation {
+            hash: H256::repeat_byte(1),
+            number: 100,
+            receipts_root: H256::repeat_byte(2),
+        };
+        assert_noop!(
+            XGatewayEthereum::attest_header(RawOrigin::Signed(dave()).into(), header),
+            XGatewayEthereumErr::NotTrustee
+        );
+
+        assert_ok!(XGatewayEthereum::attest_header(
+            RawOrigin::Signed(alice()).into(),
+            header
+        ));
+        assert_eq!(XGatewayEthereum::headers(&header.hash), None);
+        assert_noop!(
+            XGatewayEthereum::attest_header(RawOrigin::Signed(alice()).into(), header),
+            XGatewayEthereumErr::DuplicateVote
+        );
+
+        // a different attestation for the same block does not share the votes
+        let fake = EthHeaderAttestation {
+            receipts_root: H256::repeat_byte(3),
+            ..header
+        };
+        assert_ok!(XGatewayEthereum::attest_header(
+            RawOrigin::Signed(bob()).into(),
+            fake
+        ));
+        assert_eq!(XGatewayEthereum::headers(&header.hash), None);
+        // a trustee could only vote for one attestation of the block
+        assert_noop!(
+            XGatewayEthereum::attest_header(RawOrigin::Signed(alice()).into(), fake),
+            XGatewayEthereumErr::DuplicateVote
+        );
+        let vote_keys = XGatewayEthereum::header_vote_keys(&header.hash);
+        assert_eq!(vote_keys.len(), 2);
+
+        assert_ok!(XGatewayEthereum::attest_header(
+            RawOrigin::Signed(charlie()).into(),
+            header
+        ));
+        assert_eq!(XGatewayEthereum::headers(&header.hash), Some(header));
+        // the votes for the losing attestation are dropped
+        assert!(XGatewayEthereum::header_vote_keys(&header.hash).is_empty());
+        for key in vote_keys {
+            assert!(XGatewayEthereum::header_votes(&key).is_empty());
+        }
+        assert_noop!(
+            XGatewayEthereum::attest_header(RawOrigin::Signed(bob()).into(), header),
+            XGatewayEthereumErr::ExistingHeader
+        );
+    });
+}
+
+#[test]
+fn test_push_deposit() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup_bridge();
+        let recipient = H256::from_slice(dave().as_ref());
+        let receipt = deposit_receipt(bridge(), H160::zero(), recipient, U256::from(ETH));
+        let (receipts_root, proof) = single_receipt_proof(3, &receipt);
+        let header = EthHeaderAttestation {
+            hash: H256::repeat_byte(1),
+            number: 100,
+            receipts_root,
+        };
+
+        assert_noop!(
+            XGatewayEthereum::push_deposit(
+                RawOrigin::Signed(alice()).into(),
+                header.hash,
+                3,
+                proof.clone()
+            ),
+            XGatewayEthereumErr::UnattestedHeader
+        );
+        attest(header);
+
+        // the proof is for the receipt with index 3
+        assert_noop!(
+            XGatewayEthereum::push_deposit(
+                RawOrigin::Signed(alice()).into(),
+                header.hash,
+                2,
+                proof.clone()
+            ),
+            XGatewayEthereumErr::InvalidReceiptProof
+        );
+
+        assert_ok!(XGatewayEthereum::push_deposit(
+            RawOrigin::Signed(alice()).into(),
+            header.hash,
+            3,
+            proof.clone()
+        ));
+        assert_eq!(XAssets::usable_balance(&dave(), &X_ETH), ETH);
+        assert!(XGatewayEthereum::processed_receipts(&header.hash, 3));
+
+        assert_noop!(
+            XGatewayEthereum::push_deposit(RawOrigin::Signed(bob()).into(), header.hash, 3, proof),
+            XGatewayEthereumErr::ReplayedReceipt
+        );
+    });
+}
+
+#[test]
+fn test_push_deposit_from_other_contract() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup_bridge();
+        let recipient = H256::from_slice(dave().as_ref());
+        let receipt = deposit_receipt(
+            H160::repeat_byte(0xcc),
+            H160::zero(),
+            recipient,
+            U256::from(ETH),
+        );
+        let (receipts_root, proof) = single_receipt_proof(0, &receipt);
+        let header = EthHeaderAttestation {
+            hash: H256::repeat_byte(1),
+            number: 100,
+            receipts_root,
+        };
+        attest(header);
+
+        assert_noop!(
+            XGatewayEthereum::push_deposit(
+                RawOrigin::Signed(alice()).into(),
+                header.hash,
+                0,
+                proof
+            ),
+            XGatewayEthereumErr::NoDepositLog
+        );
+    });
+}
+
+#[test]
+fn test_sign_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup_bridge();
+        assert_ok!(XGatewayRecords::deposit(&dave(), X_ETH, ETH));
+        assert_ok!(XGatewayRecords::withdraw(
+            &dave(),
+            X_ETH,
+            ETH,
+            vec![0xaa; 20],
+            b"".to_vec().into()
+        ));
+
+        let record = XGatewayRecords::pending_withdrawals(0).unwrap();
+        assert_eq!(
+            XGatewayEthereum::withdrawal_message(0, &record).unwrap(),
+            hex!["e6b3924cb8c04e6bc5e82e0eacad3362f9ff3066b6ddb39bbdd419c034092957"]
+        );
+
+        // signed by the key of alice
+        let alice_sig = ecdsa::Signature::from_raw(hex![
+            "7592aab5d43618dda13fba71e3993cd7517a712d3da49664c06ee1bd3d1f70af108b78a90bcf4e3f5b8c66e3066b7a8bf90a1a41ac1d0485925048efc5c1946f00"
+        ]);
+        // signed by the key of bob
+        let bob_sig = ecdsa::Signature::from_raw(hex![
+            "e5740e63bad28081ed7cf654dd6c19029ca03382fc05ab5f5dda81f2c55b845b32667e56dd3f8147ad8276e2ae7ba612ed5fe8d4bbc4ab7d35e5070267b38bc701"
+        ]);
+
+        assert_noop!(
+            XGatewayEthereum::sign_withdrawal(RawOrigin::Signed(bob()).into(), 0, alice_sig.clone()),
+            XGatewayEthereumErr::InvalidSignature
+        );
+
+        assert_ok!(XGatewayEthereum::sign_withdrawal(
+            RawOrigin::Signed(alice()).into(),
+            0,
+            alice_sig.clone()
+        ));
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Processing)
+        );
+        assert_noop!(
+            XGatewayEthereum::sign_withdrawal(RawOrigin::Signed(alice()).into(), 0, alice_sig.clone()),
+            XGatewayEthereumErr::DuplicateVote
+        );
+
+        assert_ok!(XGatewayEthereum::sign_withdrawal(
+            RawOrigin::Signed(bob()).into(),
+            0,
+            bob_sig
+        ));
+        // the approved withdrawal keeps processing until the payout is relayed
+        assert!(XGatewayEthereum::withdrawal_signatures(0).is_empty());
+        assert_eq!(
+            XGatewayEthereum::approved_withdrawals(0),
+            Some(H256(hex![
+                "e6b3924cb8c04e6bc5e82e0eacad3362f9ff3066b6ddb39bbdd419c034092957"
+            ]))
+        );
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Processing)
+        );
+        assert_eq!(XAssets::total_issuance(&X_ETH), ETH);
+        assert_noop!(
+            XGatewayEthereum::sign_withdrawal(RawOrigin::Signed(charlie()).into(), 0, alice_sig),
+            XGatewayEthereumErr::ApprovedWithdrawal
+        );
+    });
+}
+
+#[test]
+fn test_release_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup_bridge();
+        assert_ok!(XGatewayRecords::deposit(&dave(), X_ETH, ETH));
+        assert_ok!(XGatewayRecords::withdraw(
+            &dave(),
+            X_ETH,
+            ETH,
+            vec![0xaa; 20],
+            b"".to_vec().into()
+        ));
+        let record = XGatewayRecords::pending_withdrawals(0).unwrap();
+        let message = H256(XGatewayEthereum::withdrawal_message(0, &record).unwrap());
+
+        let receipt = withdrawn_receipt(bridge(), message);
+        let (receipts_root, proof) = single_receipt_proof(1, &receipt);
+        let header = EthHeaderAttestation {
+            hash: H256::repeat_byte(1),
+            number: 100,
+            receipts_root,
+        };
+        attest(header);
+
+        // the withdrawal has not been approved by trustees
+        assert_noop!(
+            XGatewayEthereum::release_withdrawal(
+                RawOrigin::Signed(dave()).into(),
+                0,
+                header.hash,
+                1,
+                proof.clone()
+            ),
+            XGatewayEthereumErr::UnapprovedWithdrawal
+        );
+
+        assert_ok!(XGatewayRecords::process_withdrawal(0, Chain::Ethereum));
+        ApprovedWithdrawals::insert(0, message);
+
+        // the payout of another withdrawal
+        let other = withdrawn_receipt(bridge(), H256::repeat_byte(9));
+        let (other_root, other_proof) = single_receipt_proof(1, &other);
+        let other_header = EthHeaderAttestation {
+            hash: H256::repeat_byte(2),
+            number: 101,
+            receipts_root: other_root,
+        };
+        attest(other_header);
+        assert_noop!(
+            XGatewayEthereum::release_withdrawal(
+                RawOrigin::Signed(dave()).into(),
+                0,
+                other_header.hash,
+                1,
+                other_proof
+            ),
+            XGatewayEthereumErr::NoWithdrawnLog
+        );
+
+        assert_ok!(XGatewayEthereum::release_withdrawal(
+            RawOrigin::Signed(dave()).into(),
+            0,
+            header.hash,
+            1,
+            proof.clone()
+        ));
+        assert!(XGatewayRecords::pending_withdrawals(0).is_none());
+        assert!(XGatewayEthereum::approved_withdrawals(0).is_none());
+        assert_eq!(XAssets::total_issuance(&X_ETH), 0);
+
+        assert_noop!(
+            XGatewayEthereum::release_withdrawal(
+                RawOrigin::Signed(dave()).into(),
+                0,
+                header.hash,
+                1,
+                proof
+            ),
+            XGatewayEthereumErr::UnapprovedWithdrawal
+        );
+    });
+}
+
+#[test]
+fn test_check_addr() {
+    ExtBuilder::default().build_and_execute(|| {
+        use xpallet_assets::ChainT;
+        setup_bridge();
+        assert_ok!(XGatewayEthereum::check_addr(&[0xaa; 20], b""));
+        assert!(XGatewayEthereum::check_addr(&[0xaa; 19], b"").is_err());
+        assert!(XGatewayEthereum::check_addr(&[0; 20], b"").is_err());
+        assert!(XGatewayEthereum::check_addr(bridge().as_bytes(), b"").is_err());
+    });
+}
+
+#[test]
+fn test_withdrawal_calldata() {
+    ExtBuilder::default().build_and_execute(|| {
+        use xpallet_gateway_common::traits::ChainGateway;
+        setup_bridge();
+        assert_ok!(XGatewayEthereum::check_memo(&b"".to_vec().into()));
+        assert_noop!(
+            XGatewayEthereum::check_memo(&hex!["a9059c"].to_vec().into()),
+            XGatewayEthereumErr::InvalidCalldata
+        );
+        // the calldata is not a readable note
+        assert_ok!(XGatewayEthereum::check_memo(&b"<>()".to_vec().into()));
+        assert!(XGatewayEthereum::check_memo(&vec![0u8; 129].into()).is_err());
+
+        let calldata = hex!["a9059cbb11111111"].to_vec();
+        assert_ok!(XGatewayRecords::deposit(&dave(), X_ETH, ETH));
+        assert_ok!(XGatewayRecords::withdraw(
+            &dave(),
+            X_ETH,
+            ETH,
+            vec![0xaa; 20],
+            calldata.into()
+        ));
+        let record = XGatewayRecords::pending_withdrawals(0).unwrap();
+        assert_eq!(
+            XGatewayEthereum::withdrawal_message(0, &record).unwrap(),
+            hex!["7be98ed46459025951e1d4b857545b4549ff54c95f1a159336228a87bdc7a549"]
+        );
+    });
+}
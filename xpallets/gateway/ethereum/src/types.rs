@@ -0,0 +1,40 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_core::{H160, H256};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use chainx_primitives::AssetId;
+
+/// The ethereum header info attested by trustees.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthHeaderAttestation {
+    pub hash: H256,
+    pub number: u64,
+    pub receipts_root: H256,
+}
+
+/// An ERC-20 token (or ETH, with zero address) which could be deposited into ChainX.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthTokenInfo {
+    /// The asset in ChainX.
+    pub asset_id: AssetId,
+    /// The decimals of the token on ethereum.
+    pub decimals: u8,
+}
+
+/// A log in the ethereum receipt.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug)]
+pub struct EthLog {
+    pub address: H160,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
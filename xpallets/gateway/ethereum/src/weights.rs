@@ -0,0 +1,110 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_gateway_ethereum
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! DATE: 2020-11-20, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("benchmarks"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/chainx
+// benchmark
+// --chain=benchmarks
+// --steps=50
+// --repeat=20
+// --pallet=xpallet_gateway_ethereum
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./xpallets/gateway/ethereum/src/weights.rs
+// --template=./scripts/xpallet-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_gateway_ethereum.
+pub trait WeightInfo {
+    fn attest_header(u: u32) -> Weight;
+    fn push_deposit() -> Weight;
+    fn sign_withdrawal() -> Weight;
+    fn release_withdrawal() -> Weight;
+    fn register_token() -> Weight;
+    fn set_bridge_contract() -> Weight;
+}
+
+/// Weights for xpallet_gateway_ethereum using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn attest_header(u: u32) -> Weight {
+        (98_241_000 as Weight)
+            .saturating_add((6_873_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+    fn push_deposit() -> Weight {
+        (312_508_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn sign_withdrawal() -> Weight {
+        (387_115_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn release_withdrawal() -> Weight {
+        (291_736_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn register_token() -> Weight {
+        (38_564_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_bridge_contract() -> Weight {
+        (4_102_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn attest_header(u: u32) -> Weight {
+        (98_241_000 as Weight)
+            .saturating_add((6_873_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+    fn push_deposit() -> Weight {
+        (312_508_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn sign_withdrawal() -> Weight {
+        (387_115_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn release_withdrawal() -> Weight {
+        (291_736_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn register_token() -> Weight {
+        (38_564_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_bridge_contract() -> Weight {
+        (4_102_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::Encode;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::storage::{StorageMap, StorageValue};
+use frame_system::RawOrigin;
+use sp_core::{ed25519, H256};
+use sp_finality_grandpa::{AuthorityId, AuthoritySignature, KEY_TYPE};
+use sp_runtime::{traits::Header as HeaderT, SaturatedConversion};
+use sp_std::prelude::*;
+
+use chainx_primitives::AssetId;
+use xpallet_assets::Module as XAssets;
+use xpallet_gateway_records::Module as XGatewayRecords;
+
+use crate::{
+    precommit_payload,
+    proof::{events_storage_key, single_storage_proof},
+    AuthoritySet, BridgeAccount, BridgedTransfer, Call, Commit, GrandpaJustification,
+    ImportedHashes, ImportedHashesPointer, ImportedHeaders, Module, Precommit, SignedPrecommit,
+    Trait, TransferParser, HEADERS_TO_KEEP, MAX_BRIDGED_AUTHORITIES, MAX_VOTES_ANCESTRIES,
+};
+
+const ASSET_ID: AssetId = xp_protocol::X_DOT;
+const DOT: u128 = 10_000_000_000;
+
+/// Generate `count` authorities in the keystore with the equal weight.
+fn authority_set(count: u32) -> AuthoritySet {
+    AuthoritySet {
+        authorities: (0..count)
+            .map(|_| {
+                (
+                    AuthorityId::from(sp_io::crypto::ed25519_generate(KEY_TYPE, None)),
+                    1,
+                )
+            })
+            .collect(),
+        set_id: 0,
+    }
+}
+
+fn bridged_header<T: Trait>(number: u32, state_root: H256) -> T::BridgedHeader {
+    T::BridgedHeader::new(
+        number.into(),
+        Default::default(),
+        state_root,
+        Default::default(),
+        Default::default(),
+    )
+}
+
+fn child_header<T: Trait>(parent: &T::BridgedHeader) -> T::BridgedHeader {
+    T::BridgedHeader::new(
+        *parent.number() + 1u32.into(),
+        Default::default(),
+        Default::default(),
+        parent.hash(),
+        Default::default(),
+    )
+}
+
+benchmarks! {
+    _{ }
+
+    initialize {
+        let header = bridged_header::<T>(0, Default::default());
+        let hash = header.hash();
+    }: _(RawOrigin::Root, header, authority_set(1))
+    verify {
+        assert_eq!(Module::<T>::best_finalized(), hash);
+    }
+
+    submit_finality_proof {
+        let p in 1 .. MAX_BRIDGED_AUTHORITIES;
+        let a in 0 .. MAX_VOTES_ANCESTRIES;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let authority_set = authority_set(p);
+        let genesis = bridged_header::<T>(0, Default::default());
+        Module::<T>::initialize(RawOrigin::Root.into(), genesis.clone(), authority_set.clone()).unwrap();
+        // the oldest imported header would be pruned
+        ImportedHashesPointer::put(HEADERS_TO_KEEP - 1);
+        ImportedHashes::insert(HEADERS_TO_KEEP - 1, H256::repeat_byte(1));
+
+        // all the precommits are for the highest header of the votes ancestries
+        let header = child_header::<T>(&genesis);
+        let mut votes_ancestries = Vec::new();
+        let mut target = header.clone();
+        for _ in 0..a {
+            target = child_header::<T>(&target);
+            votes_ancestries.push(target.clone());
+        }
+        let precommit = Precommit {
+            target_hash: target.hash(),
+            target_number: *target.number(),
+        };
+        let payload = precommit_payload(&precommit, 1, authority_set.set_id);
+        let precommits = authority_set
+            .authorities
+            .iter()
+            .map(|(id, _)| {
+                let public: &ed25519::Public = id.as_ref();
+                let signature = sp_io::crypto::ed25519_sign(KEY_TYPE, public, &payload)
+                    .expect("the authority is generated in the keystore");
+                SignedPrecommit {
+                    precommit: precommit.clone(),
+                    signature: AuthoritySignature::from(signature),
+                    id: id.clone(),
+                }
+            })
+            .collect();
+        let hash = header.hash();
+        let justification = GrandpaJustification::<T::BridgedHeader> {
+            round: 1,
+            commit: Commit {
+                target_hash: hash,
+                target_number: *header.number(),
+                precommits,
+            },
+            votes_ancestries,
+        };
+    }: _(RawOrigin::Signed(caller), header, justification.encode())
+    verify {
+        assert_eq!(Module::<T>::best_finalized(), hash);
+    }
+
+    push_transfer {
+        let caller: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = whitelisted_caller();
+        let mut from = [0u8; 32];
+        from.copy_from_slice(&receiver.encode());
+        BridgeAccount::put([0xbb; 32]);
+
+        let events = T::TransferParser::transfer_events(BridgedTransfer {
+            from,
+            to: [0xbb; 32],
+            amount: DOT,
+        });
+        let (state_root, proof) = single_storage_proof(&events_storage_key(), &events);
+        let header = bridged_header::<T>(0, state_root);
+        let hash = header.hash();
+        ImportedHeaders::<T>::insert(&hash, header);
+    }: _(RawOrigin::Signed(caller), hash, 0, proof)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&receiver, &ASSET_ID), DOT.saturated_into());
+    }
+
+    release_withdrawal {
+        let caller: T::AccountId = whitelisted_caller();
+        let who: T::AccountId = whitelisted_caller();
+        BridgeAccount::put([0xbb; 32]);
+        XGatewayRecords::<T>::deposit(&who, ASSET_ID, DOT.saturated_into()).unwrap();
+        let id = XGatewayRecords::<T>::id();
+        XGatewayRecords::<T>::withdraw(&who, ASSET_ID, DOT.saturated_into(), vec![0xaa; 32], b"".to_vec().into()).unwrap();
+
+        let events = T::TransferParser::transfer_events(BridgedTransfer {
+            from: [0xbb; 32],
+            to: [0xaa; 32],
+            amount: DOT,
+        });
+        let (state_root, proof) = single_storage_proof(&events_storage_key(), &events);
+        let header = bridged_header::<T>(0, state_root);
+        let hash = header.hash();
+        ImportedHeaders::<T>::insert(&hash, header);
+    }: _(RawOrigin::Signed(caller), id, hash, 0, proof)
+    verify {
+        assert!(XGatewayRecords::<T>::pending_withdrawals(id).is_none());
+    }
+
+    set_bridge_account {
+        let account = [0xbb; 32];
+    }: _(RawOrigin::Root, account)
+    verify {
+        assert_eq!(Module::<T>::bridge_account(), account);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+    use sp_core::{testing::KeyStore, traits::KeystoreExt};
+
+    #[test]
+    fn test_benchmarks() {
+        let mut ext = ExtBuilder::default().build();
+        // the authorities are generated in the keystore
+        ext.register_extension(KeystoreExt(KeyStore::new()));
+        ext.execute_with(|| {
+            assert_ok!(test_benchmark_initialize::<Test>());
+            assert_ok!(test_benchmark_submit_finality_proof::<Test>());
+            assert_ok!(test_benchmark_push_transfer::<Test>());
+            assert_ok!(test_benchmark_release_withdrawal::<Test>());
+            assert_ok!(test_benchmark_set_bridge_account::<Test>());
+        });
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Verify the GRANDPA justification and the authority set change of the bridged chain.
+
+use codec::Encode;
+use sp_finality_grandpa::{AuthorityId, ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+use sp_runtime::{
+    generic::OpaqueDigestItemId,
+    traits::{Header as HeaderT, Zero},
+    DispatchResult, RuntimeAppPublic,
+};
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    prelude::*,
+};
+
+use xp_logging::error;
+
+use crate::types::{AuthoritySet, GrandpaJustification, Precommit};
+use crate::{Error, Trait};
+
+type BridgedHeaderOf<T> = <T as Trait>::BridgedHeader;
+type BridgedNumberOf<T> = <BridgedHeaderOf<T> as HeaderT>::Number;
+
+/// The signed message of a precommit, the same as `sp_finality_grandpa::localized_payload`
+/// with `finality_grandpa::Message::Precommit` (the index of the variant is 1).
+pub fn precommit_payload<Hash: Encode, Number: Encode>(
+    precommit: &Precommit<Hash, Number>,
+    round: u64,
+    set_id: u64,
+) -> Vec<u8> {
+    (1u8, precommit, round, set_id).encode()
+}
+
+/// Verify that the header is finalized by the authority set, the precommits must be signed by
+/// the authorities with more than 2/3 weight of the set.
+pub fn verify_justification<T: Trait>(
+    header: &BridgedHeaderOf<T>,
+    authority_set: &AuthoritySet,
+    justification: &GrandpaJustification<BridgedHeaderOf<T>>,
+) -> DispatchResult {
+    let hash = header.hash();
+    let commit = &justification.commit;
+    if commit.target_hash != hash || commit.target_number != *header.number() {
+        error!(
+            "[verify_justification] Justification target ({:?}, {:?}) not match the header {:?}",
+            commit.target_hash, commit.target_number, hash
+        );
+        return Err(Error::<T>::InvalidJustificationTarget.into());
+    }
+
+    let weights = authority_set
+        .authorities
+        .iter()
+        .cloned()
+        .collect::<BTreeMap<AuthorityId, u64>>();
+    let ancestry = justification
+        .votes_ancestries
+        .iter()
+        .map(|header| (header.hash(), header))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut voted = BTreeSet::new();
+    let mut voted_weight = 0u64;
+    for signed in commit.precommits.iter() {
+        let weight = weights
+            .get(&signed.id)
+            .ok_or(Error::<T>::UnknownAuthority)?;
+        if !voted.insert(signed.id.clone()) {
+            return Err(Error::<T>::DuplicateVote.into());
+        }
+        // the precommit must be for the target or the descendant of the target
+        let mut current = signed.precommit.target_hash;
+        let mut steps = 0;
+        while current != hash {
+            let ancestor = ancestry
+                .get(&current)
+                .filter(|_| steps < ancestry.len())
+                .ok_or(Error::<T>::InvalidAncestry)?;
+            current = *ancestor.parent_hash();
+            steps += 1;
+        }
+        let payload =
+            precommit_payload(&signed.precommit, justification.round, authority_set.set_id);
+        if !signed.id.verify(&payload, &signed.signature) {
+            error!(
+                "[verify_justification] Invalid signature of the authority {:?}",
+                signed.id
+            );
+            return Err(Error::<T>::InvalidSignature.into());
+        }
+        voted_weight = voted_weight.saturating_add(*weight);
+    }
+
+    let total_weight = authority_set
+        .authorities
+        .iter()
+        .fold(0u64, |acc, (_, weight)| acc.saturating_add(*weight));
+    // the same threshold as `finality_grandpa::VoterSet`
+    let faulty = total_weight.saturating_sub(1) / 3;
+    if voted_weight == 0 || voted_weight < total_weight - faulty {
+        error!(
+            "[verify_justification] Not enough weight, voted:{}, total:{}",
+            voted_weight, total_weight
+        );
+        return Err(Error::<T>::NotEnoughWeight.into());
+    }
+    Ok(())
+}
+
+/// Find the authority set change which is scheduled in the header. Only the standard change
+/// with zero delay could be applied by the light client.
+pub fn find_scheduled_change<T: Trait>(
+    header: &BridgedHeaderOf<T>,
+) -> Result<Option<ScheduledChange<BridgedNumberOf<T>>>, Error<T>> {
+    let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+    let change = header.digest().convert_first(|log| {
+        log.try_to(id)
+            .and_then(|log: ConsensusLog<BridgedNumberOf<T>>| match log {
+                ConsensusLog::ScheduledChange(change) => Some(change),
+                _ => None,
+            })
+    });
+    match change {
+        Some(change) if !change.delay.is_zero() => Err(Error::<T>::UnsupportedScheduledChange),
+        change => Ok(change),
+    }
+}
@@ -0,0 +1,481 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! this module is for dot-bridge
+//!
+//! A light client of the bridged chain with GRANDPA finality (e.g. Polkadot): the finalized
+//! headers are imported by the GRANDPA justification of the current authority set, and the
+//! transfers to the bridge account are verified by the storage proof of `System::Events`
+//! against the state root of the imported header.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod grandpa;
+mod proof;
+mod types;
+pub mod weights;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::Decode;
+use sp_core::H256;
+use sp_runtime::{
+    traits::{Header as HeaderT, SaturatedConversion},
+    DispatchError, DispatchResult,
+};
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchResultWithPostInfo, PostDispatchInfo},
+    ensure,
+    traits::Get,
+    weights::Pays,
+    Parameter,
+};
+use frame_system::{ensure_root, ensure_signed};
+use orml_utilities::with_transaction_result;
+
+use chainx_primitives::AssetId;
+use xp_logging::{error, info};
+use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_gateway_common::{traits::ChainGateway, types::AddrFormat};
+use xpallet_gateway_records::{WithdrawalRecordId, WithdrawalState};
+
+pub use self::grandpa::precommit_payload;
+pub use self::types::{
+    AuthoritySet, BridgedTransfer, Commit, EventRecord, EventRecordsParser, GrandpaJustification,
+    Precommit, SignedPrecommit, TransferEvent, TransferParser,
+};
+pub use self::weights::WeightInfo;
+
+/// The maximum number of the authorities of the bridged chain, which also bounds the
+/// precommits in a justification.
+pub const MAX_BRIDGED_AUTHORITIES: u32 = 1024;
+/// The maximum number of the headers in the votes ancestries of a justification.
+pub const MAX_VOTES_ANCESTRIES: u32 = 256;
+/// The number of the latest imported headers which are kept for the proofs of transfers.
+pub const HEADERS_TO_KEEP: u32 = 1024;
+
+pub trait Trait: xpallet_gateway_records::Trait {
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+    /// The header of the bridged chain, e.g. `generic::Header<u32, BlakeTwo256>` for Polkadot.
+    type BridgedHeader: Parameter + HeaderT<Hash = H256>;
+    /// Parse the transfers from the events of the bridged chain.
+    type TransferParser: TransferParser;
+    /// The decimals of the native token of the bridged chain, e.g. 10 for DOT.
+    type BridgedDecimals: Get<u8>;
+    type WeightInfo: WeightInfo;
+}
+
+decl_error! {
+    /// Error for the XBridge Polkadot module
+    pub enum Error for Module<T: Trait> {
+        /// the light client is not initialized
+        NotInitialized,
+        /// the authority set is larger than `MAX_BRIDGED_AUTHORITIES`
+        TooManyAuthorities,
+        /// the precommits or the votes ancestries of the justification exceed the limit
+        TooLargeJustification,
+        /// the header is not higher than the best finalized header
+        OldHeader,
+        /// can not decode the justification
+        InvalidJustification,
+        /// the target of the justification is not the header
+        InvalidJustificationTarget,
+        /// the precommit is signed by an unknown authority
+        UnknownAuthority,
+        /// duplicate precommit of the same authority
+        DuplicateVote,
+        /// the precommit target is not a descendant of the justification target
+        InvalidAncestry,
+        /// invalid signature of the precommit
+        InvalidSignature,
+        /// the weight of the precommits does not reach the threshold
+        NotEnoughWeight,
+        /// only the scheduled authority set change with zero delay is supported
+        UnsupportedScheduledChange,
+        /// the header is not imported
+        UnknownHeader,
+        /// invalid storage proof of the events
+        InvalidStorageProof,
+        /// the event is not a transfer
+        NotTransfer,
+        /// the transfer is not to the bridge account
+        NotBridgeAccount,
+        /// the transfer has been processed
+        ReplayedTransfer,
+        /// the sender of the transfer could not be decoded into an account
+        InvalidRecipient,
+        /// invalid account on the bridged chain
+        InvalidAddress,
+        /// the transfer is not from the bridge account
+        NotFromBridgeAccount,
+        /// the withdrawal is not pending
+        NotPendingWithdrawal,
+        /// the recipient or amount of the transfer does not match the withdrawal
+        WithdrawalMismatch,
+        /// the amount overflows after converting the decimals
+        AmountOverflow,
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>,
+    {
+        /// A finalized header of the bridged chain was imported. [block_hash]
+        HeaderImported(H256),
+        /// The authority set of the bridged chain was changed. [set_id]
+        AuthoritySetChanged(u64),
+        /// A transfer to the bridge account was minted. [block_hash, event_index, who, amount]
+        Deposited(H256, u32, AccountId, Balance),
+        /// A withdrawal was paid by the bridge account and released. [id, block_hash, event_index]
+        Released(WithdrawalRecordId, H256, u32),
+    }
+);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XGatewayPolkadot {
+        /// the current GRANDPA authority set of the bridged chain
+        pub Authorities get(fn authority_set): AuthoritySet;
+        /// the best finalized header of the bridged chain
+        pub BestFinalized get(fn best_finalized): H256;
+        /// block hash => the finalized header
+        pub ImportedHeaders get(fn imported_headers):
+            map hasher(identity) H256 => Option<T::BridgedHeader>;
+        /// ring buffer of the hashes of the latest `HEADERS_TO_KEEP` imported headers
+        pub ImportedHashes get(fn imported_hashes): map hasher(twox_64_concat) u32 => Option<H256>;
+        /// the slot in `ImportedHashes` for the next imported header
+        pub ImportedHashesPointer get(fn imported_hashes_pointer): u32;
+        /// the account on the bridged chain which receives the deposits
+        pub BridgeAccount get(fn bridge_account): [u8; 32];
+        /// (block hash, event index) => whether the transfer to or from the bridge account
+        /// has been processed
+        pub ProcessedTransfers get(fn processed_transfers):
+            double_map hasher(identity) H256, hasher(twox_64_concat) u32 => bool;
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        /// Initialize the light client by a trusted finalized header and the authority set
+        /// which would finalize the next headers.
+        #[weight = <T as Trait>::WeightInfo::initialize()]
+        pub fn initialize(origin, header: T::BridgedHeader, authority_set: AuthoritySet) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                authority_set.authorities.len() <= MAX_BRIDGED_AUTHORITIES as usize,
+                Error::<T>::TooManyAuthorities
+            );
+            let hash = header.hash();
+            info!(
+                "[initialize] Initialize from header:{:?}, authority set:{:?}",
+                hash, authority_set
+            );
+            Self::import_header(hash, header);
+            BestFinalized::put(hash);
+            Authorities::put(authority_set);
+            Ok(())
+        }
+
+        /// Import a finalized header by the GRANDPA justification. The header which schedules an
+        /// authority set change must be imported, or the following justifications could not be
+        /// verified.
+        ///
+        /// The worst case of the justification is charged first, and refunded by the actual
+        /// number of the precommits and the votes ancestries.
+        #[weight = <T as Trait>::WeightInfo::submit_finality_proof(
+            MAX_BRIDGED_AUTHORITIES,
+            MAX_VOTES_ANCESTRIES
+        )]
+        pub fn submit_finality_proof(
+            origin,
+            header: T::BridgedHeader,
+            justification: Vec<u8>
+        ) -> DispatchResultWithPostInfo {
+            let _from = ensure_signed(origin)?;
+            let (precommits, ancestries) = Self::apply_finality_proof(header, justification)?;
+            Ok(PostDispatchInfo {
+                actual_weight: Some(<T as Trait>::WeightInfo::submit_finality_proof(
+                    precommits,
+                    ancestries,
+                )),
+                pays_fee: Pays::Yes,
+            })
+        }
+
+        /// Relay a transfer to the bridge account, mint the asset for the account with the same
+        /// public key as the sender. `proof` is the storage proof of `System::Events`.
+        #[weight = <T as Trait>::WeightInfo::push_transfer()]
+        pub fn push_transfer(origin, block_hash: H256, event_index: u32, proof: Vec<Vec<u8>>) -> DispatchResult {
+            let _from = ensure_signed(origin)?;
+            Self::apply_push_transfer(block_hash, event_index, proof)
+        }
+
+        /// Relay the transfer from the bridge account which pays the withdrawal `id`, release
+        /// the withdrawal. `proof` is the storage proof of `System::Events`.
+        #[weight = <T as Trait>::WeightInfo::release_withdrawal()]
+        pub fn release_withdrawal(
+            origin,
+            id: WithdrawalRecordId,
+            block_hash: H256,
+            event_index: u32,
+            proof: Vec<Vec<u8>>
+        ) -> DispatchResult {
+            let _from = ensure_signed(origin)?;
+            Self::apply_release_withdrawal(id, block_hash, event_index, proof)
+        }
+
+        /// Set the bridge account on the bridged chain.
+        #[weight = <T as Trait>::WeightInfo::set_bridge_account()]
+        pub fn set_bridge_account(origin, account: [u8; 32]) -> DispatchResult {
+            ensure_root(origin)?;
+            BridgeAccount::put(account);
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Import the finalized header, return the number of the precommits and the votes
+    /// ancestries in the justification.
+    fn apply_finality_proof(
+        header: T::BridgedHeader,
+        justification: Vec<u8>,
+    ) -> Result<(u32, u32), DispatchError> {
+        let best =
+            Self::imported_headers(Self::best_finalized()).ok_or(Error::<T>::NotInitialized)?;
+        ensure!(header.number() > best.number(), Error::<T>::OldHeader);
+
+        let justification =
+            GrandpaJustification::<T::BridgedHeader>::decode(&mut justification.as_slice())
+                .map_err(|_| Error::<T>::InvalidJustification)?;
+        let precommits = justification.commit.precommits.len() as u32;
+        let ancestries = justification.votes_ancestries.len() as u32;
+        if precommits > MAX_BRIDGED_AUTHORITIES || ancestries > MAX_VOTES_ANCESTRIES {
+            error!(
+                "[apply_finality_proof] Too large justification, precommits:{}, ancestries:{}",
+                precommits, ancestries
+            );
+            return Err(Error::<T>::TooLargeJustification.into());
+        }
+        let authority_set = Self::authority_set();
+        grandpa::verify_justification::<T>(&header, &authority_set, &justification)?;
+        let change = grandpa::find_scheduled_change::<T>(&header)?;
+        if let Some(ref change) = change {
+            ensure!(
+                change.next_authorities.len() <= MAX_BRIDGED_AUTHORITIES as usize,
+                Error::<T>::TooManyAuthorities
+            );
+        }
+
+        let hash = header.hash();
+        info!(
+            "[apply_finality_proof] Import header:{:?}, number:{:?}",
+            hash,
+            header.number()
+        );
+        Self::import_header(hash, header);
+        BestFinalized::put(hash);
+        Self::deposit_event(Event::<T>::HeaderImported(hash));
+
+        if let Some(change) = change {
+            let set_id = authority_set.set_id + 1;
+            info!(
+                "[apply_finality_proof] Authority set changed, set id:{}, authorities:{:?}",
+                set_id, change.next_authorities
+            );
+            Authorities::put(AuthoritySet {
+                authorities: change.next_authorities,
+                set_id,
+            });
+            Self::deposit_event(Event::<T>::AuthoritySetChanged(set_id));
+        }
+        Ok((precommits, ancestries))
+    }
+
+    /// Insert the imported header, and prune the oldest one out of the latest `HEADERS_TO_KEEP`
+    /// headers together with its processed transfers.
+    fn import_header(hash: H256, header: T::BridgedHeader) {
+        let index = Self::imported_hashes_pointer();
+        if let Some(pruned) = ImportedHashes::get(index) {
+            ImportedHeaders::<T>::remove(&pruned);
+            ProcessedTransfers::remove_prefix(&pruned);
+        }
+        ImportedHashes::insert(index, hash);
+        ImportedHashesPointer::put((index + 1) % HEADERS_TO_KEEP);
+        ImportedHeaders::<T>::insert(&hash, header);
+    }
+
+    /// Return the unprocessed transfer in the imported header by the storage proof of events.
+    fn verified_transfer(
+        block_hash: H256,
+        event_index: u32,
+        proof: Vec<Vec<u8>>,
+    ) -> Result<BridgedTransfer, DispatchError> {
+        let header = Self::imported_headers(&block_hash).ok_or(Error::<T>::UnknownHeader)?;
+        ensure!(
+            !Self::processed_transfers(&block_hash, event_index),
+            Error::<T>::ReplayedTransfer
+        );
+
+        let events =
+            proof::read_storage_proof(*header.state_root(), proof, &proof::events_storage_key())
+                .ok_or(Error::<T>::InvalidStorageProof)?;
+        let transfer =
+            T::TransferParser::transfer(&events, event_index).ok_or(Error::<T>::NotTransfer)?;
+        Ok(transfer)
+    }
+
+    fn asset_decimals() -> Result<u8, DispatchError> {
+        Ok(xpallet_assets_registrar::Module::<T>::get_asset_info(&xp_protocol::X_DOT)?.decimals())
+    }
+
+    fn apply_push_transfer(
+        block_hash: H256,
+        event_index: u32,
+        proof: Vec<Vec<u8>>,
+    ) -> DispatchResult {
+        let transfer = Self::verified_transfer(block_hash, event_index, proof)?;
+        if transfer.to != Self::bridge_account() {
+            error!(
+                "[apply_push_transfer] Transfer to {:?}, not the bridge account",
+                transfer.to
+            );
+            return Err(Error::<T>::NotBridgeAccount.into());
+        }
+        let who = T::AccountId::decode(&mut &transfer.from[..])
+            .map_err(|_| Error::<T>::InvalidRecipient)?;
+        let balance = to_asset_amount(
+            transfer.amount,
+            T::BridgedDecimals::get(),
+            Self::asset_decimals()?,
+        )
+        .ok_or(Error::<T>::AmountOverflow)?;
+        let balance: BalanceOf<T> = balance.saturated_into();
+
+        with_transaction_result(|| {
+            xpallet_gateway_records::Module::<T>::deposit(&who, xp_protocol::X_DOT, balance)?;
+            ProcessedTransfers::insert(&block_hash, event_index, true);
+            info!(
+                "[apply_push_transfer] Deposit, block:{:?}, event:{}, who:{:?}, balance:{:?}",
+                block_hash, event_index, who, balance
+            );
+            Self::deposit_event(Event::<T>::Deposited(block_hash, event_index, who, balance));
+            Ok(())
+        })
+    }
+
+    fn apply_release_withdrawal(
+        id: WithdrawalRecordId,
+        block_hash: H256,
+        event_index: u32,
+        proof: Vec<Vec<u8>>,
+    ) -> DispatchResult {
+        let transfer = Self::verified_transfer(block_hash, event_index, proof)?;
+        ensure!(
+            transfer.from == Self::bridge_account(),
+            Error::<T>::NotFromBridgeAccount
+        );
+
+        let record = xpallet_gateway_records::Module::<T>::pending_withdrawals(id)
+            .filter(|record| record.asset_id() == xp_protocol::X_DOT)
+            .ok_or(Error::<T>::NotPendingWithdrawal)?;
+        let amount = to_bridged_amount(
+            record.balance().saturated_into(),
+            T::BridgedDecimals::get(),
+            Self::asset_decimals()?,
+        )
+        .ok_or(Error::<T>::AmountOverflow)?;
+        if record.addr()[..] != transfer.to[..] || amount != transfer.amount {
+            error!(
+                "[apply_release_withdrawal] Withdrawal:{}, transfer:{:?}, expected amount:{}",
+                id, transfer, amount
+            );
+            return Err(Error::<T>::WithdrawalMismatch.into());
+        }
+
+        with_transaction_result(|| {
+            if xpallet_gateway_records::Module::<T>::state_of(id) == Some(WithdrawalState::Applying)
+            {
+                xpallet_gateway_records::Module::<T>::process_withdrawal(id, Chain::Polkadot)?;
+            }
+            xpallet_gateway_records::Module::<T>::finish_withdrawal(id, Some(Chain::Polkadot))?;
+            ProcessedTransfers::insert(&block_hash, event_index, true);
+            info!(
+                "[apply_release_withdrawal] Release withdrawal:{}, block:{:?}, event:{}",
+                id, block_hash, event_index
+            );
+            Self::deposit_event(Event::<T>::Released(id, block_hash, event_index));
+            Ok(())
+        })
+    }
+}
+
+/// Convert the amount on the bridged chain to the asset amount in ChainX, the dust is dropped.
+fn to_asset_amount(amount: u128, bridged_decimals: u8, asset_decimals: u8) -> Option<u128> {
+    if bridged_decimals >= asset_decimals {
+        Some(amount / 10u128.checked_pow(u32::from(bridged_decimals - asset_decimals))?)
+    } else {
+        amount.checked_mul(10u128.checked_pow(u32::from(asset_decimals - bridged_decimals))?)
+    }
+}
+
+/// Convert the asset amount in ChainX to the amount on the bridged chain.
+fn to_bridged_amount(balance: u128, bridged_decimals: u8, asset_decimals: u8) -> Option<u128> {
+    if bridged_decimals >= asset_decimals {
+        balance.checked_mul(10u128.checked_pow(u32::from(bridged_decimals - asset_decimals))?)
+    } else {
+        Some(balance / 10u128.checked_pow(u32::from(asset_decimals - bridged_decimals))?)
+    }
+}
+
+impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
+    const ASSET_ID: AssetId = xp_protocol::X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        // the addr is the raw 32 bytes public key of the account on the bridged chain
+        ensure!(addr.len() == 32, Error::<T>::InvalidAddress);
+        ensure!(addr.iter().any(|b| *b != 0), Error::<T>::InvalidAddress);
+        // do not allow withdraw to the bridge account
+        ensure!(
+            addr != &Self::bridge_account()[..],
+            Error::<T>::InvalidAddress
+        );
+        Ok(())
+    }
+}
+
+impl<T: Trait> ChainGateway<T::AccountId, BalanceOf<T>> for Module<T> {
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+
+    fn addr_format() -> AddrFormat {
+        AddrFormat::Hex
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        <Self as ChainT<_>>::withdrawal_limit(asset_id)
+    }
+
+    fn check_addr(addr: &[u8], ext: &[u8]) -> DispatchResult {
+        <Self as ChainT<_>>::check_addr(addr, ext)
+    }
+}
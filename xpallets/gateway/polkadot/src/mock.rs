@@ -0,0 +1,212 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::collections::BTreeMap;
+
+use codec::{Decode, Encode};
+use frame_support::{impl_outer_origin, parameter_types, sp_io, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    generic,
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32, Perbill,
+};
+
+use chainx_primitives::AssetId;
+use xp_assets_registrar::Chain;
+pub use xp_protocol::X_DOT;
+use xpallet_assets::AssetRestrictions;
+use xpallet_assets_registrar::AssetInfo;
+
+use crate::{BridgedTransfer, Error, EventRecordsParser, Module, Trait, TransferEvent};
+
+/// The AccountId alias in this test module.
+pub(crate) type AccountId = AccountId32;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+pub(crate) type Amount = i128;
+
+/// The header of the bridged chain.
+pub(crate) type BridgedHeader = generic::Header<u32, BlakeTwo256>;
+
+impl_outer_origin! {
+    pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = ();
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 0;
+}
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const BridgedDecimals: u8 = 10;
+}
+
+// assets
+parameter_types! {
+    pub const ChainXAssetId: AssetId = 0;
+}
+
+impl xpallet_assets_registrar::Trait for Test {
+    type Event = ();
+    type NativeAssetId = ChainXAssetId;
+    type RegistrarHandler = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_assets::Trait for Test {
+    type Event = ();
+    type Currency = Balances;
+    type Amount = Amount;
+    type TreasuryAccount = ();
+    type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
+    type OnAssetChanged = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_gateway_records::Trait for Test {
+    type Event = ();
+    type GuardianOrigin = EnsureRoot<AccountId>;
+    type WeightInfo = ();
+}
+
+/// The runtime event of the bridged chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Debug)]
+pub enum BridgedEvent {
+    Remark(Vec<u8>),
+    Transfer([u8; 32], [u8; 32], u128),
+}
+
+impl TransferEvent for BridgedEvent {
+    fn transfer(&self) -> Option<BridgedTransfer> {
+        match self {
+            BridgedEvent::Transfer(from, to, amount) => Some(BridgedTransfer {
+                from: *from,
+                to: *to,
+                amount: *amount,
+            }),
+            _ => None,
+        }
+    }
+
+    fn from_transfer(transfer: BridgedTransfer) -> Self {
+        BridgedEvent::Transfer(transfer.from, transfer.to, transfer.amount)
+    }
+}
+
+impl Trait for Test {
+    type Event = ();
+    type BridgedHeader = BridgedHeader;
+    type TransferParser = EventRecordsParser<BridgedEvent>;
+    type BridgedDecimals = BridgedDecimals;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type XAssets = xpallet_assets::Module<Test>;
+pub type XGatewayRecords = xpallet_gateway_records::Module<Test>;
+pub type XGatewayPolkadot = Module<Test>;
+pub type XGatewayPolkadotErr = Error<Test>;
+
+pub fn alice() -> AccountId {
+    AccountId32::new([1; 32])
+}
+pub fn bob() -> AccountId {
+    AccountId32::new([2; 32])
+}
+
+fn dot() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        X_DOT,
+        AssetInfo::new::<Test>(
+            b"X-DOT".to_vec(),
+            b"X-DOT".to_vec(),
+            Chain::Polkadot,
+            10,
+            b"ChainX's cross-chain Polkadot".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self
+    }
+}
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        let (asset_id, info, restrictions) = dot();
+        let _ = xpallet_assets_registrar::GenesisConfig {
+            assets: vec![(asset_id, info, true, true)],
+        }
+        .assimilate_storage::<Test>(&mut storage);
+
+        let _ = xpallet_assets::GenesisConfig::<Test> {
+            assets_restrictions: vec![(asset_id, restrictions)],
+            endowed: BTreeMap::new(),
+        }
+        .assimilate_storage(&mut storage);
+
+        sp_io::TestExternalities::new(storage)
+    }
+    pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
+        let mut ext = self.build();
+        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(test);
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Read the storage of the bridged chain by the storage proof against the state root.
+
+use sp_core::H256;
+use sp_io::hashing::twox_128;
+use sp_runtime::traits::BlakeTwo256;
+use sp_std::prelude::*;
+use sp_trie::{read_trie_value, HashDBT, Layout, StorageProof, EMPTY_PREFIX};
+
+/// The storage key of `System::Events`.
+pub fn events_storage_key() -> Vec<u8> {
+    let mut key = twox_128(b"System").to_vec();
+    key.extend_from_slice(&twox_128(b"Events"));
+    key
+}
+
+/// Read the value of `key` from the storage proof, return `None` if the proof is invalid or the
+/// value does not exist.
+pub fn read_storage_proof(state_root: H256, proof: Vec<Vec<u8>>, key: &[u8]) -> Option<Vec<u8>> {
+    let db = StorageProof::new(proof).into_memory_db::<BlakeTwo256>();
+    if !db.contains(&state_root, EMPTY_PREFIX) {
+        return None;
+    }
+    read_trie_value::<Layout<BlakeTwo256>, _>(&db, &state_root, key)
+        .ok()
+        .flatten()
+}
+
+/// Build the storage proof of a trie which only contains the `key`.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn single_storage_proof(key: &[u8], value: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    use sp_trie::{MemoryDB, TrieDBMut, TrieMut};
+
+    let mut db = MemoryDB::<BlakeTwo256>::default();
+    let mut root = H256::default();
+    {
+        let mut trie = TrieDBMut::<Layout<BlakeTwo256>>::new(&mut db, &mut root);
+        trie.insert(key, value)
+            .expect("insert into the memory db; qed");
+    }
+    let proof = db.drain().into_iter().map(|(_, (node, _))| node).collect();
+    (root, proof)
+}
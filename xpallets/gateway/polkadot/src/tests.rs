@@ -0,0 +1,524 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{StorageDoubleMap, StorageValue},
+};
+use frame_system::RawOrigin;
+use sp_core::{ed25519, Pair, H256};
+use sp_finality_grandpa::{AuthorityId, ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+use sp_runtime::{
+    generic::{Digest, DigestItem},
+    traits::Header as HeaderT,
+};
+
+use xpallet_gateway_records::WithdrawalState;
+
+use crate::mock::*;
+use crate::proof::{events_storage_key, single_storage_proof};
+use crate::{
+    precommit_payload, AuthoritySet, BridgedTransfer, Commit, EventRecordsParser,
+    GrandpaJustification, ImportedHashesPointer, Precommit, ProcessedTransfers, SignedPrecommit,
+    TransferParser, HEADERS_TO_KEEP, MAX_VOTES_ANCESTRIES,
+};
+
+const DOT: Balance = 10_000_000_000;
+const BRIDGE: [u8; 32] = [0xbb; 32];
+
+fn authorities() -> Vec<ed25519::Pair> {
+    (1..=4u8)
+        .map(|i| ed25519::Pair::from_seed(&[i; 32]))
+        .collect()
+}
+
+fn authority_set(set_id: u64) -> AuthoritySet {
+    AuthoritySet {
+        authorities: authorities()
+            .iter()
+            .map(|pair| (AuthorityId::from(pair.public()), 1))
+            .collect(),
+        set_id,
+    }
+}
+
+fn header(number: u32, parent: &BridgedHeader) -> BridgedHeader {
+    BridgedHeader::new(
+        number,
+        Default::default(),
+        Default::default(),
+        parent.hash(),
+        Default::default(),
+    )
+}
+
+fn justification(
+    target: &BridgedHeader,
+    precommit_target: &BridgedHeader,
+    votes_ancestries: Vec<BridgedHeader>,
+    signers: &[ed25519::Pair],
+    set_id: u64,
+) -> Vec<u8> {
+    let round = 1;
+    let precommit = Precommit {
+        target_hash: precommit_target.hash(),
+        target_number: *precommit_target.number(),
+    };
+    let precommits = signers
+        .iter()
+        .map(|pair| SignedPrecommit {
+            precommit: precommit.clone(),
+            signature: pair
+                .sign(&precommit_payload(&precommit, round, set_id))
+                .into(),
+            id: pair.public().into(),
+        })
+        .collect();
+    GrandpaJustification::<BridgedHeader> {
+        round,
+        commit: Commit {
+            target_hash: target.hash(),
+            target_number: *target.number(),
+            precommits,
+        },
+        votes_ancestries,
+    }
+    .encode()
+}
+
+fn genesis() -> BridgedHeader {
+    BridgedHeader::new(
+        0,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    )
+}
+
+fn initialize(header: BridgedHeader) {
+    assert_ok!(XGatewayPolkadot::initialize(
+        RawOrigin::Root.into(),
+        header,
+        authority_set(0)
+    ));
+}
+
+#[test]
+fn test_submit_finality_proof() {
+    ExtBuilder::default().build_and_execute(|| {
+        let genesis = genesis();
+        let header1 = header(1, &genesis);
+        let signers = authorities();
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header1.clone(),
+                justification(&header1, &header1, vec![], &signers[..3], 0)
+            ),
+            XGatewayPolkadotErr::NotInitialized
+        );
+        initialize(genesis.clone());
+
+        // 2 of 4 authorities is not enough
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header1.clone(),
+                justification(&header1, &header1, vec![], &signers[..2], 0)
+            ),
+            XGatewayPolkadotErr::NotEnoughWeight
+        );
+        // signed for a wrong set id
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header1.clone(),
+                justification(&header1, &header1, vec![], &signers[..3], 1)
+            ),
+            XGatewayPolkadotErr::InvalidSignature
+        );
+        // duplicate precommits of the same authority
+        let duplicate = vec![signers[0].clone(), signers[0].clone(), signers[1].clone()];
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header1.clone(),
+                justification(&header1, &header1, vec![], &duplicate, 0)
+            ),
+            XGatewayPolkadotErr::DuplicateVote
+        );
+        // unknown authority
+        let unknown = vec![ed25519::Pair::from_seed(&[9; 32])];
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header1.clone(),
+                justification(&header1, &header1, vec![], &unknown, 0)
+            ),
+            XGatewayPolkadotErr::UnknownAuthority
+        );
+
+        assert_ok!(XGatewayPolkadot::submit_finality_proof(
+            RawOrigin::Signed(alice()).into(),
+            header1.clone(),
+            justification(&header1, &header1, vec![], &signers[..3], 0)
+        ));
+        assert_eq!(XGatewayPolkadot::best_finalized(), header1.hash());
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header1.clone(),
+                justification(&header1, &header1, vec![], &signers[..3], 0)
+            ),
+            XGatewayPolkadotErr::OldHeader
+        );
+
+        // the precommits are for the descendant of the target
+        let header2 = header(2, &header1);
+        let header3 = header(3, &header2);
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header2.clone(),
+                justification(&header2, &header3, vec![], &signers[..3], 0)
+            ),
+            XGatewayPolkadotErr::InvalidAncestry
+        );
+        assert_ok!(XGatewayPolkadot::submit_finality_proof(
+            RawOrigin::Signed(alice()).into(),
+            header2.clone(),
+            justification(&header2, &header3, vec![header3.clone()], &signers[..3], 0)
+        ));
+        assert_eq!(XGatewayPolkadot::best_finalized(), header2.hash());
+    });
+}
+
+#[test]
+fn test_too_large_justification() {
+    ExtBuilder::default().build_and_execute(|| {
+        let genesis = genesis();
+        initialize(genesis.clone());
+
+        let header1 = header(1, &genesis);
+        let mut votes_ancestries = vec![];
+        let mut target = header1.clone();
+        for number in 2..=MAX_VOTES_ANCESTRIES + 2 {
+            target = header(number, &target);
+            votes_ancestries.push(target.clone());
+        }
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header1.clone(),
+                justification(&header1, &target, votes_ancestries, &authorities()[..3], 0)
+            ),
+            XGatewayPolkadotErr::TooLargeJustification
+        );
+    });
+}
+
+#[test]
+fn test_prune_imported_headers() {
+    ExtBuilder::default().build_and_execute(|| {
+        let genesis = genesis();
+        initialize(genesis.clone());
+        assert_eq!(XGatewayPolkadot::imported_hashes(0), Some(genesis.hash()));
+        // the slot of genesis is the next but one
+        ImportedHashesPointer::put(HEADERS_TO_KEEP - 1);
+        ProcessedTransfers::insert(genesis.hash(), 0, true);
+
+        let header1 = header(1, &genesis);
+        assert_ok!(XGatewayPolkadot::submit_finality_proof(
+            RawOrigin::Signed(alice()).into(),
+            header1.clone(),
+            justification(&header1, &header1, vec![], &authorities()[..3], 0)
+        ));
+        assert!(XGatewayPolkadot::imported_headers(genesis.hash()).is_some());
+
+        let header2 = header(2, &header1);
+        assert_ok!(XGatewayPolkadot::submit_finality_proof(
+            RawOrigin::Signed(alice()).into(),
+            header2.clone(),
+            justification(&header2, &header2, vec![], &authorities()[..3], 0)
+        ));
+        assert!(XGatewayPolkadot::imported_headers(genesis.hash()).is_none());
+        assert!(!XGatewayPolkadot::processed_transfers(genesis.hash(), 0));
+        assert_eq!(XGatewayPolkadot::imported_hashes(0), Some(header2.hash()));
+        assert_eq!(XGatewayPolkadot::imported_hashes_pointer(), 1);
+        assert!(XGatewayPolkadot::imported_headers(header1.hash()).is_some());
+    });
+}
+
+#[test]
+fn test_authority_set_change() {
+    ExtBuilder::default().build_and_execute(|| {
+        let genesis = genesis();
+        initialize(genesis.clone());
+
+        let next = vec![ed25519::Pair::from_seed(&[5; 32])];
+        let change = ConsensusLog::<u32>::ScheduledChange(ScheduledChange {
+            next_authorities: vec![(AuthorityId::from(next[0].public()), 1)],
+            delay: 0,
+        });
+        let mut header1 = header(1, &genesis);
+        *header1.digest_mut() = Digest {
+            logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, change.encode())],
+        };
+        // the change is finalized by the current authority set
+        assert_ok!(XGatewayPolkadot::submit_finality_proof(
+            RawOrigin::Signed(alice()).into(),
+            header1.clone(),
+            justification(&header1, &header1, vec![], &authorities()[..3], 0)
+        ));
+        assert_eq!(XGatewayPolkadot::authority_set().set_id, 1);
+
+        let header2 = header(2, &header1);
+        assert_noop!(
+            XGatewayPolkadot::submit_finality_proof(
+                RawOrigin::Signed(alice()).into(),
+                header2.clone(),
+                justification(&header2, &header2, vec![], &authorities()[..3], 1)
+            ),
+            XGatewayPolkadotErr::UnknownAuthority
+        );
+        assert_ok!(XGatewayPolkadot::submit_finality_proof(
+            RawOrigin::Signed(alice()).into(),
+            header2.clone(),
+            justification(&header2, &header2, vec![], &next, 1)
+        ));
+    });
+}
+
+#[test]
+fn test_push_transfer() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayPolkadot::set_bridge_account(
+            RawOrigin::Root.into(),
+            BRIDGE
+        ));
+        let events = EventRecordsParser::<BridgedEvent>::transfer_events(BridgedTransfer {
+            from: bob().into(),
+            to: BRIDGE,
+            amount: DOT,
+        });
+        let (state_root, proof) = single_storage_proof(&events_storage_key(), &events);
+        let mut block = genesis();
+        block.set_state_root(state_root);
+        let hash = block.hash();
+
+        assert_noop!(
+            XGatewayPolkadot::push_transfer(
+                RawOrigin::Signed(alice()).into(),
+                hash,
+                0,
+                proof.clone()
+            ),
+            XGatewayPolkadotErr::UnknownHeader
+        );
+        initialize(block);
+
+        assert_noop!(
+            XGatewayPolkadot::push_transfer(
+                RawOrigin::Signed(alice()).into(),
+                hash,
+                1,
+                proof.clone()
+            ),
+            XGatewayPolkadotErr::NotTransfer
+        );
+        assert_noop!(
+            XGatewayPolkadot::push_transfer(
+                RawOrigin::Signed(alice()).into(),
+                hash,
+                0,
+                proof[1..].to_vec()
+            ),
+            XGatewayPolkadotErr::InvalidStorageProof
+        );
+
+        assert_ok!(XGatewayPolkadot::push_transfer(
+            RawOrigin::Signed(alice()).into(),
+            hash,
+            0,
+            proof.clone()
+        ));
+        assert_eq!(XAssets::usable_balance(&bob(), &X_DOT), DOT);
+        assert_noop!(
+            XGatewayPolkadot::push_transfer(RawOrigin::Signed(alice()).into(), hash, 0, proof),
+            XGatewayPolkadotErr::ReplayedTransfer
+        );
+    });
+}
+
+#[test]
+fn test_push_transfer_to_other_account() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayPolkadot::set_bridge_account(
+            RawOrigin::Root.into(),
+            BRIDGE
+        ));
+        let events = EventRecordsParser::<BridgedEvent>::transfer_events(BridgedTransfer {
+            from: bob().into(),
+            to: [0xcc; 32],
+            amount: DOT,
+        });
+        let (state_root, proof) = single_storage_proof(&events_storage_key(), &events);
+        let mut block = genesis();
+        block.set_state_root(state_root);
+        let hash = block.hash();
+        initialize(block);
+
+        assert_noop!(
+            XGatewayPolkadot::push_transfer(RawOrigin::Signed(alice()).into(), hash, 0, proof),
+            XGatewayPolkadotErr::NotBridgeAccount
+        );
+    });
+}
+
+fn transfer_block(transfer: BridgedTransfer) -> (H256, Vec<Vec<u8>>) {
+    let events = EventRecordsParser::<BridgedEvent>::transfer_events(transfer);
+    let (state_root, proof) = single_storage_proof(&events_storage_key(), &events);
+    let mut block = genesis();
+    block.set_state_root(state_root);
+    let hash = block.hash();
+    initialize(block);
+    (hash, proof)
+}
+
+#[test]
+fn test_release_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayPolkadot::set_bridge_account(
+            RawOrigin::Root.into(),
+            BRIDGE
+        ));
+        assert_ok!(XGatewayRecords::deposit(&bob(), X_DOT, DOT));
+        assert_ok!(XGatewayRecords::withdraw(
+            &bob(),
+            X_DOT,
+            DOT,
+            vec![0xaa; 32],
+            b"".to_vec().into()
+        ));
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Applying)
+        );
+
+        // the payout must be from the bridge account, to the withdrawal address, with the amount
+        let (hash, proof) = transfer_block(BridgedTransfer {
+            from: [0xcc; 32],
+            to: [0xaa; 32],
+            amount: DOT,
+        });
+        assert_noop!(
+            XGatewayPolkadot::release_withdrawal(
+                RawOrigin::Signed(alice()).into(),
+                0,
+                hash,
+                0,
+                proof
+            ),
+            XGatewayPolkadotErr::NotFromBridgeAccount
+        );
+        let (hash, proof) = transfer_block(BridgedTransfer {
+            from: BRIDGE,
+            to: [0xaa; 32],
+            amount: DOT - 1,
+        });
+        assert_noop!(
+            XGatewayPolkadot::release_withdrawal(
+                RawOrigin::Signed(alice()).into(),
+                0,
+                hash,
+                0,
+                proof
+            ),
+            XGatewayPolkadotErr::WithdrawalMismatch
+        );
+
+        let (hash, proof) = transfer_block(BridgedTransfer {
+            from: BRIDGE,
+            to: [0xaa; 32],
+            amount: DOT,
+        });
+        assert_noop!(
+            XGatewayPolkadot::release_withdrawal(
+                RawOrigin::Signed(alice()).into(),
+                1,
+                hash,
+                0,
+                proof.clone()
+            ),
+            XGatewayPolkadotErr::NotPendingWithdrawal
+        );
+        assert_ok!(XGatewayPolkadot::release_withdrawal(
+            RawOrigin::Signed(alice()).into(),
+            0,
+            hash,
+            0,
+            proof.clone()
+        ));
+        assert!(XGatewayRecords::pending_withdrawals(0).is_none());
+        assert_eq!(XAssets::total_issuance(&X_DOT), 0);
+        assert_noop!(
+            XGatewayPolkadot::release_withdrawal(
+                RawOrigin::Signed(alice()).into(),
+                0,
+                hash,
+                0,
+                proof
+            ),
+            XGatewayPolkadotErr::ReplayedTransfer
+        );
+    });
+}
+
+#[test]
+fn test_convert_decimals() {
+    use crate::{to_asset_amount, to_bridged_amount};
+    // DOT has 10 decimals, KSM has 12
+    assert_eq!(to_asset_amount(DOT, 10, 10), Some(DOT));
+    assert_eq!(
+        to_asset_amount(1_000_000_000_001, 12, 10),
+        Some(10_000_000_000)
+    );
+    assert_eq!(
+        to_bridged_amount(10_000_000_000, 12, 10),
+        Some(1_000_000_000_000)
+    );
+    assert_eq!(to_asset_amount(DOT, 8, 10), Some(DOT * 100));
+    assert_eq!(to_bridged_amount(DOT * 100, 8, 10), Some(DOT));
+    assert_eq!(to_asset_amount(u128::max_value(), 0, 10), None);
+}
+
+#[test]
+fn test_check_addr() {
+    ExtBuilder::default().build_and_execute(|| {
+        use xpallet_assets::ChainT;
+        assert_ok!(XGatewayPolkadot::set_bridge_account(
+            RawOrigin::Root.into(),
+            BRIDGE
+        ));
+        assert_ok!(XGatewayPolkadot::check_addr(&[0xaa; 32], b""));
+        assert!(XGatewayPolkadot::check_addr(&[0xaa; 20], b"").is_err());
+        assert!(XGatewayPolkadot::check_addr(&[0; 32], b"").is_err());
+        assert!(XGatewayPolkadot::check_addr(&BRIDGE, b"").is_err());
+    });
+}
+
+#[test]
+fn test_precommit_payload() {
+    // the same encoding as `finality_grandpa::Message::Precommit`
+    let precommit = Precommit {
+        target_hash: H256::repeat_byte(1),
+        target_number: 2u32,
+    };
+    let payload = precommit_payload(&precommit, 3, 4);
+    assert_eq!(payload[0], 1);
+    assert_eq!(&payload[1..33], &[1; 32][..]);
+    assert_eq!(&payload[33..37], &2u32.to_le_bytes()[..]);
+    assert_eq!(&payload[37..45], &3u64.to_le_bytes()[..]);
+    assert_eq!(&payload[45..], &4u64.to_le_bytes()[..]);
+}
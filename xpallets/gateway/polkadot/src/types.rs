@@ -0,0 +1,122 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use frame_system::Phase;
+use sp_core::H256;
+use sp_finality_grandpa::{AuthorityId, AuthorityList, AuthoritySignature, SetId};
+use sp_runtime::{traits::Header as HeaderT, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The GRANDPA authority set of the bridged chain.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AuthoritySet {
+    pub authorities: AuthorityList,
+    pub set_id: SetId,
+}
+
+/// A precommit of GRANDPA, the same encoding as `finality_grandpa::Precommit`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Precommit<Hash, Number> {
+    pub target_hash: Hash,
+    pub target_number: Number,
+}
+
+/// A signed precommit, the same encoding as `finality_grandpa::SignedPrecommit`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct SignedPrecommit<Hash, Number> {
+    pub precommit: Precommit<Hash, Number>,
+    pub signature: AuthoritySignature,
+    pub id: AuthorityId,
+}
+
+/// A commit of GRANDPA, the same encoding as `finality_grandpa::Commit`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Commit<Hash, Number> {
+    pub target_hash: Hash,
+    pub target_number: Number,
+    pub precommits: Vec<SignedPrecommit<Hash, Number>>,
+}
+
+/// The justification of a finalized block, the same encoding as the justification which is
+/// produced by `sc-finality-grandpa`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct GrandpaJustification<Header: HeaderT> {
+    pub round: u64,
+    pub commit: Commit<Header::Hash, Header::Number>,
+    pub votes_ancestries: Vec<Header>,
+}
+
+/// A transfer on the bridged chain.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct BridgedTransfer {
+    pub from: [u8; 32],
+    pub to: [u8; 32],
+    pub amount: u128,
+}
+
+/// Parse the transfer from the raw `System::Events` storage of the bridged chain.
+pub trait TransferParser {
+    /// Return the transfer if the event with `index` is a transfer.
+    fn transfer(events: &[u8], index: u32) -> Option<BridgedTransfer>;
+
+    /// Build the raw `System::Events` which only contains the transfer.
+    #[cfg(any(feature = "runtime-benchmarks", test))]
+    fn transfer_events(transfer: BridgedTransfer) -> Vec<u8>;
+}
+
+impl TransferParser for () {
+    fn transfer(_: &[u8], _: u32) -> Option<BridgedTransfer> {
+        None
+    }
+
+    #[cfg(any(feature = "runtime-benchmarks", test))]
+    fn transfer_events(_: BridgedTransfer) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+/// The runtime event of the bridged chain which may be a transfer.
+pub trait TransferEvent {
+    fn transfer(&self) -> Option<BridgedTransfer>;
+
+    #[cfg(any(feature = "runtime-benchmarks", test))]
+    fn from_transfer(transfer: BridgedTransfer) -> Self;
+}
+
+/// The same encoding as `frame_system::EventRecord`.
+#[derive(Encode, Decode)]
+pub struct EventRecord<E> {
+    pub phase: Phase,
+    pub event: E,
+    pub topics: Vec<H256>,
+}
+
+/// Parse the transfer by decoding the event records with the runtime event of the bridged chain.
+pub struct EventRecordsParser<E>(PhantomData<E>);
+
+impl<E: Encode + Decode + TransferEvent> TransferParser for EventRecordsParser<E> {
+    fn transfer(events: &[u8], index: u32) -> Option<BridgedTransfer> {
+        let records = Vec::<EventRecord<E>>::decode(&mut &events[..]).ok()?;
+        let record = records.get(index as usize)?;
+        // only the transfer which is applied by an extrinsic
+        match record.phase {
+            Phase::ApplyExtrinsic(_) => record.event.transfer(),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(feature = "runtime-benchmarks", test))]
+    fn transfer_events(transfer: BridgedTransfer) -> Vec<u8> {
+        vec![EventRecord {
+            phase: Phase::ApplyExtrinsic(1),
+            event: E::from_transfer(transfer),
+            topics: vec![],
+        }]
+        .encode()
+    }
+}
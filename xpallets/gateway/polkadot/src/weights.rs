@@ -0,0 +1,97 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_gateway_polkadot
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! DATE: 2020-11-20, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("benchmarks"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/chainx
+// benchmark
+// --chain=benchmarks
+// --steps=50
+// --repeat=20
+// --pallet=xpallet_gateway_polkadot
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./xpallets/gateway/polkadot/src/weights.rs
+// --template=./scripts/xpallet-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_gateway_polkadot.
+pub trait WeightInfo {
+    fn initialize() -> Weight;
+    fn submit_finality_proof(p: u32, a: u32) -> Weight;
+    fn push_transfer() -> Weight;
+    fn release_withdrawal() -> Weight;
+    fn set_bridge_account() -> Weight;
+}
+
+/// Weights for xpallet_gateway_polkadot using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn initialize() -> Weight {
+        (31_760_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn submit_finality_proof(p: u32, a: u32) -> Weight {
+        (96_417_000 as Weight)
+            .saturating_add((88_362_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add((1_925_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn push_transfer() -> Weight {
+        (247_905_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn release_withdrawal() -> Weight {
+        (292_417_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn set_bridge_account() -> Weight {
+        (4_037_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn initialize() -> Weight {
+        (31_760_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn submit_finality_proof(p: u32, a: u32) -> Weight {
+        (96_417_000 as Weight)
+            .saturating_add((88_362_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add((1_925_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn push_transfer() -> Weight {
+        (247_905_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn release_withdrawal() -> Weight {
+        (292_417_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn set_bridge_account() -> Weight {
+        (4_037_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
@@ -3,13 +3,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
 
-use sp_std::collections::btree_map::BTreeMap;
+use sp_std::{collections::btree_map::BTreeMap, prelude::Vec};
 
 use codec::Codec;
 
 pub use chainx_primitives::{AssetId, Decimals};
 pub use xpallet_assets::Chain;
-pub use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId, WithdrawalState};
+pub use xpallet_gateway_records::{
+    CompletedWithdrawal, Withdrawal, WithdrawalRecordId, WithdrawalState,
+};
 
 sp_api::decl_runtime_apis! {
     pub trait XGatewayRecordsApi<AccountId, Balance, BlockNumber>
@@ -21,5 +23,7 @@ sp_api::decl_runtime_apis! {
         fn withdrawal_list() -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
 
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
+
+        fn withdrawal_history(who: AccountId, page_index: u32, page_size: u32) -> Vec<CompletedWithdrawal<Balance, BlockNumber>>;
     }
 }
@@ -16,7 +16,7 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result};
 
 use xpallet_gateway_records_rpc_runtime_api::{
-    AssetId, Chain, Withdrawal, WithdrawalRecordId, WithdrawalState,
+    AssetId, Chain, CompletedWithdrawal, Withdrawal, WithdrawalRecordId, WithdrawalState,
     XGatewayRecordsApi as GatewayRecordsRuntimeApi,
 };
 
@@ -62,6 +62,16 @@ where
         chain: Chain,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<WithdrawalRecordId, RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>>;
+
+    /// Return the finished and canceled withdrawals of an account, from the latest one.
+    #[rpc(name = "xgatewayrecords_withdrawalHistory")]
+    fn withdrawal_history(
+        &self,
+        who: AccountId,
+        page_index: u32,
+        page_size: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Page<Vec<RpcCompletedWithdrawal<Balance, BlockNumber>>>>;
 }
 
 impl<C, Block, AccountId, Balance, BlockNumber>
@@ -127,6 +137,34 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn withdrawal_history(
+        &self,
+        who: AccountId,
+        page_index: u32,
+        page_size: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Page<Vec<RpcCompletedWithdrawal<Balance, BlockNumber>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let data = api
+            .withdrawal_history(&at, who, page_index, page_size)
+            .map(|history| history.into_iter().map(Into::into).collect())
+            .map_err(runtime_error_into_rpc_err)?;
+        Ok(Page {
+            page_index,
+            page_size,
+            data,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub page_index: u32,
+    pub page_size: u32,
+    pub data: T,
 }
 
 #[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -158,3 +196,37 @@ impl<AccountId, Balance: Display + FromStr, BlockNumber>
         }
     }
 }
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcCompletedWithdrawal<Balance: Display + FromStr, BlockNumber> {
+    pub id: WithdrawalRecordId,
+    pub asset_id: AssetId,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub balance: Balance,
+    pub addr: String,
+    pub ext: String,
+    pub state: WithdrawalState,
+    /// The hex-encoded hash of the transaction on the target chain, if any.
+    pub tx_hash: Option<String>,
+    pub transitions: Vec<(WithdrawalState, BlockNumber)>,
+}
+
+impl<Balance: Display + FromStr, BlockNumber> From<CompletedWithdrawal<Balance, BlockNumber>>
+    for RpcCompletedWithdrawal<Balance, BlockNumber>
+{
+    fn from(withdrawal: CompletedWithdrawal<Balance, BlockNumber>) -> Self {
+        Self {
+            id: withdrawal.id,
+            asset_id: withdrawal.asset_id,
+            balance: withdrawal.balance,
+            addr: String::from_utf8_lossy(withdrawal.addr.as_ref()).into_owned(),
+            ext: String::from_utf8_lossy(withdrawal.ext.as_ref()).into_owned(),
+            state: withdrawal.state,
+            tx_hash: withdrawal
+                .tx_hash
+                .map(|hash| format!("0x{}", hex::encode(hash))),
+            transitions: withdrawal.transitions,
+        }
+    }
+}
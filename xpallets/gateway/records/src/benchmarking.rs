@@ -19,12 +19,38 @@ fn deposit<T: Trait>(who: T::AccountId, amount: BalanceOf<T>) {
     );
 }
 
+fn delayed_withdrawal<T: Trait>(who: T::AccountId, amount: BalanceOf<T>) -> WithdrawalRecordId {
+    let delay = WithdrawalDelayOf::<T> {
+        threshold: 100.into(),
+        delay: 10.into(),
+    };
+    WithdrawalDelays::<T>::insert(ASSET_ID, delay);
+    let id = deposit_and_withdraw_impl::<T>(who, amount);
+    assert_eq!(
+        XGatewayRecords::<T>::state_of(id),
+        Some(WithdrawalState::Delayed)
+    );
+    id
+}
+
 fn deposit_and_withdraw<T: Trait>(who: T::AccountId, amount: BalanceOf<T>) {
+    let _ = deposit_and_withdraw_impl::<T>(who, amount);
+    assert_eq!(
+        XGatewayRecords::<T>::state_of(0),
+        Some(WithdrawalState::Applying)
+    );
+}
+
+fn deposit_and_withdraw_impl<T: Trait>(
+    who: T::AccountId,
+    amount: BalanceOf<T>,
+) -> WithdrawalRecordId {
     deposit::<T>(who.clone(), amount);
     let withdrawal = amount - 500.into();
     let addr = b"3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF".to_vec();
     let memo = b"memo".to_vec().into();
     let receiver_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(who);
+    let id = XGatewayRecords::<T>::id();
     XGatewayRecords::<T>::root_withdraw(
         RawOrigin::Root.into(),
         receiver_lookup,
@@ -34,10 +60,7 @@ fn deposit_and_withdraw<T: Trait>(who: T::AccountId, amount: BalanceOf<T>) {
         memo,
     )
     .unwrap();
-    assert_eq!(
-        XGatewayRecords::<T>::state_of(0),
-        Some(WithdrawalState::Applying)
-    );
+    id
 }
 
 benchmarks! {
@@ -89,6 +112,37 @@ benchmarks! {
     verify {
         assert_eq!(XGatewayRecords::<T>::state_of(0), None);
     }
+
+    freeze_withdrawal {
+        let receiver: T::AccountId = whitelisted_caller();
+        let amount: BalanceOf<T> = 1000.into();
+        let id = delayed_withdrawal::<T>(receiver, amount);
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(XGatewayRecords::<T>::frozen_withdrawals(id));
+    }
+
+    unfreeze_withdrawal {
+        let receiver: T::AccountId = whitelisted_caller();
+        let amount: BalanceOf<T> = 1000.into();
+        let id = delayed_withdrawal::<T>(receiver, amount);
+        XGatewayRecords::<T>::freeze_withdrawal(RawOrigin::Root.into(), id).unwrap();
+        // the delay has expired, the withdrawal matures at once
+        frame_system::Module::<T>::set_block_number(100.into());
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert_eq!(XGatewayRecords::<T>::state_of(id), Some(WithdrawalState::Applying));
+    }
+
+    set_withdrawal_delay {
+        let delay = WithdrawalDelayOf::<T> {
+            threshold: 100.into(),
+            delay: 10.into(),
+        };
+    }: _(RawOrigin::Root, ASSET_ID, Some(delay))
+    verify {
+        assert_eq!(XGatewayRecords::<T>::withdrawal_delay_of(ASSET_ID), Some(delay));
+    }
 }
 
 #[cfg(test)]
@@ -103,6 +157,9 @@ mod tests {
             assert_ok!(test_benchmark_root_deposit::<Test>());
             assert_ok!(test_benchmark_root_withdraw::<Test>());
             assert_ok!(test_benchmark_set_withdrawal_state::<Test>());
+            assert_ok!(test_benchmark_freeze_withdrawal::<Test>());
+            assert_ok!(test_benchmark_unfreeze_withdrawal::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_delay::<Test>());
         });
     }
 }
@@ -17,10 +17,13 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
-    ensure, IterableStorageMap,
+    ensure,
+    traits::{EnsureOrigin, Get},
+    weights::Weight,
+    IterableStorageMap,
 };
 use frame_system::ensure_root;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{Saturating, StaticLookup, Zero};
 
 use orml_utilities::with_transaction_result;
 
@@ -30,7 +33,10 @@ use xp_runtime::Memo;
 use xpallet_assets::{AssetType, BalanceOf, Chain};
 use xpallet_support::try_addr;
 
-pub use self::types::{Withdrawal, WithdrawalRecord, WithdrawalRecordId, WithdrawalState};
+pub use self::types::{
+    CompletedWithdrawal, Withdrawal, WithdrawalDelay, WithdrawalRecord, WithdrawalRecordId,
+    WithdrawalState,
+};
 pub use self::weights::WeightInfo;
 
 pub type WithdrawalRecordOf<T> = WithdrawalRecord<
@@ -39,6 +45,15 @@ pub type WithdrawalRecordOf<T> = WithdrawalRecord<
     <T as frame_system::Trait>::BlockNumber,
 >;
 
+pub type CompletedWithdrawalOf<T> =
+    CompletedWithdrawal<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+/// The maximum number of the completed withdrawals kept in the history of an account.
+pub const MAX_WITHDRAWAL_HISTORY: u32 = 32;
+
+pub type WithdrawalDelayOf<T> =
+    WithdrawalDelay<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
 /// The module's config trait.
 ///
 /// `frame_system::Trait` should always be included in our implied traits.
@@ -46,6 +61,9 @@ pub trait Trait: xpallet_assets::Trait {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
+    /// The origin which could freeze and unfreeze the delayed withdrawals besides root.
+    type GuardianOrigin: EnsureOrigin<Self::Origin>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -65,6 +83,12 @@ decl_error! {
         InvalidState,
         /// Meet unexpected chain
         UnexpectedChain,
+        /// WithdrawalRecord state not `Delayed`
+        NotDelayedState,
+        /// The delayed withdrawal has been frozen
+        AlreadyFrozen,
+        /// The delayed withdrawal is not frozen
+        NotFrozen,
     }
 }
 
@@ -73,6 +97,7 @@ decl_event!(
     pub enum Event<T>
     where
         <T as frame_system::Trait>::AccountId,
+        <T as frame_system::Trait>::BlockNumber,
         Balance = BalanceOf<T>,
         WithdrawalRecord = WithdrawalRecordOf<T>
     {
@@ -88,6 +113,14 @@ decl_event!(
         WithdrawalCanceled(WithdrawalRecordId, WithdrawalState),
         /// A withdrawal proposal was finished successfully. [withdrawal_id, withdrawal_state]
         WithdrawalFinished(WithdrawalRecordId, WithdrawalState),
+        /// A large withdrawal was time-locked until the block. [withdrawal_id, maturity]
+        WithdrawalDelayed(WithdrawalRecordId, BlockNumber),
+        /// A delayed withdrawal matured and could be processed by trustees. [withdrawal_id]
+        WithdrawalMatured(WithdrawalRecordId),
+        /// A delayed withdrawal was frozen by the guardian. [withdrawal_id]
+        WithdrawalFrozen(WithdrawalRecordId),
+        /// A frozen withdrawal was unfrozen by the guardian. [withdrawal_id]
+        WithdrawalUnfrozen(WithdrawalRecordId),
     }
 );
 
@@ -103,6 +136,47 @@ decl_storage! {
 
         /// The id of next withdrawal record.
         pub NextWithdrawalRecordId get(fn id): WithdrawalRecordId = 0;
+
+        /// The `Applying` withdrawals of the asset in the ascending order of the id.
+        pub ApplyingWithdrawalsOf get(fn applying_withdrawals_of):
+            map hasher(twox_64_concat) AssetId => Vec<WithdrawalRecordId>;
+
+        /// Whether the `Applying` withdrawals are indexed in `ApplyingWithdrawalsOf`.
+        IsApplyingWithdrawalsIndexed: bool;
+
+        /// The time-lock of the large withdrawals of an asset.
+        pub WithdrawalDelays get(fn withdrawal_delay_of):
+            map hasher(twox_64_concat) AssetId => Option<WithdrawalDelayOf<T>>;
+
+        /// The block in which a `Delayed` withdrawal matures into `Applying`.
+        pub WithdrawalMaturityOf get(fn withdrawal_maturity_of):
+            map hasher(twox_64_concat) WithdrawalRecordId => Option<T::BlockNumber>;
+
+        /// The delayed withdrawals which mature in the block.
+        pub MaturingWithdrawals get(fn maturing_withdrawals):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<WithdrawalRecordId>;
+
+        /// The delayed withdrawals frozen by the guardian, which would not mature until unfrozen.
+        pub FrozenWithdrawals get(fn frozen_withdrawals):
+            map hasher(twox_64_concat) WithdrawalRecordId => bool;
+
+        /// The state transitions and their blocks of a pending withdrawal.
+        pub WithdrawalTransitionsOf get(fn withdrawal_transitions_of):
+            map hasher(twox_64_concat) WithdrawalRecordId => Vec<(WithdrawalState, T::BlockNumber)>;
+
+        /// The hash of the transaction which paid a pending withdrawal on the target chain.
+        pub WithdrawalTxOf get(fn withdrawal_tx_of):
+            map hasher(twox_64_concat) WithdrawalRecordId => Option<Vec<u8>>;
+
+        /// The number of the completed withdrawals of an account.
+        pub CompletedWithdrawalCount get(fn completed_withdrawal_count):
+            map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// The latest `MAX_WITHDRAWAL_HISTORY` completed withdrawals of an account, the n-th
+        /// completed withdrawal is at the index of `n % MAX_WITHDRAWAL_HISTORY`.
+        pub CompletedWithdrawals get(fn completed_withdrawals):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u32
+            => Option<CompletedWithdrawalOf<T>>;
     }
 }
 
@@ -112,6 +186,25 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            if IsApplyingWithdrawalsIndexed::get() {
+                return T::DbWeight::get().reads(1);
+            }
+            let mut withdrawals = 0;
+            for (id, state) in WithdrawalStateOf::iter() {
+                if state == WithdrawalState::Applying {
+                    Self::index_applying_withdrawal(id, true);
+                }
+                withdrawals += 1;
+            }
+            IsApplyingWithdrawalsIndexed::put(true);
+            T::DbWeight::get().reads_writes(2 * withdrawals + 1, withdrawals + 1)
+        }
+
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::mature_withdrawals(now)
+        }
+
         /// Deposit asset token.
         ///
         /// This is a root-only operation.
@@ -171,6 +264,56 @@ decl_module! {
             }
             Ok(())
         }
+
+        /// Freeze a `Delayed` withdrawal for review, which would not mature until unfrozen.
+        ///
+        /// This is a guardian or root operation.
+        #[weight = <T as Trait>::WeightInfo::freeze_withdrawal()]
+        pub fn freeze_withdrawal(origin, #[compact] withdrawal_id: WithdrawalRecordId) -> DispatchResult {
+            T::GuardianOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            Self::ensure_delayed(withdrawal_id)?;
+            ensure!(!Self::frozen_withdrawals(withdrawal_id), Error::<T>::AlreadyFrozen);
+
+            FrozenWithdrawals::insert(withdrawal_id, true);
+            Self::deposit_event(Event::<T>::WithdrawalFrozen(withdrawal_id));
+            Ok(())
+        }
+
+        /// Unfreeze a frozen withdrawal, which matures at once if the delay has expired.
+        ///
+        /// This is a guardian or root operation.
+        #[weight = <T as Trait>::WeightInfo::unfreeze_withdrawal()]
+        pub fn unfreeze_withdrawal(origin, #[compact] withdrawal_id: WithdrawalRecordId) -> DispatchResult {
+            T::GuardianOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            Self::ensure_delayed(withdrawal_id)?;
+            ensure!(Self::frozen_withdrawals(withdrawal_id), Error::<T>::NotFrozen);
+
+            FrozenWithdrawals::remove(withdrawal_id);
+            Self::deposit_event(Event::<T>::WithdrawalUnfrozen(withdrawal_id));
+
+            let now = frame_system::Module::<T>::block_number();
+            if Self::withdrawal_maturity_of(withdrawal_id).map_or(true, |maturity| maturity <= now) {
+                Self::mature_withdrawal(withdrawal_id);
+            }
+            Ok(())
+        }
+
+        /// Set the time-lock of the large withdrawals of an asset, `None` to disable it.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_delay()]
+        pub fn set_withdrawal_delay(
+            origin,
+            #[compact] asset_id: AssetId,
+            delay: Option<WithdrawalDelayOf<T>>
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            match delay {
+                Some(delay) => WithdrawalDelays::<T>::insert(asset_id, delay),
+                None => WithdrawalDelays::<T>::remove(asset_id),
+            }
+            Ok(())
+        }
     }
 }
 
@@ -194,6 +337,15 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    fn ensure_delayed(id: WithdrawalRecordId) -> DispatchResult {
+        let state = Self::state_of(id).ok_or(Error::<T>::NotExisted)?;
+        ensure!(
+            state == WithdrawalState::Delayed,
+            Error::<T>::NotDelayedState
+        );
+        Ok(())
+    }
+
     fn ensure_withdrawal_records_exists(
         id: WithdrawalRecordId,
     ) -> Result<(WithdrawalRecordOf<T>, WithdrawalState), DispatchError> {
@@ -222,7 +374,8 @@ impl<T: Trait> Module<T> {
 
     /// Withdrawal asset (lock asset token firstly, follow-up operations are required).
     ///
-    /// WithdrawalRecord State: `Applying`
+    /// WithdrawalRecord State: `Applying`, or `Delayed` if the balance is above the threshold
+    /// of the time-lock.
     ///
     /// NOTE: this function has included withdrawal_init and withdrawal_locking.
     pub fn withdraw(
@@ -254,14 +407,52 @@ impl<T: Trait> Module<T> {
 
         // Set storages
         PendingWithdrawals::<T>::insert(id, record.clone());
-        WithdrawalStateOf::insert(id, WithdrawalState::Applying);
         let next_id = id.checked_add(1_u32).unwrap_or(0);
         NextWithdrawalRecordId::put(next_id);
 
         Self::deposit_event(Event::<T>::WithdrawalCreated(id, record));
+
+        match Self::withdrawal_delay_of(asset_id) {
+            Some(delay) if !delay.delay.is_zero() && balance > delay.threshold => {
+                let maturity = height.saturating_add(delay.delay);
+                Self::set_state(id, WithdrawalState::Delayed);
+                WithdrawalMaturityOf::<T>::insert(id, maturity);
+                MaturingWithdrawals::<T>::mutate(maturity, |ids| ids.push(id));
+                Self::deposit_event(Event::<T>::WithdrawalDelayed(id, maturity));
+            }
+            _ => Self::set_state(id, WithdrawalState::Applying),
+        }
         Ok(())
     }
 
+    /// Mature the delayed withdrawals of the block, except the frozen ones.
+    ///
+    /// WithdrawalRecord State: `Delayed` ==> `Applying`
+    fn mature_withdrawals(now: T::BlockNumber) -> Weight {
+        let db = T::DbWeight::get();
+        if !MaturingWithdrawals::<T>::contains_key(now) {
+            return db.reads(1);
+        }
+
+        let ids = MaturingWithdrawals::<T>::take(now);
+        let mut matured: Weight = 0;
+        for id in ids.iter() {
+            if Self::state_of(id) == Some(WithdrawalState::Delayed) && !Self::frozen_withdrawals(id)
+            {
+                Self::mature_withdrawal(*id);
+                matured += 1;
+            }
+        }
+        db.reads_writes(1 + 2 * ids.len() as Weight + 2 * matured, 1 + 3 * matured)
+    }
+
+    fn mature_withdrawal(id: WithdrawalRecordId) {
+        info!("[mature_withdrawal] id:{}", id);
+        Self::set_state(id, WithdrawalState::Applying);
+        WithdrawalMaturityOf::<T>::remove(id);
+        Self::deposit_event(Event::<T>::WithdrawalMatured(id));
+    }
+
     /// Process withdrawal (cannot be canceled, but can be recovered).
     ///
     /// WithdrawalRecord State: `Applying` ==> `Processing`
@@ -282,7 +473,7 @@ impl<T: Trait> Module<T> {
             );
             return Err(Error::<T>::NotApplyingState.into());
         }
-        WithdrawalStateOf::insert(id, WithdrawalState::Processing);
+        Self::set_state(id, WithdrawalState::Processing);
         Self::deposit_event(Event::<T>::WithdrawalProcessed(id));
         Ok(())
     }
@@ -317,14 +508,14 @@ impl<T: Trait> Module<T> {
             );
             return Err(Error::<T>::NotProcessingState.into());
         }
-        WithdrawalStateOf::insert(id, WithdrawalState::Applying);
+        Self::set_state(id, WithdrawalState::Applying);
         Self::deposit_event(Event::<T>::WithdrawalRecovered(id));
         Ok(())
     }
 
     /// Cancel withdrawal
     ///
-    /// WithdrawalRecord State: `Applying` ==> `NormalCancel`
+    /// WithdrawalRecord State: `Applying`|`Delayed` ==> `NormalCancel`
     pub fn cancel_withdrawal(id: WithdrawalRecordId, who: &T::AccountId) -> DispatchResult {
         let (record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
         if record.applicant() != who {
@@ -346,9 +537,9 @@ impl<T: Trait> Module<T> {
         curr_state: WithdrawalState,
         new_state: WithdrawalState,
     ) -> DispatchResult {
-        if curr_state != WithdrawalState::Applying {
+        if curr_state != WithdrawalState::Applying && curr_state != WithdrawalState::Delayed {
             error!(
-                "[cancel_withdrawal] id:{}, current withdrawal state ({:?}) must be `Applying`",
+                "[cancel_withdrawal] id:{}, current withdrawal state ({:?}) must be `Applying` or `Delayed`",
                 id, curr_state
             );
             return Err(Error::<T>::NotApplyingState.into());
@@ -358,8 +549,11 @@ impl<T: Trait> Module<T> {
         Self::unlock(record.applicant(), record.asset_id(), record.balance())?;
 
         // Remove storage
-        PendingWithdrawals::<T>::remove(id);
-        WithdrawalStateOf::remove(id);
+        Self::complete_withdrawal(id, record, new_state);
+        if curr_state == WithdrawalState::Delayed {
+            WithdrawalMaturityOf::<T>::remove(id);
+            FrozenWithdrawals::remove(id);
+        }
 
         Self::deposit_event(Event::<T>::WithdrawalCanceled(id, new_state));
         Ok(())
@@ -407,8 +601,7 @@ impl<T: Trait> Module<T> {
         Self::destroy(record.applicant(), record.asset_id(), record.balance())?;
 
         // Remove storage
-        PendingWithdrawals::<T>::remove(id);
-        WithdrawalStateOf::remove(id);
+        Self::complete_withdrawal(id, record, new_state);
 
         Self::deposit_event(Event::<T>::WithdrawalFinished(id, new_state));
         Ok(())
@@ -442,9 +635,17 @@ impl<T: Trait> Module<T> {
                 // State: `Processing` ==> `Applying`
                 Self::recover_withdrawal_impl(id, curr_state)
             }
+            (WithdrawalState::Delayed, WithdrawalState::Applying) => {
+                // State: `Delayed` ==> `Applying`, even if it's frozen
+                FrozenWithdrawals::remove(id);
+                Self::mature_withdrawal(id);
+                Ok(())
+            }
             (WithdrawalState::Applying, WithdrawalState::NormalCancel)
-            | (WithdrawalState::Applying, WithdrawalState::RootCancel) => {
-                // State: `Applying` ==> `NormalCancel`|`RootCancel`
+            | (WithdrawalState::Applying, WithdrawalState::RootCancel)
+            | (WithdrawalState::Delayed, WithdrawalState::NormalCancel)
+            | (WithdrawalState::Delayed, WithdrawalState::RootCancel) => {
+                // State: `Applying`|`Delayed` ==> `NormalCancel`|`RootCancel`
                 Self::cancel_withdrawal_impl(id, record, curr_state, new_state)
             }
             (WithdrawalState::Applying, WithdrawalState::NormalFinish)
@@ -498,6 +699,69 @@ impl<T: Trait> Module<T> {
         Self::set_withdrawal_state(frame_system::RawOrigin::Root.into(), id, new_state)
     }
 
+    /// Note the hash of the transaction which paid the withdrawal on the target chain, which
+    /// would be kept in the history once the withdrawal is finished.
+    pub fn note_withdrawal_tx(id: WithdrawalRecordId, tx_hash: Vec<u8>) {
+        WithdrawalTxOf::insert(id, tx_hash);
+    }
+
+    fn set_state(id: WithdrawalRecordId, state: WithdrawalState) {
+        let now = frame_system::Module::<T>::block_number();
+        let was_applying = Self::state_of(id) == Some(WithdrawalState::Applying);
+        let is_applying = state == WithdrawalState::Applying;
+        if was_applying != is_applying {
+            Self::index_applying_withdrawal(id, is_applying);
+        }
+        WithdrawalStateOf::insert(id, state);
+        WithdrawalTransitionsOf::<T>::append(id, (state, now));
+    }
+
+    /// Insert the withdrawal into or remove it from `ApplyingWithdrawalsOf`, keeping the order.
+    fn index_applying_withdrawal(id: WithdrawalRecordId, applying: bool) {
+        let asset_id = match Self::pending_withdrawals(id) {
+            Some(record) => record.asset_id(),
+            None => return,
+        };
+        ApplyingWithdrawalsOf::mutate(asset_id, |ids| match (ids.binary_search(&id), applying) {
+            (Err(pos), true) => ids.insert(pos, id),
+            (Ok(pos), false) => {
+                ids.remove(pos);
+            }
+            _ => {}
+        });
+    }
+
+    /// Remove the pending withdrawal and keep it in the history of the applicant.
+    fn complete_withdrawal(
+        id: WithdrawalRecordId,
+        record: WithdrawalRecordOf<T>,
+        state: WithdrawalState,
+    ) {
+        if Self::state_of(id) == Some(WithdrawalState::Applying) {
+            Self::index_applying_withdrawal(id, false);
+        }
+        PendingWithdrawals::<T>::remove(id);
+        WithdrawalStateOf::remove(id);
+
+        let mut transitions = WithdrawalTransitionsOf::<T>::take(id);
+        transitions.push((state, frame_system::Module::<T>::block_number()));
+        let completed = CompletedWithdrawal {
+            id,
+            asset_id: record.asset_id(),
+            balance: record.balance(),
+            addr: record.addr().clone(),
+            ext: record.ext().clone(),
+            state,
+            tx_hash: WithdrawalTxOf::take(id),
+            transitions,
+        };
+
+        let who = record.applicant();
+        let count = Self::completed_withdrawal_count(who);
+        CompletedWithdrawals::<T>::insert(who, count % MAX_WITHDRAWAL_HISTORY, completed);
+        CompletedWithdrawalCount::<T>::insert(who, count.wrapping_add(1));
+    }
+
     fn lock(who: &T::AccountId, asset_id: AssetId, value: BalanceOf<T>) -> DispatchResult {
         xpallet_assets::Module::<T>::move_balance(
             &asset_id,
@@ -543,6 +807,23 @@ impl<T: Trait> Module<T> {
             .collect()
     }
 
+    /// Returns the completed withdrawals of an account from the latest one.
+    ///
+    /// The returned data will be empty if `page_index` is invalid.
+    pub fn withdrawal_history(
+        who: &T::AccountId,
+        page_index: u32,
+        page_size: u32,
+    ) -> Vec<CompletedWithdrawalOf<T>> {
+        let count = Self::completed_withdrawal_count(who);
+        (0..count.min(MAX_WITHDRAWAL_HISTORY))
+            .map(|n| count.wrapping_sub(n + 1) % MAX_WITHDRAWAL_HISTORY)
+            .skip(page_index.saturating_mul(page_size) as usize)
+            .take(page_size as usize)
+            .filter_map(|index| Self::completed_withdrawals(who, index))
+            .collect()
+    }
+
     pub fn withdrawals_list_by_chain(
         chain: Chain,
     ) -> BTreeMap<WithdrawalRecordId, Withdrawal<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
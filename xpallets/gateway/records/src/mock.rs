@@ -100,6 +100,7 @@ impl xpallet_assets::Trait for Test {
 
 impl Trait for Test {
     type Event = ();
+    type GuardianOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -3,7 +3,7 @@
 pub use super::mock::*;
 use super::*;
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
 use frame_system::RawOrigin;
 
 #[test]
@@ -194,3 +194,137 @@ fn test_withdrawal_chainx() {
         );
     })
 }
+
+#[test]
+fn test_delayed_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XRecords::deposit(&ALICE, X_BTC, 200));
+        let delay = WithdrawalDelay {
+            threshold: 50,
+            delay: 10,
+        };
+        assert_noop!(
+            XRecords::set_withdrawal_delay(Origin::signed(ALICE), X_BTC, Some(delay)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XRecords::set_withdrawal_delay(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(delay)
+        ));
+        let withdraw = |balance| {
+            XRecords::withdraw(
+                &ALICE,
+                X_BTC,
+                balance,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+            )
+        };
+
+        // not above the threshold
+        assert_ok!(withdraw(50));
+        assert_eq!(XRecords::state_of(0), Some(WithdrawalState::Applying));
+        assert_ok!(withdraw(60));
+        assert_eq!(XRecords::state_of(1), Some(WithdrawalState::Delayed));
+        assert_eq!(XRecords::withdrawal_maturity_of(1), Some(11));
+        assert_eq!(XRecords::applying_withdrawals_of(X_BTC), vec![0]);
+        assert_noop!(
+            XRecords::process_withdrawal(1, Chain::Bitcoin),
+            XRecordsErr::NotApplyingState
+        );
+
+        // the applicant could cancel it in the delay
+        assert_ok!(withdraw(70));
+        assert_ok!(XRecords::cancel_withdrawal(2, &ALICE));
+        assert_eq!(XRecords::withdrawal_maturity_of(2), None);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 300 - 50 - 60);
+
+        assert_ok!(withdraw(80));
+        assert_noop!(
+            XRecords::freeze_withdrawal(RawOrigin::Root.into(), 0),
+            XRecordsErr::NotDelayedState
+        );
+        assert_ok!(XRecords::freeze_withdrawal(RawOrigin::Root.into(), 3));
+        assert_noop!(
+            XRecords::freeze_withdrawal(RawOrigin::Root.into(), 3),
+            XRecordsErr::AlreadyFrozen
+        );
+
+        // the frozen withdrawal does not mature
+        XRecords::on_initialize(11);
+        assert_eq!(XRecords::state_of(1), Some(WithdrawalState::Applying));
+        assert_eq!(XRecords::state_of(3), Some(WithdrawalState::Delayed));
+        assert!(XRecords::maturing_withdrawals(11).is_empty());
+        assert_eq!(XRecords::applying_withdrawals_of(X_BTC), vec![0, 1]);
+
+        // it matures once unfrozen after the delay
+        System::set_block_number(12);
+        assert_ok!(XRecords::unfreeze_withdrawal(RawOrigin::Root.into(), 3));
+        assert_eq!(XRecords::state_of(3), Some(WithdrawalState::Applying));
+        assert!(!XRecords::frozen_withdrawals(3));
+        assert_eq!(XRecords::applying_withdrawals_of(X_BTC), vec![0, 1, 3]);
+        assert_ok!(XRecords::process_withdrawals(&[1, 3], Chain::Bitcoin));
+        assert_eq!(XRecords::applying_withdrawals_of(X_BTC), vec![0]);
+        assert_ok!(XRecords::cancel_withdrawal(0, &ALICE));
+        assert!(XRecords::applying_withdrawals_of(X_BTC).is_empty());
+    })
+}
+
+#[test]
+fn test_withdrawal_history() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XRecords::deposit(&ALICE, X_BTC, 1000));
+        let withdraw = |balance| {
+            XRecords::withdraw(
+                &ALICE,
+                X_BTC,
+                balance,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+            )
+        };
+
+        assert_ok!(withdraw(10));
+        assert_ok!(withdraw(20));
+        System::set_block_number(2);
+        assert_ok!(XRecords::process_withdrawal(0, Chain::Bitcoin));
+        XRecords::note_withdrawal_tx(0, vec![0xaa; 32]);
+        System::set_block_number(3);
+        assert_ok!(XRecords::finish_withdrawal(0, Some(Chain::Bitcoin)));
+        assert_ok!(XRecords::cancel_withdrawal(1, &ALICE));
+        assert_eq!(XRecords::withdrawal_transitions_of(0), vec![]);
+        assert_eq!(XRecords::withdrawal_tx_of(0), None);
+
+        let history = XRecords::withdrawal_history(&ALICE, 0, 10);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].id, 1);
+        assert_eq!(history[0].state, WithdrawalState::NormalCancel);
+        assert_eq!(history[0].tx_hash, None);
+        assert_eq!(history[1].id, 0);
+        assert_eq!(history[1].balance, 10);
+        assert_eq!(history[1].state, WithdrawalState::NormalFinish);
+        assert_eq!(history[1].tx_hash, Some(vec![0xaa; 32]));
+        assert_eq!(
+            history[1].transitions,
+            vec![
+                (WithdrawalState::Applying, 1),
+                (WithdrawalState::Processing, 2),
+                (WithdrawalState::NormalFinish, 3),
+            ]
+        );
+        assert_eq!(XRecords::withdrawal_history(&ALICE, 1, 1)[0].id, 0);
+        assert!(XRecords::withdrawal_history(&ALICE, 1, 2).is_empty());
+        assert!(XRecords::withdrawal_history(&BOB, 0, 10).is_empty());
+
+        // only the latest withdrawals are kept
+        for id in 2..MAX_WITHDRAWAL_HISTORY + 2 {
+            assert_ok!(withdraw(1));
+            assert_ok!(XRecords::cancel_withdrawal(id, &ALICE));
+        }
+        let history = XRecords::withdrawal_history(&ALICE, 0, 100);
+        assert_eq!(history.len(), MAX_WITHDRAWAL_HISTORY as usize);
+        assert_eq!(history[0].id, MAX_WITHDRAWAL_HISTORY + 1);
+        assert_eq!(history.last().map(|w| w.id), Some(2));
+    })
+}
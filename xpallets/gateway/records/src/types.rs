@@ -5,6 +5,7 @@ use codec::{Codec, Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
 
 use chainx_primitives::{AddrStr, AssetId};
 use xp_runtime::Memo;
@@ -14,6 +15,11 @@ pub type WithdrawalRecordId = u32;
 
 /// The state machine of WithdrawState:
 ///
+/// Delayed (lock token, time-locked, can be frozen by the guardian)
+///     |
+///     +----> NormalCancel|RootCancel (unlock token)
+///     |
+///     v (the delay expires)
 /// Applying (lock token) <---> Processing (can't cancel, but can be recovered to `Applying`)
 ///     |                           |
 ///     |                           +----> NormalFinish|RootFinish (destroy token)
@@ -31,6 +37,8 @@ pub enum WithdrawalState {
     RootFinish,
     NormalCancel,
     RootCancel,
+    /// The large withdrawal is time-locked, trustees could only process it after it matures.
+    Delayed,
 }
 
 impl Default for WithdrawalState {
@@ -39,6 +47,15 @@ impl Default for WithdrawalState {
     }
 }
 
+/// The time-lock of the large withdrawals of an asset.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+pub struct WithdrawalDelay<Balance, BlockNumber> {
+    /// The withdrawal whose balance is above the threshold would be `Delayed`.
+    pub threshold: Balance,
+    /// The blocks for which the withdrawal is `Delayed`.
+    pub delay: BlockNumber,
+}
+
 /// WithdrawalRecord for withdrawal
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct WithdrawalRecord<AccountId, Balance, BlockNumber> {
@@ -93,6 +110,22 @@ where
     }
 }
 
+/// The withdrawal which has been finished or canceled, kept in the history of the applicant.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct CompletedWithdrawal<Balance, BlockNumber> {
+    pub id: WithdrawalRecordId,
+    pub asset_id: AssetId,
+    pub balance: Balance,
+    pub addr: AddrStr,
+    pub ext: Memo,
+    /// The final state, `NormalFinish`|`RootFinish`|`NormalCancel`|`RootCancel`.
+    pub state: WithdrawalState,
+    /// The hash of the transaction which paid the withdrawal on the target chain, if known.
+    pub tx_hash: Option<Vec<u8>>,
+    /// The state transitions and their blocks, from the creation to the final state.
+    pub transitions: Vec<(WithdrawalState, BlockNumber)>,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode)]
 pub struct Withdrawal<AccountId, Balance, BlockNumber> {
     pub asset_id: AssetId,
@@ -34,6 +34,9 @@ pub trait WeightInfo {
     fn root_withdraw() -> Weight;
     fn set_withdrawal_state() -> Weight;
     fn set_withdrawal_state_list(u: u32) -> Weight;
+    fn freeze_withdrawal() -> Weight;
+    fn unfreeze_withdrawal() -> Weight;
+    fn set_withdrawal_delay() -> Weight;
 }
 
 /// Weights for xpallet_gateway_records using the Substrate node and recommended hardware.
@@ -59,6 +62,19 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(8 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn freeze_withdrawal() -> Weight {
+        (24_634_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn unfreeze_withdrawal() -> Weight {
+        (36_218_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn set_withdrawal_delay() -> Weight {
+        (5_416_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -83,4 +99,17 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(8 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    fn freeze_withdrawal() -> Weight {
+        (24_634_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn unfreeze_withdrawal() -> Weight {
+        (36_218_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn set_withdrawal_delay() -> Weight {
+        (5_416_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
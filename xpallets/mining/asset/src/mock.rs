@@ -266,6 +266,7 @@ impl xpallet_mining_staking::Trait for Test {
     type MinimumReferralId = MinimumReferralId;
     type MaximumReferralId = MaximumReferralId;
     type SessionInterface = Self;
+    type OnNewEra = ();
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
     type WeightInfo = ();
@@ -0,0 +1,109 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+const MAX_TARGETS: u32 = 16;
+
+fn create_funded_user<T: Trait>(string: &'static str, n: u32, value: u32) -> T::AccountId {
+    let user = account(string, n, SEED);
+    let balance: BalanceOf<T> = value.into();
+    <T as xpallet_mining_staking::Trait>::Currency::make_free_balance_be(&user, balance);
+    <T as xpallet_mining_staking::Trait>::Currency::issue(balance);
+    user
+}
+
+fn create_targets<T: Trait>(n: u32) -> Vec<T::AccountId> {
+    (0..n)
+        .map(|i| {
+            let validator = create_funded_user::<T>("liquid_validator", i, 1_000_000);
+            if !Staking::<T>::is_validator(&validator) {
+                assert!(Staking::<T>::register(
+                    RawOrigin::Signed(validator.clone()).into(),
+                    (1000 + i).to_be_bytes().to_vec(),
+                    100_000.into(),
+                )
+                .is_ok());
+            }
+            validator
+        })
+        .collect()
+}
+
+fn prepare_pool<T: Trait>(n: u32) -> Result<T::AccountId, &'static str> {
+    Module::<T>::register_derivative();
+    Module::<T>::set_targets(RawOrigin::Root.into(), create_targets::<T>(n))?;
+    Ok(create_funded_user::<T>("depositor", n, 100_000))
+}
+
+benchmarks! {
+    _{ }
+
+    deposit {
+        let depositor = prepare_pool::<T>(2)?;
+    }: _(RawOrigin::Signed(depositor.clone()), 1000.into())
+    verify {
+        assert!(!xpallet_assets::Module::<T>::usable_balance(&depositor, &T::DerivativeAssetId::get()).is_zero());
+    }
+
+    redeem {
+        let depositor = prepare_pool::<T>(2)?;
+        Module::<T>::deposit(RawOrigin::Signed(depositor.clone()).into(), 1000.into())?;
+    }: _(RawOrigin::Signed(depositor.clone()), 500.into())
+    verify {
+        assert_eq!(Module::<T>::redemptions(&depositor).len(), 1);
+    }
+
+    withdraw_redeemed {
+        let depositor = prepare_pool::<T>(2)?;
+        Module::<T>::deposit(RawOrigin::Signed(depositor.clone()).into(), 1000.into())?;
+        Module::<T>::redeem(RawOrigin::Signed(depositor.clone()).into(), 500.into())?;
+
+        let block_number = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(
+            block_number + Staking::<T>::bonding_duration() + 1.into()
+        );
+    }: _(RawOrigin::Signed(depositor.clone()))
+    verify {
+        assert!(Module::<T>::redemptions(&depositor).is_empty());
+    }
+
+    set_targets {
+        let n in 1 .. MAX_TARGETS;
+        let targets = create_targets::<T>(n);
+    }: _(RawOrigin::Root, targets.clone())
+    verify {
+        assert_eq!(Module::<T>::targets(), targets);
+    }
+
+    compound {
+        let n in 1 .. MAX_TARGETS;
+        let depositor = prepare_pool::<T>(n)?;
+        for _ in 0..n {
+            Module::<T>::deposit(RawOrigin::Signed(depositor.clone()).into(), 1000.into())?;
+        }
+    }: {
+        Module::<T>::compound();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(test_benchmark_deposit::<Test>());
+            assert_ok!(test_benchmark_redeem::<Test>());
+            assert_ok!(test_benchmark_withdraw_redeemed::<Test>());
+            assert_ok!(test_benchmark_set_targets::<Test>());
+            assert_ok!(test_benchmark_compound::<Test>());
+        });
+    }
+}
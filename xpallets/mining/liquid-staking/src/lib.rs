@@ -0,0 +1,511 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! # Liquid Staking Module
+//!
+//! The PCX deposited to this module is pooled and bonded to the validators chosen by the
+//! council, the depositor gets the derivative asset B-PCX in return, which is transferable
+//! and can be traded on the spot market like any other asset.
+//!
+//! The staking dividends of the pool are claimed and bonded back every `CompoundPeriod`,
+//! so that the PCX one derivative is worth grows over time. The derivative can be redeemed
+//! at the current exchange rate at any time, the redeemed PCX goes through the normal
+//! unbonding queue of Staking and can be withdrawn once it's due.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    storage::IterableStorageDoubleMap,
+    traits::{Currency, ExistenceRequirement, Get},
+    weights::Weight,
+};
+use frame_system::{ensure_root, ensure_signed, RawOrigin};
+use orml_utilities::with_transaction_result;
+use sp_runtime::{
+    traits::{AccountIdConversion, SaturatedConversion, Saturating, StaticLookup, Zero},
+    ModuleId,
+};
+
+use chainx_primitives::AssetId;
+use xp_logging::{debug, error, warn};
+use xp_mining_common::Claim;
+use xp_protocol::PCX_DECIMALS;
+use xpallet_assets::{AssetInfo, Chain};
+use xpallet_mining_staking::{LockedType, Nominations, Unbonded};
+
+pub use self::weights::WeightInfo;
+
+pub type BalanceOf<T> = xpallet_mining_staking::BalanceOf<T>;
+pub type DerivativeBalanceOf<T> = xpallet_assets::BalanceOf<T>;
+
+type Staking<T> = xpallet_mining_staking::Module<T>;
+
+/// Maximum number of on-going redemptions per account.
+pub const MAXIMUM_REDEMPTIONS: usize = 16;
+
+pub trait Trait: xpallet_assets::Trait + xpallet_mining_staking::Trait {
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The module id, used for deriving the pool account.
+    type ModuleId: Get<ModuleId>;
+
+    /// Asset id of the liquid staking derivative.
+    type DerivativeAssetId: Get<AssetId>;
+
+    /// The interval in blocks of claiming the dividends of pool and bonding them back.
+    type CompoundPeriod: Get<Self::BlockNumber>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XLiquidStaking {
+        /// The validators that the pooled PCX is bonded to.
+        pub Targets get(fn targets): Vec<T::AccountId>;
+
+        /// The on-going redemptions of an account.
+        pub Redemptions get(fn redemptions):
+            map hasher(twox_64_concat) T::AccountId => Vec<Unbonded<BalanceOf<T>, T::BlockNumber>>;
+
+        /// Total balance of the redemptions that are not withdrawn yet.
+        pub TotalRedeeming get(fn total_redeeming): BalanceOf<T>;
+
+        /// Balance unlocked from the unbonded chunks of pool that is not withdrawn yet.
+        ///
+        /// The redemptions are only paid out of this balance.
+        pub TotalUnlocked get(fn total_unlocked): BalanceOf<T>;
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>,
+        DerivativeBalance = DerivativeBalanceOf<T>,
+    {
+        /// PCX was deposited to the pool and the derivative was minted. [who, value, derivative]
+        Deposited(AccountId, Balance, DerivativeBalance),
+        /// The derivative was redeemed and the PCX started unbonding. [who, derivative, value]
+        Redeemed(AccountId, DerivativeBalance, Balance),
+        /// The redeemed PCX was withdrawn from the pool. [who, value]
+        Withdrawn(AccountId, Balance),
+        /// The idle balance of pool was bonded back. [value]
+        Compounded(Balance),
+        /// The bonding targets of pool were updated. [targets]
+        TargetsSet(Vec<AccountId>),
+    }
+);
+
+decl_error! {
+    /// Error for the liquid staking module.
+    pub enum Error for Module<T: Trait> {
+        /// The operation of zero balance is meaningless.
+        ZeroBalance,
+        /// The derivative balance is not enough.
+        InsufficientBalance,
+        /// No bonding target is set.
+        NoTargets,
+        /// The target is not a validator.
+        NotValidator,
+        /// None of the targets can accept more votes.
+        NoAcceptableTarget,
+        /// The pool does not have enough bonded balance to redeem.
+        InsufficientPooled,
+        /// Can not have more on-going redemptions.
+        TooManyRedemptions,
+        /// None of the redemptions is due.
+        NoDueRedemption,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        fn on_runtime_upgrade() -> Weight {
+            Self::register_derivative();
+            T::DbWeight::get().reads_writes(2, 4)
+        }
+
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let period = T::CompoundPeriod::get();
+            if !period.is_zero() && (now % period).is_zero() {
+                let nominees = Self::compound();
+                <T as Trait>::WeightInfo::compound(nominees)
+            } else {
+                0
+            }
+        }
+
+        /// Deposit `value` of PCX to the pool and get the derivative in return.
+        ///
+        /// The derivative asset is registered on the first deposit if it does not exist yet.
+        #[weight = <T as Trait>::WeightInfo::deposit()]
+        pub fn deposit(origin, #[compact] value: BalanceOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!value.is_zero(), Error::<T>::ZeroBalance);
+
+            let derivative = Self::derivative_for(value);
+            ensure!(!derivative.is_zero(), Error::<T>::ZeroBalance);
+
+            Self::register_derivative();
+
+            with_transaction_result(|| {
+                let pool = Self::pool_account();
+                <T as xpallet_mining_staking::Trait>::Currency::transfer(
+                    &who,
+                    &pool,
+                    value,
+                    ExistenceRequirement::AllowDeath,
+                )?;
+                Self::bond_to_targets(&pool, value)?;
+                xpallet_assets::Module::<T>::issue(&T::DerivativeAssetId::get(), &who, derivative)?;
+                Self::deposit_event(Event::<T>::Deposited(who, value, derivative));
+                Ok(())
+            })
+        }
+
+        /// Redeem `derivative` for the PCX it is worth, which is unbonded from the pool.
+        ///
+        /// The value could include the idle balance and the unclaimed dividends of pool, which
+        /// are only bonded back by the next compounding, the redemption more than the bonded
+        /// balance of pool has to wait for it.
+        #[weight = <T as Trait>::WeightInfo::redeem()]
+        pub fn redeem(origin, #[compact] derivative: DerivativeBalanceOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!derivative.is_zero(), Error::<T>::ZeroBalance);
+
+            let asset_id = T::DerivativeAssetId::get();
+            ensure!(
+                derivative <= xpallet_assets::Module::<T>::usable_balance(&who, &asset_id),
+                Error::<T>::InsufficientBalance
+            );
+            ensure!(
+                Self::redemptions(&who).len() < MAXIMUM_REDEMPTIONS,
+                Error::<T>::TooManyRedemptions
+            );
+
+            let value = Self::value_of(derivative);
+            ensure!(!value.is_zero(), Error::<T>::ZeroBalance);
+            let pool = Self::pool_account();
+            ensure!(value <= Staking::<T>::staked_of(&pool), Error::<T>::InsufficientPooled);
+
+            with_transaction_result(|| {
+                xpallet_assets::Module::<T>::destroy_usable(&asset_id, &who, derivative)?;
+                // Free the slots of due chunks first, so that the new unbonded balance
+                // is less likely to be merged into a chunk others are waiting for.
+                Self::unlock_due_chunks(&pool, <frame_system::Module<T>>::block_number())?;
+                let locked_until = Self::unbond_from_targets(&pool, value)?;
+                Redemptions::<T>::mutate(&who, |redemptions| {
+                    redemptions.push(Unbonded { value, locked_until })
+                });
+                TotalRedeeming::<T>::mutate(|total| *total = total.saturating_add(value));
+                Self::deposit_event(Event::<T>::Redeemed(who, derivative, value));
+                Ok(())
+            })
+        }
+
+        /// Withdraw the redeemed PCX that is due.
+        ///
+        /// The unbonded chunks of pool can mature later than the redemptions expect
+        /// since the chunks are merged, only the balance actually unlocked is paid out,
+        /// the rest of due redemptions is left for later.
+        #[weight = <T as Trait>::WeightInfo::withdraw_redeemed()]
+        pub fn withdraw_redeemed(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let current_block = <frame_system::Module<T>>::block_number();
+            let mut redemptions = Self::redemptions(&who);
+            ensure!(
+                redemptions.iter().any(|redemption| current_block > redemption.locked_until),
+                Error::<T>::NoDueRedemption
+            );
+
+            with_transaction_result(|| {
+                let pool = Self::pool_account();
+                Self::unlock_due_chunks(&pool, current_block)?;
+
+                redemptions.sort_by_key(|redemption| redemption.locked_until);
+                let mut available = Self::total_unlocked();
+                let mut value: BalanceOf<T> = Zero::zero();
+                for redemption in redemptions.iter_mut() {
+                    if available.is_zero() || current_block <= redemption.locked_until {
+                        break;
+                    }
+                    let paid = redemption.value.min(available);
+                    redemption.value -= paid;
+                    available -= paid;
+                    value += paid;
+                }
+                ensure!(!value.is_zero(), Error::<T>::NoDueRedemption);
+
+                <T as xpallet_mining_staking::Trait>::Currency::transfer(
+                    &pool,
+                    &who,
+                    value,
+                    ExistenceRequirement::AllowDeath,
+                )?;
+                redemptions.retain(|redemption| !redemption.value.is_zero());
+                if redemptions.is_empty() {
+                    Redemptions::<T>::remove(&who);
+                } else {
+                    Redemptions::<T>::insert(&who, redemptions);
+                }
+                TotalUnlocked::<T>::put(available);
+                TotalRedeeming::<T>::mutate(|total| *total = total.saturating_sub(value));
+                Self::deposit_event(Event::<T>::Withdrawn(who, value));
+                Ok(())
+            })
+        }
+
+        /// Set the validators that the pool bonds to.
+        ///
+        /// The nominations to the validators no longer in the targets are kept,
+        /// they are the first to be unbonded on redemption.
+        #[weight = <T as Trait>::WeightInfo::set_targets(targets.len() as u32)]
+        pub fn set_targets(origin, targets: Vec<T::AccountId>) {
+            ensure_root(origin)?;
+            ensure!(!targets.is_empty(), Error::<T>::NoTargets);
+            for target in targets.iter() {
+                ensure!(Staking::<T>::is_validator(target), Error::<T>::NotValidator);
+            }
+            Targets::<T>::put(targets.clone());
+            Self::deposit_event(Event::<T>::TargetsSet(targets));
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// The account that holds and bonds the pooled PCX.
+    pub fn pool_account() -> T::AccountId {
+        T::ModuleId::get().into_account()
+    }
+
+    /// Registers the derivative asset if it does not exist yet.
+    pub fn register_derivative() {
+        let asset_id = T::DerivativeAssetId::get();
+        if xpallet_assets_registrar::Module::<T>::exists(&asset_id) {
+            return;
+        }
+        let result = AssetInfo::new::<T>(
+            b"B-PCX".to_vec(),
+            b"B-PCX".to_vec(),
+            Chain::ChainX,
+            PCX_DECIMALS,
+            b"ChainX's liquid staking derivative of PCX".to_vec(),
+        )
+        .and_then(|info| {
+            xpallet_assets_registrar::Module::<T>::register(
+                RawOrigin::Root.into(),
+                asset_id,
+                info,
+                true,
+                false,
+            )
+        });
+        if let Err(e) = result {
+            error!(
+                "[register_derivative] Failed to register the derivative asset #{}: {:?}",
+                asset_id, e
+            );
+        }
+    }
+
+    /// Returns the total PCX of the pool that backs the derivative.
+    ///
+    /// Besides the bonded balance, the idle balance and the dividends not claimed yet
+    /// are included as well, otherwise they would be gained by whoever deposits right
+    /// before the next compounding.
+    pub fn total_pooled() -> BalanceOf<T> {
+        let pool = Self::pool_account();
+        let current_block = <frame_system::Module<T>>::block_number();
+        let pending_dividends = Nominations::<T>::iter_prefix(&pool)
+            .filter_map(|(nominee, _)| {
+                Staking::<T>::compute_dividend_at(&pool, &nominee, current_block).ok()
+            })
+            .fold(Zero::zero(), |acc: BalanceOf<T>, x| acc.saturating_add(x));
+        Staking::<T>::staked_of(&pool)
+            .saturating_add(Self::idle_balance(&pool))
+            .saturating_add(pending_dividends)
+    }
+
+    /// Returns the amount of derivative that `value` of PCX is worth.
+    pub fn derivative_for(value: BalanceOf<T>) -> DerivativeBalanceOf<T> {
+        let value = value.saturated_into::<u128>();
+        let pooled = Self::total_pooled().saturated_into::<u128>();
+        let supply = Self::derivative_supply();
+        if pooled.is_zero() || supply.is_zero() {
+            value.saturated_into()
+        } else {
+            (value.saturating_mul(supply) / pooled).saturated_into()
+        }
+    }
+
+    /// Returns the amount of PCX that `derivative` is worth.
+    pub fn value_of(derivative: DerivativeBalanceOf<T>) -> BalanceOf<T> {
+        let derivative = derivative.saturated_into::<u128>();
+        let pooled = Self::total_pooled().saturated_into::<u128>();
+        let supply = Self::derivative_supply();
+        if supply.is_zero() {
+            Zero::zero()
+        } else {
+            (derivative.saturating_mul(pooled) / supply).saturated_into()
+        }
+    }
+
+    fn derivative_supply() -> u128 {
+        xpallet_assets::Module::<T>::total_issuance(&T::DerivativeAssetId::get()).saturated_into()
+    }
+
+    /// Returns the balance of pool that is neither bonded nor reserved for the redemptions.
+    fn idle_balance(pool: &T::AccountId) -> BalanceOf<T> {
+        let bonded = Staking::<T>::locks(pool)
+            .get(&LockedType::Bonded)
+            .copied()
+            .unwrap_or_else(Zero::zero);
+        <T as xpallet_mining_staking::Trait>::Currency::free_balance(pool)
+            .saturating_sub(bonded)
+            .saturating_sub(Self::total_redeeming())
+    }
+
+    fn signed_origin(pool: &T::AccountId) -> T::Origin {
+        RawOrigin::Signed(pool.clone()).into()
+    }
+
+    /// Bonds `value` to the target with the least nomination of pool that can accept it.
+    fn bond_to_targets(pool: &T::AccountId, value: BalanceOf<T>) -> DispatchResult {
+        let mut targets = Self::targets();
+        ensure!(!targets.is_empty(), Error::<T>::NoTargets);
+        targets.sort_by_key(|target| Staking::<T>::nominations(pool, target).nomination);
+
+        for target in targets {
+            let lookup = T::Lookup::unlookup(target.clone());
+            match Staking::<T>::bond(Self::signed_origin(pool), lookup, value) {
+                Ok(_) => return Ok(()),
+                Err(e) => {
+                    debug!(
+                        "[bond_to_targets] Failed to bond {:?} to {:?}: {:?}",
+                        value, target, e
+                    );
+                }
+            }
+        }
+
+        Err(Error::<T>::NoAcceptableTarget.into())
+    }
+
+    /// Unbonds `value` from the nominees of pool, starting with the largest nomination.
+    ///
+    /// Returns the block number at which point all of the unbonded balance is due.
+    fn unbond_from_targets(
+        pool: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> Result<T::BlockNumber, DispatchError> {
+        let mut nominations = Nominations::<T>::iter_prefix(pool)
+            .map(|(target, ledger)| (target, ledger.nomination))
+            .filter(|(_, nomination)| !nomination.is_zero())
+            .collect::<Vec<_>>();
+        nominations.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let mut remaining = value;
+        let mut locked_until = Zero::zero();
+        for (target, nomination) in nominations {
+            if remaining.is_zero() {
+                break;
+            }
+            let to_unbond = remaining.min(nomination);
+            let lookup = T::Lookup::unlookup(target.clone());
+            Staking::<T>::unbond(Self::signed_origin(pool), lookup, to_unbond)?;
+            remaining -= to_unbond;
+
+            // The new unbonded chunk could be merged into an existing one,
+            // hence take the latest maturity of all the chunks.
+            let latest = Staking::<T>::nominations(pool, &target)
+                .unbonded_chunks
+                .iter()
+                .map(|chunk| chunk.locked_until)
+                .max()
+                .unwrap_or_else(Zero::zero);
+            locked_until = locked_until.max(latest);
+        }
+        ensure!(remaining.is_zero(), Error::<T>::InsufficientPooled);
+
+        Ok(locked_until)
+    }
+
+    /// Unlocks all the unbonded chunks of pool that are due and adds them to `TotalUnlocked`.
+    fn unlock_due_chunks(pool: &T::AccountId, current_block: T::BlockNumber) -> DispatchResult {
+        let nominations = Nominations::<T>::iter_prefix(pool).collect::<Vec<_>>();
+        let mut unlocked: BalanceOf<T> = Zero::zero();
+        for (target, ledger) in nominations {
+            let mut chunks = ledger.unbonded_chunks;
+            while let Some(index) = chunks
+                .iter()
+                .position(|chunk| current_block > chunk.locked_until)
+            {
+                let lookup = T::Lookup::unlookup(target.clone());
+                Staking::<T>::unlock_unbonded_withdrawal(
+                    Self::signed_origin(pool),
+                    lookup,
+                    index as u32,
+                )?;
+                unlocked = unlocked.saturating_add(chunks.swap_remove(index).value);
+            }
+        }
+        if !unlocked.is_zero() {
+            TotalUnlocked::<T>::mutate(|total| *total = total.saturating_add(unlocked));
+        }
+        Ok(())
+    }
+
+    /// Claims the dividends of pool from all the nominees and bonds the idle balance back.
+    ///
+    /// Returns the number of nominees.
+    fn compound() -> u32 {
+        let pool = Self::pool_account();
+
+        let nominees = Nominations::<T>::iter_prefix(&pool)
+            .map(|(target, _)| target)
+            .collect::<Vec<_>>();
+        for nominee in nominees.iter() {
+            if let Err(e) = <Staking<T> as Claim<T::AccountId>>::claim(&pool, nominee) {
+                debug!(
+                    "[compound] Failed to claim the dividend from {:?}: {:?}",
+                    nominee, e
+                );
+            }
+        }
+
+        let idle = Self::idle_balance(&pool);
+        if !idle.is_zero() {
+            match Self::bond_to_targets(&pool, idle) {
+                Ok(_) => Self::deposit_event(Event::<T>::Compounded(idle)),
+                Err(e) => {
+                    warn!(
+                        "[compound] Failed to bond the idle balance {:?} of pool: {:?}",
+                        idle, e
+                    );
+                }
+            }
+        }
+
+        nominees.len() as u32
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    assert_err, assert_ok,
+    storage::StorageValue,
+    traits::{Get, OnInitialize},
+};
+
+use xp_mining_staking::SessionIndex;
+use xp_protocol::B_PCX;
+
+use super::*;
+use crate::mock::*;
+
+fn t_deposit(who: AccountId, value: Balance) -> DispatchResult {
+    XLiquidStaking::deposit(Origin::signed(who), value)
+}
+
+fn t_redeem(who: AccountId, derivative: Balance) -> DispatchResult {
+    XLiquidStaking::redeem(Origin::signed(who), derivative)
+}
+
+fn t_withdraw_redeemed(who: AccountId) -> DispatchResult {
+    XLiquidStaking::withdraw_redeemed(Origin::signed(who))
+}
+
+fn t_derivative_of(who: AccountId) -> Balance {
+    XAssets::usable_balance(&who, &B_PCX)
+}
+
+fn t_pool_nomination_to(target: AccountId) -> Balance {
+    XStaking::nominations(&XLiquidStaking::pool_account(), &target).nomination
+}
+
+fn t_start_session(session_index: SessionIndex) {
+    assert_eq!(
+        <Period as Get<BlockNumber>>::get(),
+        1,
+        "start_session can only be used with session length 1."
+    );
+    for i in Session::current_index()..session_index {
+        System::set_block_number((i + 1).into());
+        Timestamp::set_timestamp(System::block_number() * 1000 + INIT_TIMESTAMP);
+        Session::on_initialize(System::block_number());
+    }
+
+    assert_eq!(Session::current_index(), session_index);
+}
+
+#[test]
+fn set_targets_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(XLiquidStaking::targets(), vec![1, 2]);
+
+        assert_err!(
+            XLiquidStaking::set_targets(Origin::root(), vec![]),
+            Error::<Test>::NoTargets
+        );
+        assert_err!(
+            XLiquidStaking::set_targets(Origin::root(), vec![3, 100]),
+            Error::<Test>::NotValidator
+        );
+
+        assert_ok!(XLiquidStaking::set_targets(Origin::root(), vec![3, 4]));
+        assert_eq!(XLiquidStaking::targets(), vec![3, 4]);
+    });
+}
+
+#[test]
+fn deposit_and_redeem_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert!(XAssetsRegistrar::exists(&B_PCX));
+
+        assert_err!(t_deposit(10, 0), Error::<Test>::ZeroBalance);
+
+        // The first deposit is minted at 1:1 and bonded to the first target.
+        assert_ok!(t_deposit(10, 300));
+        assert_eq!(t_derivative_of(10), 300);
+        assert_eq!(Balances::free_balance(&10), 700);
+        assert_eq!(t_pool_nomination_to(1), 300);
+
+        // The next deposit goes to the target with the least nomination of pool.
+        assert_ok!(t_deposit(20, 200));
+        assert_eq!(t_derivative_of(20), 200);
+        assert_eq!(t_pool_nomination_to(2), 200);
+        assert_eq!(XLiquidStaking::total_pooled(), 500);
+
+        assert_err!(t_redeem(10, 301), Error::<Test>::InsufficientBalance);
+
+        // Redemption is unbonded from the largest nomination of pool.
+        assert_ok!(t_redeem(10, 100));
+        assert_eq!(t_derivative_of(10), 200);
+        assert_eq!(t_pool_nomination_to(1), 200);
+        assert_eq!(XLiquidStaking::total_pooled(), 400);
+        assert_eq!(XLiquidStaking::total_redeeming(), 100);
+
+        let locked_until = 1 + XStaking::bonding_duration();
+        assert_eq!(
+            XLiquidStaking::redemptions(&10),
+            vec![Unbonded {
+                value: 100,
+                locked_until
+            }]
+        );
+
+        assert_err!(t_withdraw_redeemed(10), Error::<Test>::NoDueRedemption);
+
+        System::set_block_number(locked_until + 1);
+        assert_ok!(t_withdraw_redeemed(10));
+        assert_eq!(Balances::free_balance(&10), 800);
+        assert!(XLiquidStaking::redemptions(&10).is_empty());
+        assert_eq!(XLiquidStaking::total_redeeming(), 0);
+        assert!(XStaking::nominations(&XLiquidStaking::pool_account(), &1)
+            .unbonded_chunks
+            .is_empty());
+    });
+}
+
+#[test]
+fn compound_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_deposit(10, 300));
+        assert_ok!(t_deposit(20, 200));
+
+        t_start_session(12);
+
+        // The unclaimed dividends are already taken into account before compounding.
+        let pooled = XLiquidStaking::total_pooled();
+        assert!(pooled > 500);
+        assert!(XLiquidStaking::value_of(300) > 300);
+
+        let period = <CompoundPeriod as Get<BlockNumber>>::get();
+        System::set_block_number(period * 2);
+        XLiquidStaking::on_initialize(System::block_number());
+
+        // The dividends are bonded back, the derivative is worth the same.
+        assert_eq!(XLiquidStaking::total_pooled(), pooled);
+        assert_eq!(XStaking::staked_of(&XLiquidStaking::pool_account()), pooled);
+        assert_eq!(XAssets::total_issuance(&B_PCX), 500);
+        assert!(XLiquidStaking::value_of(300) > 300);
+        assert!(XLiquidStaking::derivative_for(500) < 500);
+
+        // Redeeming all the derivative takes all the pooled PCX.
+        assert_ok!(t_redeem(10, 300));
+        assert_ok!(t_redeem(20, 200));
+        assert_eq!(XLiquidStaking::total_pooled(), 0);
+        assert_eq!(XLiquidStaking::total_redeeming(), pooled);
+    });
+}
+
+#[test]
+fn redeem_should_wait_for_compounding() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_deposit(10, 300));
+        assert_ok!(t_deposit(20, 200));
+
+        t_start_session(12);
+
+        // The unclaimed dividends are not bonded until the next compounding.
+        assert_ok!(t_redeem(10, 300));
+        assert!(
+            XLiquidStaking::value_of(200) > XStaking::staked_of(&XLiquidStaking::pool_account())
+        );
+        assert_err!(t_redeem(20, 200), Error::<Test>::InsufficientPooled);
+        assert_eq!(t_derivative_of(20), 200);
+
+        let period = <CompoundPeriod as Get<BlockNumber>>::get();
+        System::set_block_number(period * 2);
+        XLiquidStaking::on_initialize(System::block_number());
+
+        assert_ok!(t_redeem(20, 200));
+        assert_eq!(XLiquidStaking::total_pooled(), 0);
+    });
+}
+
+#[test]
+fn withdraw_redeemed_should_only_pay_unlocked() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XLiquidStaking::set_targets(Origin::root(), vec![1]));
+        xpallet_mining_staking::MaximumUnbondedChunkSize::put(1);
+
+        assert_ok!(t_deposit(10, 300));
+        assert_ok!(t_deposit(20, 200));
+
+        let bonding_duration = XStaking::bonding_duration();
+        assert_ok!(t_redeem(10, 100));
+
+        // The second redemption is merged into the only chunk of pool and postpones it.
+        System::set_block_number(5);
+        assert_ok!(t_redeem(20, 100));
+        let chunks = XStaking::nominations(&XLiquidStaking::pool_account(), &1).unbonded_chunks;
+        assert_eq!(
+            chunks,
+            vec![Unbonded {
+                value: 200,
+                locked_until: 5 + bonding_duration
+            }]
+        );
+
+        // The redemption of 10 is due but nothing is unlocked yet.
+        System::set_block_number(2 + bonding_duration);
+        assert_err!(t_withdraw_redeemed(10), Error::<Test>::NoDueRedemption);
+        assert_eq!(Balances::free_balance(&10), 700);
+        assert_eq!(XLiquidStaking::redemptions(&10).len(), 1);
+
+        System::set_block_number(6 + bonding_duration);
+        assert_ok!(t_withdraw_redeemed(10));
+        assert_eq!(XLiquidStaking::total_unlocked(), 100);
+        assert_ok!(t_withdraw_redeemed(20));
+        assert_eq!(Balances::free_balance(&10), 800);
+        assert_eq!(Balances::free_balance(&20), 1900);
+        assert_eq!(XLiquidStaking::total_unlocked(), 0);
+        assert_eq!(XLiquidStaking::total_redeeming(), 0);
+    });
+}
@@ -0,0 +1,105 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_mining_liquid_staking
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! DATE: 2020-12-08, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("benchmarks"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/chainx
+// benchmark
+// --chain=benchmarks
+// --steps=50
+// --repeat=20
+// --pallet=xpallet_mining_liquid_staking
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./xpallets/mining/liquid-staking/src/weights.rs
+// --template=./scripts/xpallet-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_mining_liquid_staking.
+pub trait WeightInfo {
+    fn deposit() -> Weight;
+    fn redeem() -> Weight;
+    fn withdraw_redeemed() -> Weight;
+    fn set_targets(n: u32) -> Weight;
+    fn compound(n: u32) -> Weight;
+}
+
+/// Weights for xpallet_mining_liquid_staking using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn deposit() -> Weight {
+        (198_403_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn redeem() -> Weight {
+        (231_672_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(16 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    fn withdraw_redeemed() -> Weight {
+        (142_915_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn set_targets(n: u32) -> Weight {
+        (10_824_000 as Weight)
+            .saturating_add((2_716_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn compound(n: u32) -> Weight {
+        (18_207_000 as Weight)
+            .saturating_add((183_522_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((11 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn deposit() -> Weight {
+        (198_403_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn redeem() -> Weight {
+        (231_672_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(16 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    fn withdraw_redeemed() -> Weight {
+        (142_915_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn set_targets(n: u32) -> Weight {
+        (10_824_000 as Weight)
+            .saturating_add((2_716_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn compound(n: u32) -> Weight {
+        (18_207_000 as Weight)
+            .saturating_add((183_522_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((11 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+    }
+}
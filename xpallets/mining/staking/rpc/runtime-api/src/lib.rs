@@ -9,8 +9,10 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use codec::Codec;
 
+pub use chainx_primitives::AssetId;
 pub use xpallet_mining_staking::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger, VoteWeight,
+    EraIndex, EraRewards, MiningYield, NominatorInfo, NominatorLedger, StakeSlash, Unbonded,
+    ValidatorInfo, ValidatorLedger, VoteWeight,
 };
 
 sp_api::decl_runtime_apis! {
@@ -36,5 +38,17 @@ sp_api::decl_runtime_apis! {
 
         /// Get individual nominator information given the nominator AccountId.
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber>;
+
+        /// Get the stake slashes of the equivocation offences reported in the given era.
+        fn stake_slashes(era: EraIndex) -> Vec<StakeSlash<AccountId, Balance>>;
+
+        /// Get the rewards minted in the given era.
+        fn era_rewards(era: EraIndex) -> EraRewards<AccountId, Balance>;
+
+        /// Get the estimated yield of nominating each active validator.
+        fn validator_yields() -> BTreeMap<AccountId, MiningYield>;
+
+        /// Get the estimated yield of each mining asset.
+        fn mining_asset_yields() -> BTreeMap<AssetId, MiningYield>;
     }
 }
@@ -17,8 +17,8 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcVoteWeight};
 
 use xpallet_mining_staking_rpc_runtime_api::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger,
-    XStakingApi as XStakingRuntimeApi,
+    AssetId, EraIndex, EraRewards, MiningYield, NominatorInfo, NominatorLedger, StakeSlash,
+    Unbonded, ValidatorInfo, ValidatorLedger, XStakingApi as XStakingRuntimeApi,
 };
 
 /// XStaking RPC methods.
@@ -74,6 +74,30 @@ where
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Result<NominatorInfo<BlockNumber>>;
+
+    /// Get the stake slashes of the equivocation offences reported in the given era.
+    #[rpc(name = "xstaking_getStakeSlashes")]
+    fn stake_slashes(
+        &self,
+        era: EraIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<StakeSlash<AccountId, RpcBalance<Balance>>>>;
+
+    /// Get the rewards minted in the given era.
+    #[rpc(name = "xstaking_eraRewards")]
+    fn era_rewards(
+        &self,
+        era: EraIndex,
+        at: Option<BlockHash>,
+    ) -> Result<EraRewards<AccountId, RpcBalance<Balance>>>;
+
+    /// Get the estimated yield of nominating each active validator.
+    #[rpc(name = "xstaking_getValidatorYields")]
+    fn validator_yields(&self, at: Option<BlockHash>) -> Result<BTreeMap<AccountId, MiningYield>>;
+
+    /// Get the estimated yield of each mining asset.
+    #[rpc(name = "xstaking_getMiningAssetYields")]
+    fn mining_asset_yields(&self, at: Option<BlockHash>) -> Result<BTreeMap<AssetId, MiningYield>>;
 }
 
 /// A struct that implements the [`XStakingApi`].
@@ -131,6 +155,7 @@ where
                         self_bonded: validator.self_bonded.into(),
                         reward_pot_account: validator.reward_pot_account,
                         reward_pot_balance: validator.reward_pot_balance.into(),
+                        commission: validator.commission,
                     })
                     .collect::<Vec<_>>()
             })
@@ -159,6 +184,7 @@ where
                 self_bonded: validator.self_bonded.into(),
                 reward_pot_account: validator.reward_pot_account,
                 reward_pot_balance: validator.reward_pot_balance.into(),
+                commission: validator.commission,
             })
             .map_err(runtime_error_into_rpc_err)?)
     }
@@ -232,4 +258,83 @@ where
             .nominator_info_of(&at, who)
             .map_err(runtime_error_into_rpc_err)?)
     }
+
+    fn stake_slashes(
+        &self,
+        era: EraIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<StakeSlash<AccountId, RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .stake_slashes(&at, era)
+            .map(|slashes| {
+                slashes
+                    .into_iter()
+                    .map(|slash| StakeSlash {
+                        validator: slash.validator,
+                        fraction: slash.fraction,
+                        own: slash.own.into(),
+                        others: slash
+                            .others
+                            .into_iter()
+                            .map(|(nominator, value)| (nominator, value.into()))
+                            .collect(),
+                        apply_at: slash.apply_at,
+                        status: slash.status,
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)?)
+    }
+
+    fn era_rewards(
+        &self,
+        era: EraIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<EraRewards<AccountId, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .era_rewards(&at, era)
+            .map(|rewards| EraRewards {
+                minted: rewards.minted.into(),
+                treasury: rewards.treasury.into(),
+                vesting: rewards.vesting.into(),
+                staking: rewards.staking.into(),
+                asset_mining: rewards
+                    .asset_mining
+                    .into_iter()
+                    .map(|(asset_id, reward)| (asset_id, reward.into()))
+                    .collect(),
+                validators: rewards
+                    .validators
+                    .into_iter()
+                    .map(|(validator, reward)| (validator, reward.into()))
+                    .collect(),
+            })
+            .map_err(runtime_error_into_rpc_err)?)
+    }
+
+    fn validator_yields(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<AccountId, MiningYield>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .validator_yields(&at)
+            .map_err(runtime_error_into_rpc_err)?)
+    }
+
+    fn mining_asset_yields(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<AssetId, MiningYield>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .mining_asset_yields(&at)
+            .map_err(runtime_error_into_rpc_err)?)
+    }
 }
@@ -1,6 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use super::*;
+use crate::types::ElectionMethod;
 
 pub use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
@@ -184,6 +185,194 @@ benchmarks! {
     verify {
         assert_eq!(SessionsPerEra::get(), c);
     }
+
+    set_election_method {
+        let method = ElectionMethod::SeqPhragmen;
+    }: _(RawOrigin::Root, method)
+    verify {
+        assert_eq!(Module::<T>::election_method(), method);
+    }
+
+    claim_all {
+        let n in 1 .. 64;
+
+        let nominator = create_funded_user::<T>("claimer", n, 100 * n);
+        let mut validators = Vec::new();
+        for i in 0..n {
+            let validator = create_funded_user::<T>("claim_validator", i, 1000);
+            if !Module::<T>::is_validator(&validator) {
+                Module::<T>::register(RawOrigin::Signed(validator.clone()).into(), (1000 + i).to_be_bytes().to_vec(), 1000.into())?;
+            }
+            b_bond::<T>(nominator.clone(), validator.clone(), 10);
+
+            let validator_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&validator);
+            T::Currency::make_free_balance_be(&validator_pot, 1_000_000u32.into());
+            T::Currency::issue(1_000_000u32.into());
+            validators.push(validator);
+        }
+
+        let block_number: T::BlockNumber = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(block_number + 1.into());
+        let balance_before = T::Currency::free_balance(&nominator);
+    }: _(RawOrigin::Signed(nominator.clone()), n)
+    verify {
+        assert!(T::Currency::free_balance(&nominator) > balance_before);
+    }
+
+    set_restake {
+        let nominator = create_funded_user::<T>("nominator", u, 100);
+    }: _(RawOrigin::Signed(nominator.clone()), true)
+    verify {
+        assert!(Module::<T>::restake(&nominator));
+    }
+
+    payout_and_restake {
+        let n in 1 .. MAXIMUM_PAYOUT_BATCH_SIZE;
+
+        let validator = create_funded_user::<T>("restake_validator", 100, 1000);
+        if !Module::<T>::is_validator(&validator) {
+            Module::<T>::register(RawOrigin::Signed(validator.clone()).into(), 100u32.to_be_bytes().to_vec(), 1000.into())?;
+        }
+        let mut claims = Vec::new();
+        for i in 0..n {
+            let nominator = create_funded_user::<T>("nominator", i, 100);
+            b_bond::<T>(nominator.clone(), validator.clone(), 10);
+            Module::<T>::set_restake(RawOrigin::Signed(nominator.clone()).into(), true)?;
+            claims.push((nominator, validator.clone()));
+        }
+
+        let validator_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&validator);
+        let pot_balance = 1_000_000u32;
+        T::Currency::make_free_balance_be(&validator_pot, pot_balance.into());
+        T::Currency::issue(pot_balance.into());
+
+        let block_number: T::BlockNumber = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(block_number + 1.into());
+        let caller = create_funded_user::<T>("caller", 0, 100);
+    }: _(RawOrigin::Signed(caller), claims.clone())
+    verify {
+        for (nominator, validator) in claims {
+            assert!(Module::<T>::bonded_to(&nominator, &validator) > 10.into());
+        }
+    }
+
+    set_commission {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        let rate = Perbill::from_percent(9);
+    }: _(RawOrigin::Signed(validator.clone()), rate)
+    verify {
+        assert_eq!(Module::<T>::commission_of(&validator), rate);
+    }
+
+    set_commission_limits {
+        let limits = CommissionLimits {
+            maximum: Perbill::from_percent(20),
+            max_change_per_era: Perbill::from_percent(2),
+        };
+    }: _(RawOrigin::Root, limits)
+    verify {
+        assert_eq!(CommissionLimitsOf::get(), limits);
+    }
+
+    cancel_deferred_slash {
+        let n in 1 .. 64;
+
+        let validator = create_funded_user::<T>("slash_validator", 0, 1000);
+        let slash = StakeSlash {
+            validator,
+            fraction: Perbill::from_percent(10),
+            own: 100.into(),
+            others: Vec::new(),
+            apply_at: 1,
+            status: SlashStatus::Pending,
+        };
+        StakeSlashes::<T>::insert(0, vec![slash; n as usize]);
+        let indices = (0..n).collect::<Vec<_>>();
+    }: _(RawOrigin::Root, 0, indices)
+    verify {
+        assert!(Module::<T>::stake_slashes(0)
+            .iter()
+            .all(|slash| slash.status == SlashStatus::Cancelled));
+    }
+
+    set_slash_defer_duration {
+        let duration: EraIndex = 48;
+    }: _(RawOrigin::Root, duration)
+    verify {
+        assert_eq!(SlashDeferDuration::get(), duration);
+    }
+
+    set_nominator_slash {
+    }: _(RawOrigin::Root, true)
+    verify {
+        assert!(NominatorSlash::get());
+    }
+
+    instant_unbond {
+        let validator = create_funded_user::<T>("instant_validator", 0, 1000);
+        if !Module::<T>::is_validator(&validator) {
+            Module::<T>::register(RawOrigin::Signed(validator.clone()).into(), 200u32.to_be_bytes().to_vec(), 1000.into())?;
+        }
+        let nominator = create_funded_user::<T>("instant_nominator", 0, 100);
+        b_bond::<T>(nominator.clone(), validator.clone(), 50);
+        InstantUnbondFee::put(Perbill::from_percent(5));
+        let validator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(validator.clone());
+    }: _(RawOrigin::Signed(nominator.clone()), validator_lookup, 50.into())
+    verify {
+        assert!(Module::<T>::bonded_to(&nominator, &validator).is_zero());
+    }
+
+    set_instant_unbond_fee {
+        let rate = Some(Perbill::from_percent(5));
+    }: _(RawOrigin::Root, rate)
+    verify {
+        assert_eq!(Module::<T>::instant_unbond_fee(), rate);
+    }
+
+    set_reward_curve {
+        let n in 1 .. MAXIMUM_REWARD_CURVE_POINTS;
+        let curve = (0..n)
+            .map(|i| RewardCurvePoint { since: i * 1000, reward: (n - i).into() })
+            .collect::<Vec<_>>();
+    }: _(RawOrigin::Root, curve.clone())
+    verify {
+        assert_eq!(Module::<T>::reward_curve(), curve);
+    }
+
+    set_vesting_share {
+        let share = Some(Perbill::from_percent(10));
+    }: _(RawOrigin::Root, share)
+    verify {
+        assert_eq!(Module::<T>::vesting_share_ratio(), share);
+    }
+
+    set_global_distribution_ratio {
+        let ratio = GlobalDistribution { treasury: 1, mining: 9 };
+    }: _(RawOrigin::Root, ratio)
+    verify {
+        assert_eq!(Module::<T>::global_distribution_ratio(), ratio);
+    }
+
+    new_era {
+        let c in 1 .. 100;
+        let v in 1 .. MAXIMUM_ELECTION_VOTERS;
+
+        let validators = (0..c)
+            .map(|i| create_validator::<T>("validator", i, 1000))
+            .collect::<Vec<_>>();
+        for i in 0..v {
+            let nominator = create_funded_user::<T>("nominator", i, 100);
+            b_bond::<T>(nominator, validators[(i % c) as usize].clone(), 10);
+        }
+        crate::ElectionMethod::put(ElectionMethod::SeqPhragmen);
+        ValidatorCount::put(c);
+        let era = Module::<T>::current_era().map(|era| era + 1).unwrap_or(0);
+    }: {
+        Module::<T>::new_era(0);
+    }
+    verify {
+        assert_eq!(Module::<T>::current_era(), Some(era));
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +396,21 @@ mod tests {
             assert_ok!(test_benchmark_set_minimum_validator_count::<Test>());
             assert_ok!(test_benchmark_set_bonding_duration::<Test>());
             assert_ok!(test_benchmark_set_validator_bonding_duration::<Test>());
+            assert_ok!(test_benchmark_set_election_method::<Test>());
+            assert_ok!(test_benchmark_claim_all::<Test>());
+            assert_ok!(test_benchmark_set_restake::<Test>());
+            assert_ok!(test_benchmark_payout_and_restake::<Test>());
+            assert_ok!(test_benchmark_set_commission::<Test>());
+            assert_ok!(test_benchmark_set_commission_limits::<Test>());
+            assert_ok!(test_benchmark_cancel_deferred_slash::<Test>());
+            assert_ok!(test_benchmark_set_slash_defer_duration::<Test>());
+            assert_ok!(test_benchmark_set_nominator_slash::<Test>());
+            assert_ok!(test_benchmark_instant_unbond::<Test>());
+            assert_ok!(test_benchmark_set_instant_unbond_fee::<Test>());
+            assert_ok!(test_benchmark_set_reward_curve::<Test>());
+            assert_ok!(test_benchmark_set_vesting_share::<Test>());
+            assert_ok!(test_benchmark_set_global_distribution_ratio::<Test>());
+            assert_ok!(test_benchmark_new_era::<Test>());
         });
     }
 }
@@ -1,6 +1,9 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use frame_support::traits::LockIdentifier;
+use sp_runtime::Perbill;
+
+use crate::EraIndex;
 
 pub const STAKING_ID: LockIdentifier = *b"staking ";
 
@@ -31,6 +34,9 @@ pub const DEFAULT_MAXIMUM_UNBONDED_CHUNK_SIZE: u32 = 10;
 /// the daily Staking earnings does not change.
 pub const DEFAULT_BLOCKS_PER_SESSION: u64 = 50;
 
+/// The number of sessions per year, used for estimating the annualised yield.
+pub const SESSIONS_PER_YEAR: u32 = 12 * 24 * 365;
+
 /// The default bonding duration for regular staker is 3 days.
 ///
 /// The staker can unbond the staked balances, but these balances will be free immediately,
@@ -39,3 +45,29 @@ pub const DEFAULT_BONDING_DURATION: u64 = DEFAULT_BLOCKS_PER_SESSION * 12 * 24 *
 
 /// The default bonding duration for validator is 3 * 10 days.
 pub const DEFAULT_VALIDATOR_BONDING_DURATION: u64 = DEFAULT_BONDING_DURATION * 10;
+
+/// The number of eras to keep in the reward history, i.e., 30 days with 1 hour per era.
+pub const DEFAULT_ERA_REWARDS_DEPTH: u32 = 24 * 30;
+
+/// The default number of eras that the stake slashes are deferred by, i.e., 1 day.
+///
+/// It has to be shorter than the bonding duration (3 days), so that the balances unbonded
+/// by the offenders during the deferral window are still locked when the slash is applied.
+pub const DEFAULT_SLASH_DEFER_DURATION: EraIndex = 24;
+
+/// The number of iterations to balance the stake distribution of the sequential Phragmén.
+pub const PHRAGMEN_BALANCING_ITERATIONS: usize = 10;
+
+/// The maximum number of voters taken into account by the sequential Phragmén.
+pub const MAXIMUM_ELECTION_VOTERS: u32 = 1024;
+
+/// The maximum number of claims in one `payout_and_restake`.
+pub const MAXIMUM_PAYOUT_BATCH_SIZE: u32 = 64;
+
+/// The maximum number of points of the session reward curve.
+pub const MAXIMUM_REWARD_CURVE_POINTS: u32 = 64;
+
+/// The commission of the validators which have never set their own one.
+///
+/// Validator themselves directly gain 10% of the reward, the rest 90% is for the reward pot.
+pub const DEFAULT_VALIDATOR_COMMISSION: Perbill = Perbill::from_percent(10);
@@ -1,6 +1,10 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use super::*;
+use crate::types::ElectionMethod;
+use frame_support::{storage::IterableStorageDoubleMap, weights::DispatchClass};
+use sp_npos_elections::{seq_phragmen, ElectionResult, VoteWeight};
+use sp_std::collections::btree_set::BTreeSet;
 
 impl<T: Trait> Module<T> {
     /// Returns a new validator set for the new era.
@@ -58,8 +62,8 @@ impl<T: Trait> Module<T> {
 
     /// Selects the new validator set at the end of the era.
     ///
-    /// Order potential validators by their total nominations and
-    /// choose the top-most ValidatorCount::get() of them.
+    /// Elect at most ValidatorCount::get() validators from the qualified candidates
+    /// using the current `ElectionMethod`.
     ///
     /// This should only be called at the end of an era.
     fn select_and_update_validators(_current_era: EraIndex) -> Option<Vec<T::AccountId>> {
@@ -68,21 +72,124 @@ impl<T: Trait> Module<T> {
         let candidates = Self::filter_out_candidates();
         debug!("[select_and_update_validators] candidates:{:?}", candidates);
 
+        let candidate_count = candidates.len() as u32;
+
         // Avoid reevaluate validator set if it would leave us with fewer than the minimum
         // needed validators.
         if candidates.len() < Self::reasonable_minimum_validator_count() as usize {
+            Self::register_election_weight(candidate_count, 0);
             return None;
         }
 
         let desired_validator_count = ValidatorCount::get() as usize;
 
-        let validators = candidates
+        let (validators, voter_count) = match Self::election_method() {
+            ElectionMethod::TopVotes => (
+                Self::elect_top_votes(candidates, desired_validator_count),
+                0,
+            ),
+            ElectionMethod::SeqPhragmen => {
+                Self::elect_seq_phragmen(candidates, desired_validator_count)
+            }
+        };
+        Self::register_election_weight(candidate_count, voter_count);
+
+        // Always return Some(new_validators).
+        Some(validators)
+    }
+
+    /// Charges the weight of election to the current block.
+    ///
+    /// The election is triggered by the session hook in `on_initialize` of Session,
+    /// which does not know about the weight of Staking.
+    fn register_election_weight(candidate_count: u32, voter_count: u32) {
+        <frame_system::Module<T>>::register_extra_weight_unchecked(
+            T::WeightInfo::new_era(candidate_count, voter_count),
+            DispatchClass::Mandatory,
+        );
+    }
+
+    fn elect_top_votes(
+        candidates: Vec<(BalanceOf<T>, T::AccountId)>,
+        desired_validator_count: usize,
+    ) -> Vec<T::AccountId> {
+        candidates
             .into_iter()
             .take(desired_validator_count)
             .map(|(_, v)| v)
-            .collect::<Vec<_>>();
+            .collect()
+    }
 
-        // Always return Some(new_validators).
-        Some(validators)
+    /// Elects the validators using the sequential Phragmén.
+    ///
+    /// The total nominations of a nominator to the candidates are regarded as its budget, which
+    /// can be spread among all the candidates it nominates. Falls back to `TopVotes` on failure.
+    ///
+    /// The self-votes of the candidates are always taken into account, at most
+    /// `MAXIMUM_ELECTION_VOTERS` of the other voters with the most budget are counted.
+    ///
+    /// Returns the elected validators and the number of voters.
+    pub(crate) fn elect_seq_phragmen(
+        candidates: Vec<(BalanceOf<T>, T::AccountId)>,
+        desired_validator_count: usize,
+    ) -> (Vec<T::AccountId>, u32) {
+        let candidate_set = candidates
+            .iter()
+            .map(|(_, v)| v.clone())
+            .collect::<BTreeSet<_>>();
+
+        let mut voters = BTreeMap::<T::AccountId, (VoteWeight, Vec<T::AccountId>)>::new();
+        for (_, candidate) in candidates.iter() {
+            let nominators = sp_std::iter::once(candidate.clone())
+                .chain(NominatorsOf::<T>::iter_prefix(candidate).map(|(nominator, _)| nominator));
+            for nominator in nominators {
+                let nomination = Self::bonded_to(&nominator, candidate);
+                if nomination.is_zero() {
+                    continue;
+                }
+                let (budget, targets) = voters.entry(nominator).or_default();
+                *budget = budget.saturating_add(nomination.saturated_into::<VoteWeight>());
+                targets.push(candidate.clone());
+            }
+        }
+        let voter_count = voters.len() as u32;
+        let voters =
+            Self::top_election_voters(voters, &candidate_set, MAXIMUM_ELECTION_VOTERS as usize);
+
+        match seq_phragmen::<T::AccountId, Perbill>(
+            desired_validator_count.min(candidate_set.len()),
+            candidate_set.into_iter().collect(),
+            voters,
+            Some((PHRAGMEN_BALANCING_ITERATIONS, 0)),
+        ) {
+            Ok(ElectionResult { winners, .. }) => {
+                (winners.into_iter().map(|(v, _)| v).collect(), voter_count)
+            }
+            Err(err) => {
+                debug!("[elect_seq_phragmen] Failed to elect, error:{:?}", err);
+                (
+                    Self::elect_top_votes(candidates, desired_validator_count),
+                    voter_count,
+                )
+            }
+        }
+    }
+
+    /// Keeps all the candidates and at most `max_nominators` of the other voters with the most
+    /// budget, the ties are broken by the order of the accounts.
+    pub(crate) fn top_election_voters(
+        voters: BTreeMap<T::AccountId, (VoteWeight, Vec<T::AccountId>)>,
+        candidates: &BTreeSet<T::AccountId>,
+        max_nominators: usize,
+    ) -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+        let (mut voters, mut nominators): (Vec<_>, Vec<_>) = voters
+            .into_iter()
+            .map(|(voter, (budget, targets))| (voter, budget, targets))
+            .partition(|(voter, _, _)| candidates.contains(voter));
+        // stable sort keeps the order of the accounts for the equal budgets
+        nominators.sort_by(|(_, b1, _), (_, b2, _)| b2.cmp(b1));
+        nominators.truncate(max_nominators);
+        voters.extend(nominators);
+        voters
     }
 }
@@ -7,8 +7,11 @@ use frame_support::weights::Weight;
 use sp_arithmetic::traits::BaseArithmetic;
 use sp_core::crypto::UncheckedFrom;
 use sp_runtime::{traits::Hash, Perbill};
-use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
+use sp_staking::offence::{
+    Kind, Offence, OffenceDetails, OffenceError, OnOffenceHandler, ReportOffence,
+};
 
+use xp_logging::error;
 use xp_mining_common::{
     generic_weight_factors, BaseMiningWeight, Claim, ComputeMiningWeight, WeightFactors, WeightType,
 };
@@ -155,6 +158,11 @@ impl<T: Trait> Module<T> {
         current_block: T::BlockNumber,
         delta: Delta<BalanceOf<T>>,
     ) {
+        if let Delta::Add(_) = delta {
+            if nominator != validator {
+                NominatorsOf::<T>::insert(validator, nominator, true);
+            }
+        }
         Nominations::<T>::mutate(nominator, validator, |claimer| {
             claimer.nomination = delta.calculate(claimer.nomination);
             claimer.last_vote_weight = new_weight;
@@ -176,6 +184,28 @@ impl<T: Trait> Module<T> {
         });
     }
 
+    /// Bond the claimed dividend back to the validator if it can still accept the votes.
+    fn try_restake(nominator: &T::AccountId, validator: &T::AccountId, dividend: BalanceOf<T>) {
+        if dividend.is_zero() {
+            return;
+        }
+        if !Self::is_validator_bonding_itself(nominator, validator) {
+            if let Err(err) = Self::check_validator_acceptable_votes_limit(validator, dividend) {
+                debug!(
+                    "[try_restake] nominator:{:?}, validator:{:?}, error:{:?}",
+                    nominator, validator, err
+                );
+                return;
+            }
+        }
+        if let Err(err) = Self::apply_bond(nominator, validator, dividend) {
+            error!(
+                "[try_restake] Failed to bond the dividend, nominator:{:?}, validator:{:?}, dividend:{:?}, error:{:?}",
+                nominator, validator, dividend, err
+            );
+        }
+    }
+
     fn update_claimer_vote_weight_on_claim(
         claimer: &T::AccountId,
         target: &T::AccountId,
@@ -216,6 +246,10 @@ impl<T: Trait> Claim<T::AccountId> for Module<T> {
         Self::update_claimer_vote_weight_on_claim(claimer, claimee, current_block);
         Self::update_claimee_vote_weight_on_claim(claimee, new_target_weight, current_block);
 
+        if Self::restake(claimer) {
+            Self::try_restake(claimer, claimee, dividend);
+        }
+
         Ok(())
     }
 }
@@ -332,7 +366,7 @@ impl<T: Trait> Module<T> {
     /// * reset `active_era.start`,
     /// * update `BondedEras` and apply slashes.
     fn start_era(_start_session: SessionIndex) {
-        let _active_era = ActiveEra::mutate(|active_era| {
+        let active_era = ActiveEra::mutate(|active_era| {
             let new_index = active_era.as_ref().map(|info| info.index + 1).unwrap_or(0);
             *active_era = Some(ActiveEraInfo {
                 index: new_index,
@@ -341,6 +375,9 @@ impl<T: Trait> Module<T> {
             });
             new_index
         });
+        Self::apply_deferred_slashes(active_era);
+        Self::prune_era_rewards(active_era);
+        T::OnNewEra::on_new_era(active_era);
     }
 
     /// Compute payout for era.
@@ -383,7 +420,8 @@ pub type IdentificationTuple<T> = (
 type Offender<T> = IdentificationTuple<T>;
 
 /// This is intended to be used with `FilterHistoricalOffences` in Substrate/Staking.
-/// In ChainX, we always apply the slash immediately, no deferred slash.
+/// In ChainX, the reward pot of offender is always slashed immediately, while the
+/// bonded stake is only slashed for the equivocation offences, see `EquivocationReporter`.
 impl<T: Trait> OnOffenceHandler<Reporter<T>, IdentificationTuple<T>, Weight> for Module<T>
 where
     T: pallet_session::Trait<ValidatorId = <T as frame_system::Trait>::AccountId>,
@@ -426,6 +464,44 @@ where
     }
 }
 
+/// The offence kinds of BABE and GRANDPA equivocations.
+const EQUIVOCATION_KINDS: [Kind; 2] = [*b"babe:equivocatio", *b"grandpa:equivoca"];
+
+/// Reports the offences to `R`, e.g., the BABE and GRANDPA equivocations.
+///
+/// The equivocating offenders reported through it are slashed on the bonded stake
+/// after a deferral window, as well as on the reward pot.
+pub struct EquivocationReporter<T, R>(sp_std::marker::PhantomData<(T, R)>);
+
+impl<T, R, O> ReportOffence<Reporter<T>, IdentificationTuple<T>, O> for EquivocationReporter<T, R>
+where
+    T: Trait,
+    R: ReportOffence<Reporter<T>, IdentificationTuple<T>, O>,
+    O: Offence<IdentificationTuple<T>>,
+{
+    fn report_offence(reporters: Vec<Reporter<T>>, offence: O) -> Result<(), OffenceError> {
+        if !EQUIVOCATION_KINDS.contains(&O::ID) {
+            return R::report_offence(reporters, offence);
+        }
+
+        let offenders = offence.offenders();
+        let slash_fraction =
+            O::slash_fraction(offenders.len() as u32, offence.validator_set_count());
+        R::report_offence(reporters, offence)?;
+
+        Module::<T>::schedule_stake_slashes(
+            offenders
+                .iter()
+                .map(|(offender, _)| (offender, slash_fraction)),
+        );
+        Ok(())
+    }
+
+    fn is_known_offence(offenders: &[IdentificationTuple<T>], time_slot: &O::TimeSlot) -> bool {
+        R::is_known_offence(offenders, time_slot)
+    }
+}
+
 /// Simple validator reward pot account determiner.
 ///
 /// Formula: `blake2_256(blake2_256(validator_pubkey) + blake2_256(registered_at))`
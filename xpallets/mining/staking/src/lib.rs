@@ -33,12 +33,13 @@ use sp_std::prelude::*;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     storage::IterableStorageMap,
-    traits::{Currency, ExistenceRequirement, Get, LockableCurrency, WithdrawReasons},
+    traits::{Currency, ExistenceRequirement, Get, Imbalance, LockableCurrency, WithdrawReasons},
+    weights::Weight,
 };
 use frame_system::{ensure_root, ensure_signed};
 use sp_runtime::{
     traits::{Convert, SaturatedConversion, Saturating, StaticLookup, Zero},
-    DispatchResult, Perbill,
+    DispatchError, DispatchResult, Perbill,
 };
 use sp_std::collections::btree_map::BTreeMap;
 
@@ -47,10 +48,12 @@ use xp_logging::debug;
 pub use xp_mining_common::RewardPotAccountFor;
 use xp_mining_common::{Claim, ComputeMiningWeight, Delta, ZeroMiningWeightError};
 use xp_mining_staking::{AssetMining, SessionIndex, UnbondedIndex};
-use xpallet_support::traits::TreasuryAccount;
+use xpallet_support::traits::{OnNewEra, TreasuryAccount, ValidatorSlasher};
 
 use self::constants::*;
-pub use self::impls::{IdentificationTuple, SimpleValidatorRewardPotAccountDeterminer};
+pub use self::impls::{
+    EquivocationReporter, IdentificationTuple, SimpleValidatorRewardPotAccountDeterminer,
+};
 pub use self::rpc::*;
 pub use self::types::*;
 pub use self::weights::WeightInfo;
@@ -80,6 +83,9 @@ pub trait Trait: frame_system::Trait {
     /// Interface for interacting with a session module.
     type SessionInterface: self::SessionInterface<Self::AccountId>;
 
+    /// Hooks for the start of a new era, e.g. the election of trustees.
+    type OnNewEra: OnNewEra;
+
     /// The number of unfinished sessions in the first halving epoch.
     ///
     /// When the ChainX 2.0 migration happens, the first halving epoch is not over yet.
@@ -131,6 +137,8 @@ decl_storage! {
             T::BlockNumber = T::BlockNumber::saturated_from::<u64>(DEFAULT_VALIDATOR_BONDING_DURATION);
 
         /// Maximum number of on-going unbonded chunk.
+        ///
+        /// Beyond that, the new unbonded balance is merged into the chunk that matures last.
         pub MaximumUnbondedChunkSize get(fn maximum_unbonded_chunk_size) config():
             u32 = DEFAULT_MAXIMUM_UNBONDED_CHUNK_SIZE;
 
@@ -150,6 +158,16 @@ decl_storage! {
         /// (Staker, Asset Miners)
         pub MiningDistributionRatio get(fn mining_distribution_ratio): MiningDistribution;
 
+        /// The piecewise session reward curve, sorted by the starting session.
+        ///
+        /// Before its first point, or if it's empty, the default halving schedule applies.
+        pub RewardCurve get(fn reward_curve): Vec<RewardCurvePoint<BalanceOf<T>>>;
+
+        /// The proportion of each session reward that goes to the vesting account.
+        ///
+        /// If not set, 20% goes to the vesting account until the first halving.
+        pub VestingShare get(fn vesting_share_ratio): Option<Perbill>;
+
         /// The map from (wannabe) validator key to the profile of that validator.
         pub Validators get(fn validators):
             map hasher(twox_64_concat) T::AccountId => ValidatorProfile<T::BlockNumber>;
@@ -163,6 +181,16 @@ decl_storage! {
             double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
             => NominatorLedger<BalanceOf<T>, VoteWeight, T::BlockNumber>;
 
+        /// The map from validator to the nominators that ever nominated it, excluding itself.
+        ///
+        /// The index of `Nominations` to find out the nominators to slash.
+        pub NominatorsOf get(fn nominators_of):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
+            => bool;
+
+        /// True if `NominatorsOf` has been built from the existing `Nominations`.
+        IsNominatorsOfIndexed: bool;
+
         /// The map from nominator to the block number of last `rebond` operation.
         pub LastRebondOf get(fn last_rebond_of):
             map hasher(twox_64_concat) T::AccountId => Option<T::BlockNumber>;
@@ -171,6 +199,9 @@ decl_storage! {
         pub Locks get(fn locks):
             map hasher(blake2_128_concat) T::AccountId => BTreeMap<LockedType, BalanceOf<T>>;
 
+        /// The method to elect the validators on new era.
+        pub ElectionMethod get(fn election_method): types::ElectionMethod;
+
         /// Mode of era forcing.
         pub ForceEra get(fn force_era) config(): Forcing;
 
@@ -194,12 +225,56 @@ decl_storage! {
         /// forcing into account.
         pub IsCurrentSessionFinal get(fn is_current_session_final): bool = false;
 
+        /// The rewards minted in the given era.
+        ///
+        /// Only the last `EraRewardsDepth` eras are kept.
+        pub ErasRewards get(fn eras_rewards):
+            map hasher(twox_64_concat) EraIndex => EraRewards<T::AccountId, BalanceOf<T>>;
+
+        /// Number of eras to keep in the reward history.
+        pub EraRewardsDepth get(fn era_rewards_depth) config(): u32 = DEFAULT_ERA_REWARDS_DEPTH;
+
         /// Offenders reported in last session.
         SessionOffenders get(fn session_offenders): Option<BTreeMap<T::AccountId, Perbill>>;
 
         /// Minimum penalty for each slash.
         pub MinimumPenalty get(fn minimum_penalty) config(): BalanceOf<T>;
 
+        /// Number of eras that the stake slashes are deferred by, during which the
+        /// slashes can be cancelled by the governance.
+        pub SlashDeferDuration get(fn slash_defer_duration) config():
+            EraIndex = DEFAULT_SLASH_DEFER_DURATION;
+
+        /// Whether the nominators of an equivocating validator are slashed too.
+        pub NominatorSlash get(fn nominator_slash): bool;
+
+        /// The stake slashes of the equivocation offences reported in the given era.
+        pub StakeSlashes get(fn stake_slashes):
+            map hasher(twox_64_concat) EraIndex => Vec<StakeSlash<T::AccountId, BalanceOf<T>>>;
+
+        /// The stake slashes to be applied at the start of the given era, as (reported_era, index).
+        pub DeferredSlashes get(fn deferred_slashes):
+            map hasher(twox_64_concat) EraIndex => Vec<(EraIndex, u32)>;
+
+        /// The number of pending stake slashes of the given validator.
+        pub PendingStakeSlashes get(fn pending_stake_slashes):
+            map hasher(twox_64_concat) T::AccountId => u32;
+
+        /// The fee rate of `instant_unbond` paid to the treasury.
+        ///
+        /// The instant unbond is disabled if not set.
+        pub InstantUnbondFee get(fn instant_unbond_fee): Option<Perbill>;
+
+        /// The nominators who prefer to bond the claimed dividend back to the validator.
+        pub Restake get(fn restake): map hasher(twox_64_concat) T::AccountId => bool;
+
+        /// The commission rates set by the validators.
+        pub Commissions get(fn commissions):
+            map hasher(twox_64_concat) T::AccountId => Option<ValidatorCommission>;
+
+        /// The maximum validator commission and its maximum change per era.
+        pub CommissionLimitsOf get(fn commission_limits): CommissionLimits;
+
         /// Immortal validators will always be elected if any.
         ///
         /// Immortals will be intialized from the genesis validators.
@@ -271,8 +346,18 @@ decl_event!(
         Withdrawn(AccountId, Balance),
         /// Offenders were forcibly to be chilled due to insufficient reward pot balance. [session_index, chilled_validators]
         ForceChilled(SessionIndex, Vec<AccountId>),
+        /// A nominator changed the restake preference. [nominator, restake]
+        RestakeSet(AccountId, bool),
         /// Unlock the unbonded withdrawal by force. [account]
         ForceAllWithdrawn(AccountId),
+        /// A validator changed the commission rate. [validator, rate]
+        CommissionSet(AccountId, Perbill),
+        /// The bonded stake was slashed due to the equivocation of validator. [nominator, validator, amount]
+        StakeSlashed(AccountId, AccountId, Balance),
+        /// A deferred stake slash was cancelled. [reported_era, index]
+        StakeSlashCancelled(EraIndex, u32),
+        /// A nominator unbonded this amount immediately by paying the fee. [nominator, validator, amount, fee]
+        InstantUnbonded(AccountId, AccountId, Balance, Balance),
     }
 );
 
@@ -319,6 +404,34 @@ decl_error! {
         XssCheckFailed,
         /// Failed to allocate the dividend.
         AllocateDividendFailed,
+        /// The commission rate exceeds the maximum commission.
+        ExcessiveCommission,
+        /// The commission rate changes more than the limit per era.
+        ExcessiveCommissionChange,
+        /// The commission rate can only be changed once per era.
+        CommissionAlreadyChanged,
+        /// The number of claims in one payout exceeds `MAXIMUM_PAYOUT_BATCH_SIZE`.
+        TooManyPayouts,
+        /// The nominations are more than the given nomination count.
+        InvalidNominationCount,
+        /// Can not find the stake slash given the era and index.
+        InvalidSlashIndex,
+        /// Only the pending stake slashes can be cancelled.
+        SlashNotPending,
+        /// The instant unbond is disabled as `InstantUnbondFee` is not set.
+        InstantUnbondDisabled,
+        /// Can not unbond the validator self-bonded votes instantly.
+        InstantUnbondSelfBondedNotAllowed,
+        /// The validator has pending stake slashes, the nominations can not be moved away at once.
+        PendingStakeSlash,
+        /// The points of reward curve must be sorted by the starting session without duplicates.
+        InvalidRewardCurve,
+        /// The reward curve has more points than `MAXIMUM_REWARD_CURVE_POINTS`.
+        TooManyRewardCurvePoints,
+        /// The sum of the distribution ratio can not be zero.
+        ZeroDistributionRatio,
+        /// The slash defer duration must be shorter than the bonding duration.
+        SlashDeferDurationTooLong,
     }
 }
 
@@ -340,6 +453,21 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            if IsNominatorsOfIndexed::get() {
+                return T::DbWeight::get().reads(1);
+            }
+            let mut nominations = 0;
+            for (nominator, validator, _) in Nominations::<T>::iter() {
+                if nominator != validator {
+                    NominatorsOf::<T>::insert(&validator, &nominator, true);
+                }
+                nominations += 1;
+            }
+            IsNominatorsOfIndexed::put(true);
+            T::DbWeight::get().reads_writes(nominations + 1, nominations + 1)
+        }
+
         /// Nominate the `target` with `value` of the origin account's balance locked.
         #[weight = T::WeightInfo::bond()]
         pub fn bond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
@@ -370,6 +498,7 @@ decl_module! {
             ensure!(Self::is_validator(&from) && Self::is_validator(&to), Error::<T>::NotValidator);
             ensure!(sender != from, Error::<T>::RebondSelfBondedNotAllowed);
             ensure!(value <= Self::bonded_to(&sender, &from), Error::<T>::InvalidRebondBalance);
+            ensure!(Self::pending_stake_slashes(&from) == 0, Error::<T>::PendingStakeSlash);
 
             if !Self::is_validator_bonding_itself(&sender, &to) {
                 Self::check_validator_acceptable_votes_limit(&to, value)?;
@@ -388,7 +517,7 @@ decl_module! {
 
         /// Unnominate the `value` of bonded balance for validator `target`.
         #[weight = T::WeightInfo::unbond()]
-        fn unbond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
+        pub fn unbond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
             let sender = ensure_signed(origin)?;
             let target = T::Lookup::lookup(target)?;
 
@@ -396,9 +525,29 @@ decl_module! {
             Self::apply_unbond(&sender, &target, value)?;
         }
 
+        /// Unnominate the `value` of bonded balance for validator `target` immediately.
+        ///
+        /// The balance is free at once instead of being locked for the bonding duration,
+        /// in exchange `InstantUnbondFee` of it is paid to the treasury.
+        #[weight = T::WeightInfo::instant_unbond()]
+        fn instant_unbond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
+            let sender = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            let fee_rate = Self::instant_unbond_fee().ok_or(Error::<T>::InstantUnbondDisabled)?;
+            ensure!(sender != target, Error::<T>::InstantUnbondSelfBondedNotAllowed);
+            ensure!(
+                Self::pending_stake_slashes(&target) == 0,
+                Error::<T>::PendingStakeSlash
+            );
+            Self::can_unbond(&sender, &target, value)?;
+
+            Self::apply_instant_unbond(&sender, &target, value, fee_rate);
+        }
+
         /// Unlock the frozen unbonded balances that are due.
         #[weight = T::WeightInfo::unlock_unbonded_withdrawal()]
-        fn unlock_unbonded_withdrawal(
+        pub fn unlock_unbonded_withdrawal(
             origin,
             target: <T::Lookup as StaticLookup>::Source,
             #[compact] unbonded_index: UnbondedIndex
@@ -426,7 +575,9 @@ decl_module! {
         }
 
         /// Claim the staking reward given the `target` validator.
-        #[weight = T::WeightInfo::claim()]
+        ///
+        /// The weight includes bonding the dividend back in case the origin prefers to restake.
+        #[weight = T::WeightInfo::claim().saturating_add(T::WeightInfo::bond())]
         fn claim(origin, target: <T::Lookup as StaticLookup>::Source) {
             let sender = ensure_signed(origin)?;
             let target = T::Lookup::lookup(target)?;
@@ -436,6 +587,75 @@ decl_module! {
             <Self as Claim<T::AccountId>>::claim(&sender, &target)?;
         }
 
+        /// Claim the staking rewards from all the validators the origin account nominates.
+        ///
+        /// `nomination_count` is the upper bound of the number of nominations, which decides
+        /// the weight of this call. Validators with nothing to claim are skipped.
+        #[weight = T::WeightInfo::claim_all(*nomination_count)
+            .saturating_add(T::WeightInfo::bond().saturating_mul(*nomination_count as Weight))]
+        fn claim_all(origin, #[compact] nomination_count: u32) {
+            let sender = ensure_signed(origin)?;
+
+            let nominees = Nominations::<T>::iter_prefix(&sender)
+                .map(|(validator, _)| validator)
+                .take(nomination_count as usize + 1)
+                .collect::<Vec<_>>();
+            ensure!(
+                nominees.len() as u32 <= nomination_count,
+                Error::<T>::InvalidNominationCount
+            );
+            let validators = nominees
+                .into_iter()
+                .filter(Self::is_validator)
+                .collect::<Vec<_>>();
+
+            for validator in validators.iter() {
+                if let Err(err) = <Self as Claim<T::AccountId>>::claim(&sender, validator) {
+                    debug!(
+                        "[claim_all] nominator:{:?}, validator:{:?}, error:{:?}",
+                        sender, validator, err
+                    );
+                }
+            }
+        }
+
+        /// Set whether the dividend would be bonded back to the validator on claim.
+        #[weight = T::WeightInfo::set_restake()]
+        fn set_restake(origin, restake: bool) {
+            let sender = ensure_signed(origin)?;
+            if restake {
+                Restake::<T>::insert(&sender, true);
+            } else {
+                Restake::<T>::remove(&sender);
+            }
+            Self::deposit_event(Event::<T>::RestakeSet(sender, restake));
+        }
+
+        /// Claim and restake the dividends for the nominators who prefer to restake.
+        ///
+        /// Anyone can pay for it, the claims of the nominators who do not prefer to restake
+        /// or have nothing to claim are skipped.
+        #[weight = T::WeightInfo::payout_and_restake(claims.len() as u32)]
+        fn payout_and_restake(origin, claims: Vec<(T::AccountId, T::AccountId)>) {
+            ensure_signed(origin)?;
+            ensure!(
+                claims.len() as u32 <= MAXIMUM_PAYOUT_BATCH_SIZE,
+                Error::<T>::TooManyPayouts
+            );
+
+            for (nominator, validator) in claims.iter() {
+                if !Self::restake(nominator) || !Self::is_validator(validator) {
+                    continue;
+                }
+                if let Err(err) = <Self as Claim<T::AccountId>>::claim(nominator, validator) {
+                    debug!(
+                        "[payout_and_restake] nominator:{:?}, validator:{:?}, error:{:?}",
+                        nominator, validator, err
+                    );
+                }
+            }
+        }
+
         /// Declare the desire to validate for the origin account.
         #[weight = T::WeightInfo::validate()]
         fn validate(origin) {
@@ -486,6 +706,24 @@ decl_module! {
             }
         }
 
+        /// Set the commission rate of the origin validator, which takes effect immediately.
+        ///
+        /// The rate can be changed once per era, by no more than `max_change_per_era`.
+        #[weight = T::WeightInfo::set_commission()]
+        fn set_commission(origin, new: Perbill) {
+            let sender = ensure_signed(origin)?;
+            ensure!(Self::is_validator(&sender), Error::<T>::NotValidator);
+
+            Self::apply_set_commission(&sender, new)?;
+            Self::deposit_event(Event::<T>::CommissionSet(sender, new));
+        }
+
+        #[weight = T::WeightInfo::set_commission_limits()]
+        fn set_commission_limits(origin, new: CommissionLimits) {
+            ensure_root(origin)?;
+            CommissionLimitsOf::put(new);
+        }
+
         #[weight = T::WeightInfo::set_validator_count()]
         fn set_validator_count(origin, #[compact] new: u32) {
             ensure_root(origin)?;
@@ -516,12 +754,107 @@ decl_module! {
             MinimumPenalty::<T>::put(new);
         }
 
+        /// Cancel the pending stake slashes reported in `era` given the indices.
+        #[weight = T::WeightInfo::cancel_deferred_slash(indices.len() as u32)]
+        fn cancel_deferred_slash(origin, #[compact] era: EraIndex, indices: Vec<u32>) {
+            ensure_root(origin)?;
+            let validators = StakeSlashes::<T>::try_mutate(era, |slashes| -> Result<Vec<T::AccountId>, DispatchError> {
+                let mut validators = Vec::with_capacity(indices.len());
+                for index in indices.iter() {
+                    let slash = slashes
+                        .get_mut(*index as usize)
+                        .ok_or(Error::<T>::InvalidSlashIndex)?;
+                    ensure!(slash.status == SlashStatus::Pending, Error::<T>::SlashNotPending);
+                    slash.status = SlashStatus::Cancelled;
+                    validators.push(slash.validator.clone());
+                }
+                Ok(validators)
+            })?;
+            for validator in validators.iter() {
+                Self::settle_pending_stake_slash(validator);
+            }
+            for index in indices {
+                Self::deposit_event(Event::<T>::StakeSlashCancelled(era, index));
+            }
+        }
+
+        #[weight = T::WeightInfo::set_instant_unbond_fee()]
+        fn set_instant_unbond_fee(origin, new: Option<Perbill>) {
+            ensure_root(origin)?;
+            match new {
+                Some(rate) => InstantUnbondFee::put(rate),
+                None => InstantUnbondFee::kill(),
+            }
+        }
+
+        #[weight = T::WeightInfo::set_slash_defer_duration()]
+        fn set_slash_defer_duration(origin, #[compact] new: EraIndex) {
+            ensure_root(origin)?;
+            // Otherwise the unbonded balances can be withdrawn before the slash is applied.
+            let era_length = T::SessionDuration::get().saturating_mul(Self::sessions_per_era().into());
+            ensure!(
+                era_length.saturating_mul(new.into()) < Self::bonding_duration(),
+                Error::<T>::SlashDeferDurationTooLong
+            );
+            SlashDeferDuration::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_nominator_slash()]
+        fn set_nominator_slash(origin, enabled: bool) {
+            ensure_root(origin)?;
+            NominatorSlash::put(enabled);
+        }
+
+        #[weight = T::WeightInfo::set_election_method()]
+        fn set_election_method(origin, new: types::ElectionMethod) {
+            ensure_root(origin)?;
+            ElectionMethod::put(new);
+        }
+
         #[weight = T::WeightInfo::set_sessions_per_era()]
         fn set_sessions_per_era(origin, #[compact] new: SessionIndex) {
             ensure_root(origin)?;
             SessionsPerEra::put(new);
         }
 
+        /// Set the session reward curve, the default halving schedule is restored if empty.
+        #[weight = T::WeightInfo::set_reward_curve(curve.len() as u32)]
+        fn set_reward_curve(origin, curve: Vec<RewardCurvePoint<BalanceOf<T>>>) {
+            ensure_root(origin)?;
+            ensure!(
+                curve.len() as u32 <= MAXIMUM_REWARD_CURVE_POINTS,
+                Error::<T>::TooManyRewardCurvePoints
+            );
+            ensure!(
+                curve.windows(2).all(|w| w[0].since < w[1].since),
+                Error::<T>::InvalidRewardCurve
+            );
+            if curve.is_empty() {
+                RewardCurve::<T>::kill();
+            } else {
+                RewardCurve::<T>::put(curve);
+            }
+        }
+
+        #[weight = T::WeightInfo::set_vesting_share()]
+        fn set_vesting_share(origin, new: Option<Perbill>) {
+            ensure_root(origin)?;
+            match new {
+                Some(share) => VestingShare::put(share),
+                None => VestingShare::kill(),
+            }
+        }
+
+        #[weight = T::WeightInfo::set_global_distribution_ratio()]
+        fn set_global_distribution_ratio(origin, new: GlobalDistribution) {
+            ensure_root(origin)?;
+            ensure!(
+                new.treasury.saturating_add(new.mining) > 0,
+                Error::<T>::ZeroDistributionRatio
+            );
+            GlobalDistributionRatio::put(new);
+        }
+
         #[weight = 10_000_000]
         fn set_immortals(origin, new: Vec<T::AccountId>) {
             ensure_root(origin)?;
@@ -582,6 +915,9 @@ pub trait SessionInterface<AccountId>: frame_system::Trait {
 
     /// Get the validators from session.
     fn validators() -> Vec<AccountId>;
+
+    /// Get the current session index.
+    fn current_index() -> SessionIndex;
 }
 
 impl<T: Trait> SessionInterface<<T as frame_system::Trait>::AccountId> for T
@@ -601,6 +937,10 @@ where
     fn validators() -> Vec<<T as frame_system::Trait>::AccountId> {
         <pallet_session::Module<T>>::validators()
     }
+
+    fn current_index() -> SessionIndex {
+        <pallet_session::Module<T>>::current_index()
+    }
 }
 
 impl<T: Trait> xpallet_support::traits::Validator<T::AccountId> for Module<T> {
@@ -611,6 +951,25 @@ impl<T: Trait> xpallet_support::traits::Validator<T::AccountId> for Module<T> {
     fn validator_for(name: &[u8]) -> Option<T::AccountId> {
         Self::validator_for(name)
     }
+
+    fn ranked_validators() -> Vec<T::AccountId> {
+        let mut validators = Self::active_validator_votes().collect::<Vec<_>>();
+        validators.sort_by(|(_, v1), (_, v2)| v2.cmp(v1));
+        validators.into_iter().map(|(v, _)| v).collect()
+    }
+}
+
+impl<T: Trait> ValidatorSlasher<T::AccountId, BalanceOf<T>> for Module<T> {
+    fn slash(who: &T::AccountId, value: BalanceOf<T>) -> BalanceOf<T> {
+        let slasher = Slasher::<T>::new(T::TreasuryAccount::treasury_account());
+        match slasher.try_slash(who, value) {
+            SlashOutcome::Slashed(slashed) | SlashOutcome::InsufficientSlash(slashed) => slashed,
+            SlashOutcome::SlashFailed(e) => {
+                debug!("Slash {:?} for {:?} somehow failed: {:?}", who, value, e);
+                Zero::zero()
+            }
+        }
+    }
 }
 
 impl<T: Trait> Module<T> {
@@ -661,6 +1020,9 @@ impl<T: Trait> Module<T> {
             Nominations::<T>::mutate(sender, target, |nominator| {
                 nominator.nomination = value;
             });
+            if sender != target {
+                NominatorsOf::<T>::insert(target, sender, true);
+            }
         }
         Ok(())
     }
@@ -676,11 +1038,6 @@ impl<T: Trait> Module<T> {
         // We can not reuse can_unbond() as the target can has no bond but has unbonds.
         // Self::can_unbond(sender, target, value)?;
         ensure!(Self::is_validator(target), Error::<T>::NotValidator);
-        ensure!(
-            Self::unbonded_chunks_of(sender, target).len()
-                < Self::maximum_unbonded_chunk_size() as usize,
-            Error::<T>::NoMoreUnbondChunks
-        );
         Self::unbond_reserve(sender, value)?;
         Self::mutate_unbonded_chunks(sender, target, value, locked_until);
         Ok(())
@@ -754,6 +1111,45 @@ impl<T: Trait> Module<T> {
         *Self::locks(who).entry(LockedType::Bonded).or_default()
     }
 
+    fn apply_set_commission(who: &T::AccountId, new: Perbill) -> DispatchResult {
+        let limits = Self::commission_limits();
+        ensure!(new <= limits.maximum, Error::<T>::ExcessiveCommission);
+
+        let current_era = Self::active_era().map(|era| era.index).unwrap_or(0);
+        let current = Self::commissions(who);
+        if let Some(commission) = current {
+            ensure!(
+                commission.updated_at < current_era,
+                Error::<T>::CommissionAlreadyChanged
+            );
+        }
+        let old = current
+            .map(|commission| commission.rate)
+            .unwrap_or(DEFAULT_VALIDATOR_COMMISSION);
+        let change = new.max(old).saturating_sub(new.min(old));
+        ensure!(
+            change <= limits.max_change_per_era,
+            Error::<T>::ExcessiveCommissionChange
+        );
+
+        Commissions::<T>::insert(
+            who,
+            ValidatorCommission {
+                rate: new,
+                updated_at: current_era,
+            },
+        );
+        Ok(())
+    }
+
+    /// Returns the commission rate of the given validator, capped by the current maximum.
+    pub fn commission_of(validator: &T::AccountId) -> Perbill {
+        Self::commissions(validator)
+            .map(|commission| commission.rate)
+            .unwrap_or(DEFAULT_VALIDATOR_COMMISSION)
+            .min(Self::commission_limits().maximum)
+    }
+
     /// Returns the associated reward pot account for the given validator.
     #[inline]
     pub fn reward_pot_for(validator: &T::AccountId) -> T::AccountId {
@@ -935,11 +1331,6 @@ impl<T: Trait> Module<T> {
             value <= Self::bonded_to(sender, target),
             Error::<T>::InvalidUnbondBalance
         );
-        ensure!(
-            Self::unbonded_chunks_of(sender, target).len()
-                < Self::maximum_unbonded_chunk_size() as usize,
-            Error::<T>::NoMoreUnbondChunks
-        );
         Ok(())
     }
 
@@ -1039,18 +1430,21 @@ impl<T: Trait> Module<T> {
         value: BalanceOf<T>,
         locked_until: T::BlockNumber,
     ) {
+        let maximum_chunks = Self::maximum_unbonded_chunk_size().max(1) as usize;
         Nominations::<T>::mutate(who, target, |nominator| {
-            if let Some(idx) = nominator
-                .unbonded_chunks
-                .iter()
-                .position(|x| x.locked_until == locked_until)
-            {
-                nominator.unbonded_chunks[idx].value += value;
-            } else {
-                nominator.unbonded_chunks.push(Unbonded {
+            let chunks = &mut nominator.unbonded_chunks;
+            if let Some(idx) = chunks.iter().position(|x| x.locked_until == locked_until) {
+                chunks[idx].value += value;
+            } else if chunks.len() < maximum_chunks {
+                chunks.push(Unbonded {
                     value,
                     locked_until,
                 });
+            } else if let Some(latest) = chunks.iter_mut().max_by_key(|x| x.locked_until) {
+                // No more chunks, the one maturing last takes the new value and
+                // its maturity is postponed if necessary.
+                latest.value += value;
+                latest.locked_until = latest.locked_until.max(locked_until);
             }
         });
     }
@@ -1085,6 +1479,52 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    fn apply_instant_unbond(
+        who: &T::AccountId,
+        target: &T::AccountId,
+        value: BalanceOf<T>,
+        fee_rate: Perbill,
+    ) {
+        Self::release_bonded(who, value);
+        Self::update_vote_weight(who, target, Delta::Sub(value));
+
+        let fee = fee_rate * value;
+        if !fee.is_zero() {
+            Self::slash_to_treasury(who, fee);
+        }
+
+        Self::deposit_event(Event::<T>::InstantUnbonded(
+            who.clone(),
+            target.clone(),
+            value,
+            fee,
+        ));
+    }
+
+    /// Frees `value` of the bonded balances immediately, skipping the bonding duration.
+    fn release_bonded(who: &T::AccountId, value: BalanceOf<T>) {
+        Locks::<T>::mutate(who, |locks| {
+            let bonded = locks.entry(LockedType::Bonded).or_default();
+            *bonded = bonded.saturating_sub(value);
+
+            let staking_locked = locks
+                .values()
+                .fold(Zero::zero(), |acc: BalanceOf<T>, x| acc + *x);
+            Self::set_lock(who, staking_locked);
+        });
+    }
+
+    /// Moves `value` of the free balance of `who` to the treasury regardless of the locks.
+    ///
+    /// The total issuance stays the same.
+    fn slash_to_treasury(who: &T::AccountId, value: BalanceOf<T>) {
+        let (imbalance, _) = T::Currency::slash(who, value);
+        let _ = T::Currency::deposit_creating(
+            &T::TreasuryAccount::treasury_account(),
+            imbalance.peek(),
+        );
+    }
+
     fn apply_unlock_unbonded_withdrawal(who: &T::AccountId, value: BalanceOf<T>) {
         let new_bonded = Self::total_locked_of(who) - value;
         Self::set_lock(who, new_bonded);
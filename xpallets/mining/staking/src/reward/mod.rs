@@ -1,6 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use super::*;
+use sp_runtime::PerThing;
 use xp_logging::debug;
 use xp_mining_staking::SessionIndex;
 
@@ -19,8 +20,8 @@ impl<T: Trait> Module<T> {
         current_index > T::MigrationSessionOffset::get()
     }
 
-    /// Returns the total reward for the session, assuming it ends with this block.
-    pub(crate) fn this_session_reward(current_index: SessionIndex) -> BalanceOf<T> {
+    /// Returns the session reward of the default halving schedule.
+    fn halving_session_reward(current_index: SessionIndex) -> BalanceOf<T> {
         let halving_epoch = if Self::first_halving_epoch_arrived(current_index) {
             (current_index - T::MigrationSessionOffset::get() - 1) / SESSIONS_PER_ROUND + 1
         } else {
@@ -30,6 +31,19 @@ impl<T: Trait> Module<T> {
         INITIAL_REWARD.saturated_into::<BalanceOf<T>>() / Self::pow2(halving_epoch)
     }
 
+    /// Returns the total reward for the session, assuming it ends with this block.
+    ///
+    /// The reward follows `RewardCurve` if the session has reached its first point,
+    /// otherwise the default halving schedule.
+    pub(crate) fn this_session_reward(current_index: SessionIndex) -> BalanceOf<T> {
+        Self::reward_curve()
+            .iter()
+            .rev()
+            .find(|point| point.since <= current_index)
+            .map(|point| point.reward)
+            .unwrap_or_else(|| Self::halving_session_reward(current_index))
+    }
+
     /// Issue new fresh PCX.
     #[inline]
     pub(crate) fn mint(receiver: &T::AccountId, value: BalanceOf<T>) {
@@ -39,14 +53,14 @@ impl<T: Trait> Module<T> {
 
     /// Reward a (potential) validator by a specific amount.
     ///
-    /// Add the reward to their balance, and their reward pot, pro-rata.
+    /// Add the commission to their balance, and the rest to their reward pot.
     fn apply_reward_validator(who: &T::AccountId, reward: BalanceOf<T>) {
-        // Validator themselves can only directly gain 10%, the rest 90% is for the reward pot.
-        let off_the_table = reward.saturated_into::<BalanceOf<T>>() / 10u32.saturated_into();
+        // Validator themselves can only directly gain the commission, the rest is for the reward pot.
+        let off_the_table = Self::commission_of(who).mul_floor(reward);
         Self::mint(who, off_the_table);
         debug!("💸 Mint validator({:?}):{:?}", who, off_the_table);
 
-        // Issue the rest 90% to validator's reward pot.
+        // Issue the rest to validator's reward pot.
         let to_reward_pot = reward - off_the_table;
         let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(who);
         Self::mint(&reward_pot, to_reward_pot);
@@ -62,20 +76,57 @@ impl<T: Trait> Module<T> {
         Self::apply_reward_validator(validator, reward);
     }
 
-    /// 20% reward of each session is for the vesting schedule in the first halving epoch.
+    /// Returns the vesting share of session reward.
+    ///
+    /// If `VestingShare` is not set, 20% reward of each session is for the vesting
+    /// schedule in the first halving epoch.
+    fn vesting_share(
+        current_index: SessionIndex,
+        this_session_reward: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        match Self::vesting_share_ratio() {
+            Some(share) => share.mul_floor(this_session_reward),
+            None if !Self::first_halving_epoch_arrived(current_index) => {
+                this_session_reward / 5u32.saturated_into::<BalanceOf<T>>()
+            }
+            None => Zero::zero(),
+        }
+    }
+
+    /// Mints the vesting share of session reward, returns the rest of it.
     pub(crate) fn try_vesting(
         current_index: SessionIndex,
         this_session_reward: BalanceOf<T>,
     ) -> BalanceOf<T> {
-        if !Self::first_halving_epoch_arrived(current_index) {
-            let to_vesting = this_session_reward / 5u32.saturated_into::<BalanceOf<T>>();
+        let to_vesting = Self::vesting_share(current_index, this_session_reward);
+        if !to_vesting.is_zero() {
             let vesting_account = Self::vesting_account();
             Self::mint(&vesting_account, to_vesting);
             debug!("💸 Mint vesting({:?}):{:?}", vesting_account, to_vesting);
-            this_session_reward - to_vesting
-        } else {
-            this_session_reward
         }
+        this_session_reward - to_vesting
+    }
+
+    /// Returns the estimated (staking, asset mining) rewards of the current session.
+    pub(crate) fn estimated_mining_rewards() -> (BalanceOf<T>, BalanceOf<T>) {
+        let current_index = T::SessionInterface::current_index();
+        let this_session_reward = Self::this_session_reward(current_index);
+        let session_reward =
+            this_session_reward - Self::vesting_share(current_index, this_session_reward);
+
+        let (_, mining_reward) =
+            Self::global_distribution_ratio().calc_rewards::<T>(session_reward);
+
+        let mining_distribution = Self::mining_distribution_ratio();
+        let staking_reward = mining_distribution.calc_staking_reward::<T>(mining_reward);
+        let max_asset_mining_reward = mining_reward - staking_reward;
+        let asset_mining_reward =
+            match mining_distribution.has_treasury_extra::<T>(max_asset_mining_reward) {
+                Some(treasury_extra) => max_asset_mining_reward - treasury_extra,
+                None => max_asset_mining_reward,
+            };
+
+        (staking_reward, asset_mining_reward)
     }
 
     /// Distribute the session reward to all the receivers, returns the total reward for validators.
@@ -86,6 +137,25 @@ impl<T: Trait> Module<T> {
 
         let session_reward = Self::try_vesting(session_index, this_session_reward);
 
-        Self::distribute_session_reward_impl_09(session_reward)
+        let era = Self::active_era()
+            .map(|active_era| active_era.index)
+            .unwrap_or(0);
+        let mut era_rewards = Self::eras_rewards(era);
+        era_rewards.minted += this_session_reward;
+        era_rewards.vesting += this_session_reward - session_reward;
+
+        let validator_rewards =
+            Self::distribute_session_reward_impl_09(session_reward, &mut era_rewards);
+
+        ErasRewards::<T>::insert(era, era_rewards);
+
+        validator_rewards
+    }
+
+    /// Removes the reward history that is older than `EraRewardsDepth`.
+    pub(crate) fn prune_era_rewards(active_era: EraIndex) {
+        if let Some(stale_era) = active_era.checked_sub(Self::era_rewards_depth()) {
+            ErasRewards::<T>::remove(stale_era);
+        }
     }
 }
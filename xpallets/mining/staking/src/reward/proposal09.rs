@@ -47,7 +47,10 @@ impl<T: Trait> Module<T> {
     }
 
     /// Distributes the invididual asset mining reward, returns the unpaid asset mining rewards.
-    fn distribute_to_mining_assets(total_reward: BalanceOf<T>) -> BalanceOf<T> {
+    fn distribute_to_mining_assets(
+        total_reward: BalanceOf<T>,
+        era_rewards: &mut EraRewards<T::AccountId, BalanceOf<T>>,
+    ) -> BalanceOf<T> {
         let asset_mining_info = T::AssetMining::asset_mining_power();
 
         // [PASS*] No risk of sum overflow practically.
@@ -64,6 +67,7 @@ impl<T: Trait> Module<T> {
                 let reward =
                     Self::calc_invididual_asset_mining_reward(total_reward, power, total_power);
                 T::AssetMining::reward(asset_id, reward);
+                *era_rewards.asset_mining.entry(asset_id).or_default() += reward;
                 total_power -= power;
                 total_reward -= reward;
             }
@@ -75,6 +79,7 @@ impl<T: Trait> Module<T> {
     /// Reward to all the active validators pro rata.
     fn distribute_to_active_validators(
         session_reward: BalanceOf<T>,
+        era_rewards: &mut EraRewards<T::AccountId, BalanceOf<T>>,
     ) -> Vec<(T::AccountId, BalanceOf<T>)> {
         let active_validators = Self::active_validator_votes().collect::<Vec<_>>();
         let mut total_stake = active_validators
@@ -89,6 +94,8 @@ impl<T: Trait> Module<T> {
                     let reward =
                         Self::calc_individual_staking_reward(total_reward, stake, total_stake);
                     Self::reward_active_validator(&validator, reward);
+                    era_rewards.staking += reward;
+                    *era_rewards.validators.entry(validator.clone()).or_default() += reward;
                     total_stake -= stake;
                     total_reward -= reward;
                     Some((validator, reward))
@@ -104,30 +111,33 @@ impl<T: Trait> Module<T> {
     fn distribute_mining_rewards(
         total: BalanceOf<T>,
         treasury_account: &T::AccountId,
+        era_rewards: &mut EraRewards<T::AccountId, BalanceOf<T>>,
     ) -> Vec<(T::AccountId, BalanceOf<T>)> {
         let mining_distribution = Self::mining_distribution_ratio();
         let staking_reward = mining_distribution.calc_staking_reward::<T>(total);
         let max_asset_mining_reward = total - staking_reward;
 
-        let validator_rewards = Self::distribute_to_active_validators(staking_reward);
+        let validator_rewards = Self::distribute_to_active_validators(staking_reward, era_rewards);
 
         let real_asset_mining_reward = if let Some(treasury_extra) =
             mining_distribution.has_treasury_extra::<T>(max_asset_mining_reward)
         {
             Self::mint(treasury_account, treasury_extra);
+            era_rewards.treasury += treasury_extra;
             max_asset_mining_reward - treasury_extra
         } else {
             max_asset_mining_reward
         };
 
         let unpaid_asset_mining_reward =
-            Self::distribute_to_mining_assets(real_asset_mining_reward);
+            Self::distribute_to_mining_assets(real_asset_mining_reward, era_rewards);
         if !unpaid_asset_mining_reward.is_zero() {
             debug!(
                 "[distribute_mining_rewards] unpaid_asset_mining_reward:{:?}",
                 unpaid_asset_mining_reward
             );
             Self::mint(treasury_account, unpaid_asset_mining_reward);
+            era_rewards.treasury += unpaid_asset_mining_reward;
         }
 
         validator_rewards
@@ -135,9 +145,11 @@ impl<T: Trait> Module<T> {
 
     /// Main minting logic.
     ///
-    /// Returns the reward balance minted specifically for Staking.
+    /// Returns the reward balance minted specifically for Staking, the shares of all
+    /// the receivers are accumulated into `era_rewards`.
     pub(super) fn distribute_session_reward_impl_09(
         session_reward: BalanceOf<T>,
+        era_rewards: &mut EraRewards<T::AccountId, BalanceOf<T>>,
     ) -> Vec<(T::AccountId, BalanceOf<T>)> {
         let global_distribution = Self::global_distribution_ratio();
         let (treasury_reward, mining_reward) =
@@ -147,13 +159,14 @@ impl<T: Trait> Module<T> {
         let treasury_account = T::TreasuryAccount::treasury_account();
         if !treasury_reward.is_zero() {
             Self::mint(&treasury_account, treasury_reward);
+            era_rewards.treasury += treasury_reward;
         }
 
         // -> Mining
         //      |-> XBTC(Asset Mining)
         //      |-> PCX(Staking)
         if !mining_reward.is_zero() {
-            return Self::distribute_mining_rewards(mining_reward, &treasury_account);
+            return Self::distribute_mining_rewards(mining_reward, &treasury_account, era_rewards);
         }
 
         Default::default()
@@ -7,13 +7,19 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use frame_support::storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap};
-use sp_runtime::RuntimeDebug;
+use sp_arithmetic::{FixedPointNumber, FixedU128};
+use sp_runtime::{
+    traits::{SaturatedConversion, Saturating},
+    PerThing, Perbill, RuntimeDebug,
+};
 
+use chainx_primitives::AssetId;
 use xp_mining_common::RewardPotAccountFor;
+use xp_mining_staking::AssetMining;
 
 use crate::{
-    types::*, BalanceOf, LastRebondOf, Module, Nominations, SessionInterface, Trait,
-    ValidatorLedgers, Validators,
+    constants::SESSIONS_PER_YEAR, types::*, BalanceOf, LastRebondOf, Module, Nominations,
+    SessionInterface, Trait, ValidatorLedgers, Validators,
 };
 
 /// Total information about a validator.
@@ -35,6 +41,8 @@ pub struct ValidatorInfo<AccountId, Balance, VoteWeight, BlockNumber> {
     pub reward_pot_account: AccountId,
     /// Balance of the reward pot account.
     pub reward_pot_balance: Balance,
+    /// The proportion of the reward that the validator takes directly.
+    pub commission: Perbill,
 }
 
 /// Profile of staking nominator.
@@ -46,6 +54,41 @@ pub struct NominatorInfo<BlockNumber> {
     pub last_rebond: Option<BlockNumber>,
 }
 
+/// Estimated annualised yield of staking or asset mining, in basis points.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MiningYield {
+    /// Annual percentage rate, assuming the dividends are never claimed.
+    pub apr: u32,
+    /// Annual percentage yield, assuming the dividends are claimed and bonded back daily.
+    pub apy: u32,
+}
+
+impl MiningYield {
+    /// Basis points per unit.
+    const BASIS: u128 = 10_000;
+
+    pub(crate) fn new(session_reward: u128, stake: u128) -> Self {
+        if stake == 0 {
+            return Self::default();
+        }
+        let apr = session_reward
+            .saturating_mul(u128::from(SESSIONS_PER_YEAR))
+            .saturating_mul(Self::BASIS)
+            / stake;
+        let daily_rate = FixedU128::saturating_from_rational(apr, Self::BASIS * 365);
+        let compounded = (FixedU128::saturating_from_integer(1u32) + daily_rate)
+            .saturating_pow(365)
+            .saturating_mul_int(Self::BASIS);
+        let apy = compounded.saturating_sub(Self::BASIS);
+        Self {
+            apr: apr.saturated_into(),
+            apy: apy.saturated_into(),
+        }
+    }
+}
+
 impl<T: Trait> Module<T> {
     pub fn validators_info(
     ) -> Vec<ValidatorInfo<T::AccountId, BalanceOf<T>, VoteWeight, T::BlockNumber>> {
@@ -62,6 +105,7 @@ impl<T: Trait> Module<T> {
         let is_validating = T::SessionInterface::validators().contains(&who);
         let reward_pot_account = T::DetermineRewardPotAccount::reward_pot_account_for(&who);
         let reward_pot_balance: BalanceOf<T> = Self::free_balance(&reward_pot_account);
+        let commission = Self::commission_of(&who);
         ValidatorInfo {
             account: who,
             profile,
@@ -70,6 +114,7 @@ impl<T: Trait> Module<T> {
             self_bonded,
             reward_pot_account,
             reward_pot_balance,
+            commission,
         }
     }
 
@@ -97,4 +142,54 @@ impl<T: Trait> Module<T> {
         let last_rebond = LastRebondOf::<T>::get(&who);
         NominatorInfo { last_rebond }
     }
+
+    /// Returns the estimated yield of nominating each active validator.
+    ///
+    /// The estimation is based on the reward of current session, the validator's share of the
+    /// total votes and the part of its reward going to the reward pot.
+    pub fn validator_yields() -> BTreeMap<T::AccountId, MiningYield> {
+        let (staking_reward, _) = Self::estimated_mining_rewards();
+        let staking_reward = staking_reward.saturated_into::<u128>();
+
+        let active_validators = Self::active_validator_votes()
+            .map(|(validator, votes)| (validator, votes.saturated_into::<u128>()))
+            .collect::<Vec<_>>();
+        let total_votes: u128 = active_validators.iter().map(|(_, votes)| votes).sum();
+        if total_votes == 0 {
+            return Default::default();
+        }
+
+        active_validators
+            .into_iter()
+            .map(|(validator, votes)| {
+                let reward = staking_reward.saturating_mul(votes) / total_votes;
+                let to_reward_pot = reward - Self::commission_of(&validator).mul_floor(reward);
+                (validator, MiningYield::new(to_reward_pot, votes))
+            })
+            .collect()
+    }
+
+    /// Returns the estimated yield of each mining asset.
+    ///
+    /// The mining power of an asset is its total issuance weighted by the fixed power per unit,
+    /// i.e., the equivalent of staked PCX. Hence the mining assets share the same estimated yield
+    /// unless the fixed power is changed.
+    pub fn mining_asset_yields() -> BTreeMap<AssetId, MiningYield> {
+        let (_, asset_mining_reward) = Self::estimated_mining_rewards();
+        let asset_mining_reward = asset_mining_reward.saturated_into::<u128>();
+
+        let asset_mining_power = T::AssetMining::asset_mining_power();
+        let total_power: u128 = asset_mining_power.iter().map(|(_, power)| power).sum();
+        if total_power == 0 {
+            return Default::default();
+        }
+
+        asset_mining_power
+            .into_iter()
+            .map(|(asset_id, power)| {
+                let reward = asset_mining_reward.saturating_mul(power) / total_power;
+                (asset_id, MiningYield::new(reward, power))
+            })
+            .collect()
+    }
 }
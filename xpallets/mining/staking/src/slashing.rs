@@ -2,6 +2,8 @@
 
 use sp_std::ops::Mul;
 
+use frame_support::storage::IterableStorageDoubleMap;
+
 use super::*;
 
 impl<T: Trait> Module<T> {
@@ -82,3 +84,152 @@ impl<T: Trait> Module<T> {
             .collect()
     }
 }
+
+impl<T: Trait> Module<T> {
+    /// Records the stake slashes of the equivocating validators and schedules them
+    /// to be applied after `SlashDeferDuration` eras.
+    ///
+    /// An offender reported again in the same era is only slashed by the extra fraction,
+    /// a cancelled slash is never re-applied.
+    pub(crate) fn schedule_stake_slashes<'a>(
+        offenders: impl Iterator<Item = (&'a T::AccountId, Perbill)>,
+    ) {
+        let active_era = Self::active_era().map(|era| era.index).unwrap_or(0);
+        let apply_at = active_era + Self::slash_defer_duration();
+        let slash_nominators = Self::nominator_slash();
+
+        let mut slashes = Self::stake_slashes(active_era);
+        for (validator, fraction) in offenders {
+            let prior = slashes
+                .iter()
+                .filter(|s| s.validator == *validator)
+                .fold(Perbill::zero(), |acc, s| acc.saturating_add(s.fraction));
+            let fraction = fraction.saturating_sub(prior);
+            if fraction.is_zero() {
+                continue;
+            }
+
+            let others = if slash_nominators {
+                NominatorsOf::<T>::iter_prefix(validator)
+                    .map(|(nominator, _)| {
+                        let value = fraction.mul(Self::bonded_to(&nominator, validator));
+                        (nominator, value)
+                    })
+                    .filter(|(_, value)| !value.is_zero())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            let mut slash = StakeSlash {
+                validator: validator.clone(),
+                fraction,
+                own: fraction.mul(Self::validator_self_bonded(validator)),
+                others,
+                apply_at,
+                status: SlashStatus::Pending,
+            };
+            debug!(
+                "[schedule_stake_slashes] era:{}, slash:{:?}",
+                active_era, slash
+            );
+
+            if apply_at == active_era {
+                Self::apply_stake_slash(&mut slash);
+            } else {
+                DeferredSlashes::append(apply_at, (active_era, slashes.len() as u32));
+                PendingStakeSlashes::<T>::mutate(validator, |pending| *pending += 1);
+            }
+            slashes.push(slash);
+        }
+        StakeSlashes::<T>::insert(active_era, slashes);
+    }
+
+    /// Applies the pending stake slashes scheduled at the start of era `era`.
+    pub(crate) fn apply_deferred_slashes(era: EraIndex) {
+        for (reported_era, index) in DeferredSlashes::take(era) {
+            StakeSlashes::<T>::mutate(reported_era, |slashes| {
+                if let Some(slash) = slashes.get_mut(index as usize) {
+                    if slash.status == SlashStatus::Pending {
+                        Self::apply_stake_slash(slash);
+                        Self::settle_pending_stake_slash(&slash.validator);
+                    }
+                }
+            });
+        }
+    }
+
+    fn apply_stake_slash(slash: &mut StakeSlash<T::AccountId, BalanceOf<T>>) {
+        Self::slash_bonded(&slash.validator, &slash.validator, slash.own);
+        for (nominator, value) in slash.others.iter() {
+            Self::slash_bonded(nominator, &slash.validator, *value);
+        }
+        slash.status = SlashStatus::Applied;
+    }
+
+    /// Decreases the number of pending stake slashes of `validator` once one is settled.
+    pub(crate) fn settle_pending_stake_slash(validator: &T::AccountId) {
+        PendingStakeSlashes::<T>::mutate_exists(validator, |pending| {
+            *pending = pending.and_then(|p| p.checked_sub(1)).filter(|p| *p > 0);
+        });
+    }
+
+    /// Slashes the stake of `nominator` to `validator`, the slashed balance goes to the treasury.
+    ///
+    /// The nomination may have been unbonded since the slash was reported, so the bonded
+    /// balance is slashed first, then the unbonded chunks from the newest to the oldest.
+    fn slash_bonded(nominator: &T::AccountId, validator: &T::AccountId, value: BalanceOf<T>) {
+        let from_bonded = value.min(Self::bonded_to(nominator, validator));
+        if !from_bonded.is_zero() {
+            Self::update_vote_weight(nominator, validator, Delta::Sub(from_bonded));
+            Self::release_bonded(nominator, from_bonded);
+        }
+
+        let from_unbonded = Self::slash_unbonded_chunks(nominator, validator, value - from_bonded);
+        if !from_unbonded.is_zero() {
+            Self::apply_unlock_unbonded_withdrawal(nominator, from_unbonded);
+        }
+
+        let slashed = from_bonded + from_unbonded;
+        if slashed.is_zero() {
+            return;
+        }
+        Self::slash_to_treasury(nominator, slashed);
+
+        Self::deposit_event(Event::<T>::StakeSlashed(
+            nominator.clone(),
+            validator.clone(),
+            slashed,
+        ));
+    }
+
+    /// Takes up to `value` from the unbonded chunks of `nominator` to `validator`, the chunk
+    /// maturing last first, returns the actual amount taken.
+    fn slash_unbonded_chunks(
+        nominator: &T::AccountId,
+        validator: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        if value.is_zero() {
+            return Zero::zero();
+        }
+        Nominations::<T>::mutate(nominator, validator, |ledger| {
+            let chunks = &mut ledger.unbonded_chunks;
+            let mut newest_first = (0..chunks.len()).collect::<Vec<_>>();
+            newest_first.sort_by_key(|&idx| sp_std::cmp::Reverse(chunks[idx].locked_until));
+
+            let mut remaining = value;
+            for idx in newest_first {
+                if remaining.is_zero() {
+                    break;
+                }
+                let taken = remaining.min(chunks[idx].value);
+                chunks[idx].value -= taken;
+                remaining -= taken;
+            }
+            chunks.retain(|chunk| !chunk.value.is_zero());
+
+            value - remaining
+        })
+    }
+}
@@ -2,7 +2,11 @@
 
 use super::*;
 use crate::mock::*;
+use crate::types::ElectionMethod;
 use frame_support::{assert_err, assert_ok, traits::OnInitialize};
+use sp_runtime::PerThing;
+use sp_staking::offence::{Kind, Offence, ReportOffence};
+use sp_std::collections::btree_set::BTreeSet;
 
 fn t_issue_pcx(to: AccountId, value: Balance) {
     XStaking::mint(&to, value);
@@ -886,3 +890,529 @@ fn migration_session_offset_should_work() {
         }
     });
 }
+
+#[test]
+fn reward_curve_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let point = |since, reward| RewardCurvePoint { since, reward };
+
+        assert_err!(
+            XStaking::set_reward_curve(Origin::root(), vec![point(10, 100), point(10, 50)]),
+            Error::<Test>::InvalidRewardCurve
+        );
+        assert_err!(
+            XStaking::set_reward_curve(
+                Origin::root(),
+                vec![point(0, 1); MAXIMUM_REWARD_CURVE_POINTS as usize + 1]
+            ),
+            Error::<Test>::TooManyRewardCurvePoints
+        );
+
+        assert_ok!(XStaking::set_reward_curve(
+            Origin::root(),
+            vec![point(10, 1000), point(20, 500)]
+        ));
+
+        // The halving schedule applies before the first point.
+        assert_eq!(XStaking::this_session_reward(9), INITIAL_REWARD as Balance);
+        assert_eq!(XStaking::this_session_reward(10), 1000);
+        assert_eq!(XStaking::this_session_reward(19), 1000);
+        assert_eq!(XStaking::this_session_reward(20), 500);
+        assert_eq!(
+            XStaking::this_session_reward(MigrationSessionOffset::get() + SESSIONS_PER_ROUND + 1),
+            500
+        );
+
+        // The vesting share applies regardless of the halving.
+        assert_eq!(XStaking::try_vesting(20, 500), 400);
+        assert_ok!(XStaking::set_vesting_share(
+            Origin::root(),
+            Some(Perbill::from_percent(10))
+        ));
+        assert_eq!(XStaking::try_vesting(20, 500), 450);
+        let after_halving = MigrationSessionOffset::get() + SESSIONS_PER_ROUND + 1;
+        assert_eq!(XStaking::try_vesting(after_halving, 500), 450);
+
+        assert_err!(
+            XStaking::set_global_distribution_ratio(
+                Origin::root(),
+                GlobalDistribution {
+                    treasury: 0,
+                    mining: 0
+                }
+            ),
+            Error::<Test>::ZeroDistributionRatio
+        );
+        let ratio = GlobalDistribution {
+            treasury: 1,
+            mining: 1,
+        };
+        assert_ok!(XStaking::set_global_distribution_ratio(
+            Origin::root(),
+            ratio
+        ));
+        assert_eq!(XStaking::global_distribution_ratio(), ratio);
+
+        // An empty curve restores the default halving schedule.
+        assert_ok!(XStaking::set_reward_curve(Origin::root(), vec![]));
+        assert_eq!(XStaking::this_session_reward(20), INITIAL_REWARD as Balance);
+    });
+}
+
+#[test]
+fn era_rewards_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        // Only the reward history of the last era is kept.
+        EraRewardsDepth::put(1);
+
+        t_start_session(1);
+
+        let rewards = XStaking::eras_rewards(0);
+        assert_eq!(rewards.minted, XStaking::this_session_reward(1));
+        assert_eq!(
+            rewards.minted,
+            rewards.treasury
+                + rewards.vesting
+                + rewards.staking
+                + rewards.asset_mining.values().sum::<Balance>()
+        );
+        assert_eq!(
+            rewards.staking,
+            rewards.validators.values().sum::<Balance>()
+        );
+        assert_eq!(
+            rewards.validators.keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+
+        t_start_session(2);
+        assert_eq!(
+            XStaking::eras_rewards(0).minted,
+            XStaking::this_session_reward(1) + XStaking::this_session_reward(2)
+        );
+
+        t_start_session(9);
+        let active_era = XStaking::active_era().unwrap().index;
+        assert!(active_era > 1);
+        assert!((0..active_era).all(|era| !ErasRewards::<Test>::contains_key(era)));
+    });
+}
+
+#[test]
+fn mining_yields_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(MiningYield::new(100, 0), MiningYield::default());
+
+        // The reward of a year equals to the stake.
+        let MiningYield { apr, apy } = MiningYield::new(1, SESSIONS_PER_YEAR as u128);
+        assert_eq!(apr, 10_000);
+        assert!((17_140..=17_150).contains(&apy));
+
+        t_start_session(1);
+        let yields = XStaking::validator_yields();
+        assert_eq!(yields.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(yields.values().all(|y| y.apr > 0 && y.apy >= y.apr));
+
+        // No asset mining in the mock runtime.
+        assert!(XStaking::mining_asset_yields().is_empty());
+    });
+}
+
+#[test]
+fn commission_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(XStaking::commission_of(&1), Perbill::from_percent(10));
+        assert_err!(
+            XStaking::set_commission(Origin::signed(1), Perbill::from_percent(11)),
+            <Error<Test>>::ExcessiveCommission
+        );
+        assert_err!(
+            XStaking::set_commission(Origin::signed(1), Perbill::from_percent(8)),
+            <Error<Test>>::ExcessiveCommissionChange
+        );
+        assert_err!(
+            XStaking::set_commission(Origin::signed(123), Perbill::from_percent(9)),
+            <Error<Test>>::NotValidator
+        );
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(1),
+            Perbill::from_percent(9)
+        ));
+        assert_err!(
+            XStaking::set_commission(Origin::signed(1), Perbill::from_percent(8)),
+            <Error<Test>>::CommissionAlreadyChanged
+        );
+        assert_eq!(XStaking::commission_of(&1), Perbill::from_percent(9));
+        assert_eq!(
+            XStaking::validator_info_of(1).commission,
+            Perbill::from_percent(9)
+        );
+
+        // the reward is split according to the commission
+        let free_before = Balances::free_balance(&1);
+        t_start_session(1);
+        let to_validator = Balances::free_balance(&1) - free_before;
+        let to_pot = t_reward_pot_balance(1);
+        assert_eq!(
+            to_validator,
+            Perbill::from_percent(9).mul_floor(to_validator + to_pot)
+        );
+
+        // the commission can be changed again in a new era
+        t_start_session(6);
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(1),
+            Perbill::from_percent(8)
+        ));
+
+        // all the commissions are capped by the maximum
+        assert_ok!(XStaking::set_commission_limits(
+            Origin::root(),
+            CommissionLimits {
+                maximum: Perbill::from_percent(5),
+                max_change_per_era: Perbill::from_percent(1),
+            }
+        ));
+        assert_eq!(XStaking::commission_of(&1), Perbill::from_percent(5));
+        assert_eq!(XStaking::commission_of(&2), Perbill::from_percent(5));
+    });
+}
+
+#[test]
+fn restake_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        t_issue_pcx(t_1, 100);
+        t_issue_pcx(t_2, 100);
+        assert_ok!(t_bond(t_1, 1, 10));
+        assert_ok!(t_bond(t_2, 1, 10));
+        assert_ok!(XStaking::set_restake(Origin::signed(t_1), true));
+        assert!(XStaking::restake(&t_1));
+
+        // the dividend is left free if the validator can not accept more votes
+        t_start_session(2);
+        let pot_before = t_reward_pot_balance(1);
+        assert_ok!(XStaking::claim(Origin::signed(t_1), 1));
+        let dividend = pot_before - t_reward_pot_balance(1);
+        assert!(dividend > 0);
+        assert_eq!(XStaking::free_balance(&t_1), 100 + dividend);
+        assert_eq!(XStaking::bonded_to(&t_1, &1), 10);
+
+        UpperBoundFactorOfAcceptableVotes::put(u32::max_value());
+
+        // only the nominators who prefer to restake are claimed
+        t_start_session(3);
+        assert_err!(
+            XStaking::payout_and_restake(
+                Origin::signed(3333),
+                vec![(t_1, 1); MAXIMUM_PAYOUT_BATCH_SIZE as usize + 1]
+            ),
+            <Error<Test>>::TooManyPayouts
+        );
+        let pot_before = t_reward_pot_balance(1);
+        assert_ok!(XStaking::payout_and_restake(
+            Origin::signed(3333),
+            vec![(t_1, 1), (t_2, 1)]
+        ));
+        let dividend2 = pot_before - t_reward_pot_balance(1);
+        assert!(dividend2 > 0);
+        assert_eq!(XStaking::bonded_to(&t_1, &1), 10 + dividend2);
+        assert_eq!(XStaking::staked_of(&t_1), 10 + dividend2);
+        assert_eq!(XStaking::free_balance(&t_1), 100 + dividend + dividend2);
+        assert_eq!(XStaking::bonded_to(&t_2, &1), 10);
+        assert_eq!(XStaking::free_balance(&t_2), 100);
+
+        assert_ok!(XStaking::set_restake(Origin::signed(t_1), false));
+        assert!(!XStaking::restake(&t_1));
+    });
+}
+
+#[test]
+fn claim_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 1, 10));
+        assert_ok!(t_bond(t_1, 2, 10));
+        assert_ok!(t_bond(t_1, 3, 10));
+
+        t_start_session(2);
+        let dividends = XStaking::staking_dividend_of(t_1);
+        assert_eq!(dividends.len(), 3);
+        assert!(dividends.values().all(|dividend| *dividend > 0));
+
+        assert_err!(
+            XStaking::claim_all(Origin::signed(t_1), 2),
+            <Error<Test>>::InvalidNominationCount
+        );
+        assert_ok!(XStaking::claim_all(Origin::signed(t_1), 3));
+        assert_eq!(
+            XStaking::free_balance(&t_1),
+            100 + dividends.values().sum::<Balance>()
+        );
+        assert!(XStaking::staking_dividend_of(t_1)
+            .values()
+            .all(|dividend| *dividend == 0));
+    });
+}
+
+#[test]
+fn seq_phragmen_election_should_work() {
+    let elect = |method: ElectionMethod| {
+        let mut validators = Vec::new();
+        ExtBuilder::default().build_and_execute(|| {
+            t_issue_pcx(1111, 300);
+            t_issue_pcx(2222, 158);
+            // total votes: 1: 10, 2: 20 + 158, 3: 30 + 150, 4: 40 + 150
+            assert_ok!(t_bond(1111, 3, 150));
+            assert_ok!(t_bond(1111, 4, 150));
+            assert_ok!(t_bond(2222, 2, 158));
+            assert_ok!(XStaking::set_validator_count(Origin::root(), 2));
+            assert_ok!(XStaking::set_election_method(Origin::root(), method));
+
+            t_start_session(3);
+            validators = Session::validators();
+            validators.sort();
+        });
+        validators
+    };
+
+    assert_eq!(elect(ElectionMethod::TopVotes), vec![3, 4]);
+    // The budget of 1111 is shared by 3 and 4 once 4 is elected, so 2 is preferred.
+    assert_eq!(elect(ElectionMethod::SeqPhragmen), vec![2, 4]);
+}
+
+#[test]
+fn top_election_voters_should_keep_self_votes() {
+    let candidates = vec![1, 2].into_iter().collect::<BTreeSet<AccountId>>();
+    let voters = vec![
+        (1, (10, vec![1])),
+        (2, (20, vec![2])),
+        (1111, (5, vec![1])),
+        (2222, (50, vec![1, 2])),
+        (3333, (20, vec![2])),
+        (4444, (20, vec![1])),
+    ]
+    .into_iter()
+    .collect::<BTreeMap<AccountId, (u64, Vec<AccountId>)>>();
+
+    let voters = XStaking::top_election_voters(voters, &candidates, 2)
+        .into_iter()
+        .map(|(voter, _, _)| voter)
+        .collect::<Vec<_>>();
+    // The nominators are picked by the budget rather than the order of the candidates.
+    assert_eq!(voters, vec![1, 2, 2222, 3333]);
+}
+
+struct TestEquivocation(Vec<AccountId>);
+
+impl Offence<(AccountId, AccountId)> for TestEquivocation {
+    const ID: Kind = *b"babe:equivocatio";
+    type TimeSlot = u64;
+
+    fn offenders(&self) -> Vec<(AccountId, AccountId)> {
+        self.0
+            .iter()
+            .map(|offender| (*offender, *offender))
+            .collect()
+    }
+
+    fn session_index(&self) -> SessionIndex {
+        0
+    }
+
+    fn validator_set_count(&self) -> u32 {
+        4
+    }
+
+    fn time_slot(&self) -> Self::TimeSlot {
+        0
+    }
+
+    fn slash_fraction(_offenders_count: u32, _validator_set_count: u32) -> Perbill {
+        Perbill::from_percent(10)
+    }
+}
+
+#[test]
+fn stake_slash_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 2, 50));
+        assert_ok!(XStaking::set_nominator_slash(Origin::root(), true));
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 1));
+
+        assert!(XStaking::nominators_of(2, t_1));
+
+        let report_equivocation = |offenders: Vec<AccountId>| {
+            assert_ok!(EquivocationReporter::<Test, ()>::report_offence(
+                vec![],
+                TestEquivocation(offenders)
+            ));
+        };
+
+        report_equivocation(vec![2, 3]);
+        // The same offender reported again with no larger fraction is not slashed twice.
+        report_equivocation(vec![2]);
+
+        let slashes = XStaking::stake_slashes(0);
+        assert_eq!(slashes.len(), 2);
+        assert_eq!(slashes[0].own, 2);
+        assert_eq!(slashes[0].others, vec![(t_1, 5)]);
+        assert_eq!(slashes[0].apply_at, 1);
+        assert_eq!(slashes[1].own, 3);
+        assert_eq!(XStaking::deferred_slashes(1), vec![(0, 0), (0, 1)]);
+        assert_eq!(XStaking::pending_stake_slashes(3), 1);
+
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), 0, vec![2]),
+            <Error<Test>>::InvalidSlashIndex
+        );
+        assert_ok!(XStaking::cancel_deferred_slash(Origin::root(), 0, vec![1]));
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), 0, vec![1]),
+            <Error<Test>>::SlashNotPending
+        );
+
+        assert_eq!(XStaking::pending_stake_slashes(3), 0);
+
+        // Nothing is slashed until the deferral window ends.
+        assert_eq!(XStaking::bonded_to(&t_1, &2), 50);
+
+        // The nominations can't be moved away at once during the deferral window.
+        assert_ok!(XStaking::set_instant_unbond_fee(
+            Origin::root(),
+            Some(Perbill::from_percent(10))
+        ));
+        assert_err!(
+            XStaking::instant_unbond(Origin::signed(t_1), 2, 10),
+            <Error<Test>>::PendingStakeSlash
+        );
+        assert_err!(
+            XStaking::rebond(Origin::signed(t_1), 2, 4, 10),
+            <Error<Test>>::PendingStakeSlash
+        );
+        assert_ok!(t_unbond(t_1, 2, 48));
+
+        t_start_session(3);
+        assert_eq!(XStaking::active_era().map(|era| era.index), Some(1));
+
+        let slashes = XStaking::stake_slashes(0);
+        assert_eq!(slashes[0].status, SlashStatus::Applied);
+        assert_eq!(slashes[1].status, SlashStatus::Cancelled);
+        assert_eq!(XStaking::pending_stake_slashes(2), 0);
+        assert_eq!(XStaking::bonded_to(&2, &2), 18);
+        assert_eq!(XStaking::bonded_to(&3, &3), 30);
+        // The unbonded chunk is slashed for the rest of the slash.
+        assert_eq!(XStaking::bonded_to(&t_1, &2), 0);
+        assert_eq!(
+            <Nominations<Test>>::get(t_1, 2)
+                .unbonded_chunks
+                .iter()
+                .map(|chunk| chunk.value)
+                .collect::<Vec<_>>(),
+            vec![45]
+        );
+        assert_eq!(XStaking::total_votes_of(&2), 18);
+        assert_eq!(Balances::free_balance(&t_1), 95);
+        assert_eq!(
+            <Locks<Test>>::get(t_1).get(&LockedType::BondedWithdrawal),
+            Some(&45)
+        );
+        assert!(XStaking::deferred_slashes(1).is_empty());
+    });
+}
+
+#[test]
+fn slash_defer_duration_should_be_shorter_than_bonding_duration() {
+    ExtBuilder::default().build_and_execute(|| {
+        // An era lasts 3 sessions of 50 blocks.
+        assert_ok!(XStaking::set_bonding_duration(Origin::root(), 600));
+        assert_err!(
+            XStaking::set_slash_defer_duration(Origin::root(), 4),
+            Error::<Test>::SlashDeferDurationTooLong
+        );
+        assert_err!(
+            XStaking::set_slash_defer_duration(Origin::root(), 5),
+            Error::<Test>::SlashDeferDurationTooLong
+        );
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 3));
+        assert_eq!(XStaking::slash_defer_duration(), 3);
+    });
+}
+
+#[test]
+fn instant_unbond_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 2, 50));
+
+        assert_err!(
+            XStaking::instant_unbond(Origin::signed(t_1), 2, 30),
+            <Error<Test>>::InstantUnbondDisabled
+        );
+
+        assert_ok!(XStaking::set_instant_unbond_fee(
+            Origin::root(),
+            Some(Perbill::from_percent(10))
+        ));
+        assert_err!(
+            XStaking::instant_unbond(Origin::signed(2), 2, 10),
+            <Error<Test>>::InstantUnbondSelfBondedNotAllowed
+        );
+        assert_err!(
+            XStaking::instant_unbond(Origin::signed(t_1), 2, 60),
+            <Error<Test>>::InvalidUnbondBalance
+        );
+
+        let treasury_balance = Balances::free_balance(&TREASURY_ACCOUNT);
+        assert_ok!(XStaking::instant_unbond(Origin::signed(t_1), 2, 30));
+        assert_eq!(XStaking::bonded_to(&t_1, &2), 20);
+        assert_eq!(XStaking::total_votes_of(&2), 20 + 20);
+        assert!(<Nominations<Test>>::get(t_1, 2).unbonded_chunks.is_empty());
+        assert_eq!(
+            <Locks<Test>>::get(t_1),
+            vec![(LockedType::Bonded, 20)].into_iter().collect()
+        );
+        assert_eq!(Balances::free_balance(&t_1), 97);
+        assert_eq!(
+            Balances::free_balance(&TREASURY_ACCOUNT),
+            treasury_balance + 3
+        );
+    });
+}
+
+#[test]
+fn unbonded_chunks_should_merge() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 2, 50));
+        MaximumUnbondedChunkSize::put(2);
+
+        let bonding_duration = XStaking::bonding_duration();
+        assert_ok!(t_unbond(t_1, 2, 5));
+        t_system_block_number_inc(1);
+        assert_ok!(t_unbond(t_1, 2, 5));
+        t_system_block_number_inc(1);
+        // The chunk limit is reached, merged into the latest chunk.
+        assert_ok!(t_unbond(t_1, 2, 5));
+
+        assert_eq!(
+            <Nominations<Test>>::get(t_1, 2).unbonded_chunks,
+            vec![
+                Unbonded {
+                    value: 5,
+                    locked_until: 1 + bonding_duration
+                },
+                Unbonded {
+                    value: 10,
+                    locked_until: 3 + bonding_duration
+                },
+            ]
+        );
+    });
+}
@@ -1,6 +1,6 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use sp_std::vec::Vec;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 use codec::{Decode, Encode};
 #[cfg(feature = "std")]
@@ -8,15 +8,17 @@ use serde::{Deserialize, Serialize};
 
 use sp_runtime::{
     traits::{SaturatedConversion, Saturating},
-    DispatchError, DispatchResult, RuntimeDebug,
+    DispatchError, DispatchResult, Perbill, RuntimeDebug,
 };
 
 use chainx_primitives::{AssetId, ReferralId};
 use xp_logging::debug;
 use xp_mining_common::{RewardPotAccountFor, WeightType};
-use xp_mining_staking::MiningPower;
+use xp_mining_staking::{MiningPower, SessionIndex};
 
-use crate::{AssetMining, BalanceOf, EraIndex, Event, Module, Trait};
+use crate::{
+    constants::DEFAULT_VALIDATOR_COMMISSION, AssetMining, BalanceOf, EraIndex, Event, Module, Trait,
+};
 
 pub type VoteWeight = WeightType;
 
@@ -110,6 +112,53 @@ pub struct ValidatorProfile<BlockNumber> {
     pub referral_id: ReferralId,
 }
 
+/// The commission rate that a validator takes from its reward.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ValidatorCommission {
+    /// The proportion of the reward that goes to the validator directly.
+    pub rate: Perbill,
+    /// The era in which the rate was changed last time.
+    pub updated_at: EraIndex,
+}
+
+/// The restrictions on the validator commission set by the governance.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CommissionLimits {
+    /// The maximum commission rate of a validator.
+    pub maximum: Perbill,
+    /// The maximum change of the commission rate in one era.
+    pub max_change_per_era: Perbill,
+}
+
+impl Default for CommissionLimits {
+    fn default() -> Self {
+        Self {
+            maximum: DEFAULT_VALIDATOR_COMMISSION,
+            max_change_per_era: Perbill::from_percent(1),
+        }
+    }
+}
+
+/// The method to elect the validators from the qualified candidates.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ElectionMethod {
+    /// Take the candidates with the most total votes.
+    TopVotes,
+    /// Sequential Phragmén, which tends to spread the stake evenly among the winners.
+    SeqPhragmen,
+}
+
+impl Default for ElectionMethod {
+    fn default() -> Self {
+        ElectionMethod::TopVotes
+    }
+}
+
 /// Information regarding the active era (era in used in session).
 #[derive(Encode, Decode, RuntimeDebug)]
 pub struct ActiveEraInfo {
@@ -142,6 +191,19 @@ impl Default for Forcing {
     }
 }
 
+/// A point of the session reward curve set by the governance.
+///
+/// The session reward stays at `reward` since session `since` until the next point.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RewardCurvePoint<Balance> {
+    /// The first session this reward applies to.
+    pub since: SessionIndex,
+    /// The total reward of each session.
+    pub reward: Balance,
+}
+
 /// Top level shares of various reward destinations.
 #[derive(Copy, Clone, PartialEq, Eq, Default, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -222,6 +284,55 @@ impl MiningDistribution {
     }
 }
 
+/// The rewards minted in an era, accumulated over its sessions.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EraRewards<AccountId: Ord, Balance> {
+    /// Total amount of the newly minted PCX.
+    pub minted: Balance,
+    /// The share of treasury, including the unpaid asset mining rewards.
+    pub treasury: Balance,
+    /// The share of the vesting schedule.
+    pub vesting: Balance,
+    /// The share of all the active validators.
+    pub staking: Balance,
+    /// The share of each mining asset.
+    pub asset_mining: BTreeMap<AssetId, Balance>,
+    /// The reward of each validator, including the commission.
+    pub validators: BTreeMap<AccountId, Balance>,
+}
+
+/// The status of a slash on the bonded stake.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SlashStatus {
+    /// Waiting to be applied at the end of the deferral window.
+    Pending,
+    /// The bonded stake has been slashed.
+    Applied,
+    /// Cancelled by the governance.
+    Cancelled,
+}
+
+/// A slash on the bonded stake of an equivocating validator and its nominators.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct StakeSlash<AccountId, Balance> {
+    /// The equivocating validator.
+    pub validator: AccountId,
+    /// The proportion of the bonded stake to be slashed.
+    pub fraction: Perbill,
+    /// The slash on the self-bonded balance of validator.
+    pub own: Balance,
+    /// The slashes on the nominators of validator.
+    pub others: Vec<(AccountId, Balance)>,
+    /// The era at the start of which the slash is applied.
+    pub apply_at: EraIndex,
+    pub status: SlashStatus,
+}
+
 /// Result of performing a slash operation.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub enum SlashOutcome<Balance> {
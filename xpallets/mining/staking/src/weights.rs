@@ -44,6 +44,21 @@ pub trait WeightInfo {
     fn set_validator_bonding_duration() -> Weight;
     fn set_minimum_penalty() -> Weight;
     fn set_sessions_per_era() -> Weight;
+    fn set_commission() -> Weight;
+    fn set_commission_limits() -> Weight;
+    fn set_election_method() -> Weight;
+    fn set_restake() -> Weight;
+    fn payout_and_restake(n: u32) -> Weight;
+    fn claim_all(n: u32) -> Weight;
+    fn cancel_deferred_slash(n: u32) -> Weight;
+    fn set_slash_defer_duration() -> Weight;
+    fn set_nominator_slash() -> Weight;
+    fn instant_unbond() -> Weight;
+    fn set_instant_unbond_fee() -> Weight;
+    fn set_reward_curve(n: u32) -> Weight;
+    fn set_vesting_share() -> Weight;
+    fn set_global_distribution_ratio() -> Weight;
+    fn new_era(c: u32, v: u32) -> Weight;
 }
 
 /// Weights for xpallet_mining_staking using the Substrate node and recommended hardware.
@@ -107,6 +122,74 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_sessions_per_era() -> Weight {
         (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_commission() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_commission_limits() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_election_method() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_restake() -> Weight {
+        (20_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn payout_and_restake(n: u32) -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add((382_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((16 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+    }
+    fn claim_all(n: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((160_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+    }
+    fn cancel_deferred_slash(n: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (6_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_nominator_slash() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn instant_unbond() -> Weight {
+        (190_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn set_instant_unbond_fee() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_reward_curve(n: u32) -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add((30_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_vesting_share() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_global_distribution_ratio() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn new_era(c: u32, v: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((1_500_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add((600_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -169,4 +252,72 @@ impl WeightInfo for () {
     fn set_sessions_per_era() -> Weight {
         (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_commission() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_commission_limits() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_election_method() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_restake() -> Weight {
+        (20_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn payout_and_restake(n: u32) -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add((382_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((16 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+    }
+    fn claim_all(n: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((160_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+    }
+    fn cancel_deferred_slash(n: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (6_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_nominator_slash() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn instant_unbond() -> Weight {
+        (190_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn set_instant_unbond_fee() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_reward_curve(n: u32) -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add((30_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_vesting_share() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_global_distribution_ratio() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn new_era(c: u32, v: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((1_500_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add((600_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
 }
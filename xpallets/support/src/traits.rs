@@ -1,5 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
+use sp_std::prelude::Vec;
+
 pub trait MultisigAddressFor<AccountId> {
     fn calc_multisig(accounts: &[AccountId], threshold: u16) -> AccountId;
 }
@@ -18,6 +20,9 @@ pub trait Validator<AccountId> {
     fn is_validator(who: &AccountId) -> bool;
 
     fn validator_for(name: &[u8]) -> Option<AccountId>;
+
+    /// Returns the active validators sorted by the total nominations (self-bonded included).
+    fn ranked_validators() -> Vec<AccountId>;
 }
 
 impl<AccountId> Validator<AccountId> for () {
@@ -28,6 +33,31 @@ impl<AccountId> Validator<AccountId> for () {
     fn validator_for(_: &[u8]) -> Option<AccountId> {
         None
     }
+
+    fn ranked_validators() -> Vec<AccountId> {
+        Vec::new()
+    }
+}
+
+/// Hooks for doing stuff when a new era of staking starts.
+pub trait OnNewEra {
+    fn on_new_era(era_index: u32);
+}
+
+impl OnNewEra for () {
+    fn on_new_era(_: u32) {}
+}
+
+/// Slash the validators out of the staking, the slashed balance goes to the treasury.
+pub trait ValidatorSlasher<AccountId, Balance> {
+    /// Slash the validator by `value` at most, returns the balance actually slashed.
+    fn slash(who: &AccountId, value: Balance) -> Balance;
+}
+
+impl<AccountId, Balance: Default> ValidatorSlasher<AccountId, Balance> for () {
+    fn slash(_: &AccountId, _: Balance) -> Balance {
+        Default::default()
+    }
 }
 
 /// This trait provides a simple way to get the treasury account.